@@ -4,7 +4,7 @@ use sha2::Sha256;
 
 use fri::fields::goldilocks::Fq;
 use fri::prover::generate_proof;
-use fri::verifier::verify;
+use fri::verifier::{verify, FriConfig};
 
 fn main() {
     let coeff = vec![
@@ -18,11 +18,28 @@ fn main() {
     let poly = DensePolynomial::from_coefficients_vec(coeff);
 
     let blowup_factor: usize = 2;
+    let folding_factor: usize = 2;
+    let max_remainder_degree: usize = 1;
+    let grinding_bits: usize = 8;
     let number_of_queries: usize = 2;
     println!("Generate proof...");
-    let proof = generate_proof::<Sha256, Fq>(poly, blowup_factor, number_of_queries);
+    let proof = generate_proof::<Sha256, Fq>(
+        poly,
+        blowup_factor,
+        folding_factor,
+        max_remainder_degree,
+        grinding_bits,
+        number_of_queries,
+    );
     println!("Verify....");
-    let result = verify::<Sha256, Fq>(proof);
+    let config = FriConfig::new(
+        blowup_factor,
+        folding_factor,
+        max_remainder_degree,
+        grinding_bits,
+        number_of_queries,
+    );
+    let result = verify::<Sha256, Fq>(proof, &config);
 
     assert!(result.is_ok());
     println!("Accepted!");
@@ -1,17 +1,18 @@
-use ark_ff::PrimeField;
-use rand::prelude::StdRng;
-use rand::SeedableRng;
-use sha2::Digest;
+use ark_ff::{BigInteger, PrimeField};
 use std::marker::PhantomData;
 
+use crate::hasher::Hasher;
+
 /// A transcript for generating cryptographic challenges using the Fiat-Shamir transform with a cryptographic hash function and a prime field.
 ///
 /// The `Transcript` struct maintains an internal state and accumulates data to produce cryptographic challenges using the Fiat-Shamir transform.
 /// It utilizes a cryptographic hash function `T` and a prime field `F` for generating challenges.
+/// The state is a full digest of `T`, which is only reduced into the field when a challenge is drawn.
 ///
 /// # Type Parameters
 ///
-/// - `T`: A type implementing the `Digest` trait from the `sha2` crate, used as the cryptographic hash function.
+/// - `T`: A type implementing the `Hasher` trait, used as the cryptographic hash function. Any `Digest`
+///   such as `Sha256` is a `Hasher`, as are the algebraic hashes of `fri::hashes`.
 /// - `F`: A prime field type implementing the `PrimeField` trait from the `ark_ff` crate, used for generating challenges.
 ///
 /// # Fiat-Shamir Transform
@@ -37,18 +38,17 @@ use std::marker::PhantomData;
 /// let c1 = transcript.generate_a_challenge();
 /// ```
 #[derive(Default, Clone)]
-pub struct Transcript<T: Digest + Default, F: PrimeField> {
-    data: Option<Vec<u8>>,
+pub struct Transcript<T: Hasher<F>, F: PrimeField> {
+    state: T::Digest,
     index: u64,
     generated: bool,
 
     #[allow(dead_code)]
     /// Phantom data for annotation purposes.
     _phantom_data: PhantomData<T>,
-    _phantom_data2: PhantomData<F>,
 }
 
-impl<T: Digest + Default, F: PrimeField> Transcript<T, F> {
+impl<T: Hasher<F>, F: PrimeField> Transcript<T, F> {
     /// Constructs a new `Transcript` initialized with the given message value.
     ///
     /// # Parameters
@@ -60,42 +60,52 @@ impl<T: Digest + Default, F: PrimeField> Transcript<T, F> {
     /// A new `Transcript` initialized with the given message value.
     pub fn new(message: F) -> Self {
         let mut transcript = Self {
-            data: None,
+            state: T::Digest::default(),
             index: 0,
             generated: true,
             _phantom_data: Default::default(),
-            _phantom_data2: Default::default(),
         };
         transcript.digest(message);
         transcript
     }
 }
 
-impl<T: Digest + Default, F: PrimeField> Transcript<T, F> {
+impl<T: Hasher<F>, F: PrimeField> Transcript<T, F> {
     /// Updates the transcript by digesting the provided message.
     ///
     /// # Parameters
     ///
     /// - `message`: A message of type `F` to be digested into the transcript.
     pub fn digest(&mut self, message: F) {
-        let mut hasher = T::default();
-        hasher.update(self.data.take().unwrap_or_default());
-        hasher.update(self.index.to_le_bytes());
-        hasher.update(message.to_string());
-        self.data = Some(hasher.finalize().to_vec());
+        self.absorb(&T::hash_elements(&[F::from(self.index), message]));
+    }
+
+    /// Updates the transcript by digesting a hash, such as the root of a Merkle tree.
+    ///
+    /// # Parameters
+    ///
+    /// - `hash`: A digest of the hasher `T` to be digested into the transcript.
+    pub fn digest_hash(&mut self, hash: &T::Digest) {
+        self.absorb(hash);
+    }
+
+    /// Chains the state with a digest.
+    fn absorb(&mut self, digest: &T::Digest) {
+        self.state = T::hash_node(&self.state, digest);
         self.index += 1;
         self.generated = false;
     }
 
-    fn generate_rng_with_seed(&mut self) -> StdRng {
+    /// Hashes the state together with a list of elements, without updating it.
+    fn hash_with_state(&self, elements: &[F]) -> F {
+        T::reduce(&T::hash_node(&self.state, &T::hash_elements(elements)))
+    }
+
+    fn check_fed(&mut self) {
         if self.generated {
             panic!("I'm hungry! Feed me something first");
         }
         self.generated = true;
-        let mut seed: [u8; 8] = Default::default();
-        seed.copy_from_slice(&self.data.clone().unwrap_or_default()[0..8]);
-        let seed = u64::from_le_bytes(seed);
-        StdRng::seed_from_u64(seed)
     }
 
     /// Generates a cryptographic challenge using the internal state of the transcript.
@@ -104,8 +114,7 @@ impl<T: Digest + Default, F: PrimeField> Transcript<T, F> {
     ///
     /// A cryptographic challenge of type `F`.
     pub fn generate_a_challenge(&mut self) -> F {
-        let mut rng = self.generate_rng_with_seed();
-        F::rand(&mut rng)
+        self.generate_challenges(1)[0]
     }
 
     /// Generates multiple cryptographic challenges using the internal state of the transcript.
@@ -118,8 +127,10 @@ impl<T: Digest + Default, F: PrimeField> Transcript<T, F> {
     ///
     /// A vector containing the generated cryptographic challenges.
     pub fn generate_challenges(&mut self, number: usize) -> Vec<F> {
-        let mut rng = self.generate_rng_with_seed();
-        (0..number).map(|_| F::rand(&mut rng)).collect()
+        self.check_fed();
+        (0..number as u64)
+            .map(|i| self.hash_with_state(&[F::from(i)]))
+            .collect()
     }
 
     /// Generates multiple cryptographic challenges as `usize` values using the internal state of the transcript.
@@ -139,10 +150,61 @@ impl<T: Digest + Default, F: PrimeField> Transcript<T, F> {
     }
 }
 
+impl<T: Hasher<F>, F: PrimeField> Transcript<T, F> {
+    /// Searches for a proof-of-work nonce such that the hash of the internal state and the nonce
+    /// has `grinding_bits` leading zero bits, then digests the nonce.
+    ///
+    /// # Parameters
+    ///
+    /// - `grinding_bits`: The number of leading zero bits required, smaller than the field size.
+    ///
+    /// # Returns
+    ///
+    /// The smallest nonce satisfying the proof of work.
+    pub fn grind(&mut self, grinding_bits: usize) -> u64 {
+        assert!(
+            grinding_bits < F::MODULUS_BIT_SIZE as usize,
+            "too many grinding bits"
+        );
+        let nonce = (0..)
+            .find(|nonce| self.check_proof_of_work(*nonce, grinding_bits))
+            .unwrap();
+        self.digest(F::from(nonce));
+        nonce
+    }
+
+    /// Checks the proof-of-work nonce found by `grind`, then digests the nonce.
+    ///
+    /// # Parameters
+    ///
+    /// - `nonce`: The proof-of-work nonce.
+    /// - `grinding_bits`: The number of leading zero bits required.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the hash of the internal state and the nonce has `grinding_bits` leading zero bits.
+    pub fn verify_grinding(&mut self, nonce: u64, grinding_bits: usize) -> bool {
+        if !self.check_proof_of_work(nonce, grinding_bits) {
+            return false;
+        }
+        self.digest(F::from(nonce));
+        true
+    }
+
+    /// Hashes the internal state, the number of grinding bits and the nonce, and counts the
+    /// leading zero bits of the hash within the field size.
+    fn check_proof_of_work(&self, nonce: u64, grinding_bits: usize) -> bool {
+        let hash = self.hash_with_state(&[F::from(grinding_bits as u64), F::from(nonce)]);
+        let leading_zeros = F::MODULUS_BIT_SIZE - hash.into_bigint().num_bits();
+        leading_zeros as usize >= grinding_bits
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Transcript;
     use crate::fields::goldilocks::Fq;
+    use crate::hasher::Hasher;
     use ark_ff::Field;
     use rand::prelude::StdRng;
     use rand::{Rng, SeedableRng};
@@ -244,4 +306,55 @@ mod tests {
         assert_eq!(g.len(), size);
         assert_eq!(g, g2);
     }
+
+    #[test]
+    fn test_digest_hash_full_width() {
+        // two digests reduced to the same field element lead to different challenges
+        let zero = sha2::digest::Output::<Sha256>::default();
+        let mut modulus = zero;
+        modulus[..8].copy_from_slice(&0xFFFF_FFFF_0000_0001u64.to_le_bytes());
+        assert_eq!(
+            <Sha256 as Hasher<Fq>>::reduce(&zero),
+            <Sha256 as Hasher<Fq>>::reduce(&modulus)
+        );
+
+        let mut transcript = Transcript::<Sha256, Fq>::new(SECRET_X);
+        let mut transcript2 = Transcript::<Sha256, Fq>::new(SECRET_X);
+        transcript.digest_hash(&zero);
+        transcript2.digest_hash(&modulus);
+        assert_ne!(
+            transcript.generate_challenges(2),
+            transcript2.generate_challenges(2)
+        );
+    }
+
+    #[test]
+    fn test_grinding() {
+        let mut transcript = Transcript::<Sha256, Fq>::new(SECRET_X);
+        let mut transcript2 = Transcript::<Sha256, Fq>::new(SECRET_X);
+        transcript.digest(Fq::from(31313213));
+        transcript2.digest(Fq::from(31313213));
+
+        let nonce = transcript.grind(8);
+        assert!(transcript2.verify_grinding(nonce, 8));
+        // the nonce is digested, so both transcripts generate the same challenges
+        assert_eq!(
+            transcript.generate_challenges(3),
+            transcript2.generate_challenges(3)
+        );
+    }
+
+    #[test]
+    fn test_grinding_wrong_nonce() {
+        let mut transcript = Transcript::<Sha256, Fq>::new(SECRET_X);
+        transcript.digest(Fq::from(31313213));
+        let nonce = transcript.clone().grind(12);
+        // the nonce found is the smallest one
+        for wrong_nonce in 0..nonce {
+            assert!(!transcript.clone().verify_grinding(wrong_nonce, 12));
+        }
+        assert!(transcript.clone().verify_grinding(nonce, 12));
+        // without grinding, any nonce is accepted
+        assert!(transcript.verify_grinding(nonce + 1, 0));
+    }
 }
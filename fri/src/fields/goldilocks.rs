@@ -1,3 +1,5 @@
+#![allow(non_local_definitions)]
+
 use ark_ff::fields::{Fp64, MontBackend, MontConfig};
 
 // this Goldilocks implementation is inspired by Electron-Labs: https://github.com/Electron-Labs/fri-commitment
@@ -2,29 +2,38 @@ use ark_ff::PrimeField;
 use ark_poly::univariate::DensePolynomial;
 use ark_poly::{EvaluationDomain, GeneralEvaluationDomain, Polynomial};
 
+use crate::hasher::Hasher;
 use crate::merkle_tree::MerkleTree;
 
 /// Represents the state of FRI variables in each interaction.
 #[derive(Clone)]
-pub struct FriLayer<F: PrimeField> {
+pub struct FriLayer<F: PrimeField, H: Hasher<F>> {
     /// Values of the committed polynomial evaluated at a subset Omega of the field F.
     pub evaluations: Vec<F>,
-    /// Merkle tree constructed from the evaluated values of the committed polynomial at a subset Omega of F.
-    pub merkle_tree: MerkleTree<F>,
+    /// Merkle tree whose leaves are the evaluations over each folding coset of Omega.
+    pub merkle_tree: MerkleTree<F, H>,
     /// Coset value used for polynomial evaluation.
+    #[allow(dead_code)]
     pub coset: F,
     /// Size of the domain subset Omega.
     pub domain_size: usize,
+    /// Number of points of Omega folded into a single point of the next layer.
+    pub folding_factor: usize,
 }
 
-impl<F: PrimeField> FriLayer<F> {
+impl<F: PrimeField, H: Hasher<F>> FriLayer<F, H> {
     /// Constructs a new FRI layer from a given dense polynomial, coset value, and domain size.
     ///
+    /// The leaf `i` of the Merkle tree bundles the evaluations at the indices
+    /// `i, i + n / a, ..., i + (a - 1) * n / a`, where `n` is the domain size and `a` the folding
+    /// factor, which are the points mapped to the same point of the next layer.
+    ///
     /// # Arguments
     ///
     /// * `poly` - A reference to a dense polynomial to be evaluated.
     /// * `coset` - The coset value to be used in the evaluation of the polynomial.
     /// * `domain_size` - The size of the domain subset Omega.
+    /// * `folding_factor` - The size of the cosets bundled in each leaf, dividing `domain_size`.
     ///
     /// # Returns
     ///
@@ -33,7 +42,12 @@ impl<F: PrimeField> FriLayer<F> {
     /// # Panics
     ///
     /// This function will panic if the domain cannot be created with the given domain size.
-    pub fn from_poly(poly: &DensePolynomial<F>, coset: F, domain_size: usize) -> Self {
+    pub fn from_poly(
+        poly: &DensePolynomial<F>,
+        coset: F,
+        domain_size: usize,
+        folding_factor: usize,
+    ) -> Self {
         // Create a domain for polynomial evaluation.
         let domain = <GeneralEvaluationDomain<F>>::new(domain_size).unwrap();
         // Evaluate the polynomial at each point in the domain.
@@ -44,14 +58,44 @@ impl<F: PrimeField> FriLayer<F> {
                 poly.evaluate(&cur1) // Evaluate polynomial at the modified root.
             })
             .collect::<Vec<_>>();
-        // Create a Merkle tree from the evaluations.
-        let merkle_tree = MerkleTree::new(evaluations.clone());
+
+        // Create a Merkle tree whose leaves are the evaluations over each folding coset.
+        let leaves = (0..domain_size / folding_factor)
+            .map(|index| coset_evaluations(&evaluations, folding_factor, index))
+            .collect();
+        let merkle_tree = MerkleTree::new(leaves);
 
         Self {
             evaluations,
             merkle_tree,
             coset,
             domain_size,
+            folding_factor,
         }
     }
+
+    /// Returns the evaluations over the folding coset of a leaf.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the leaf, smaller than `domain_size / folding_factor`.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<F>` - The `folding_factor` evaluations bundled in the leaf.
+    pub fn coset_evaluations(&self, index: usize) -> Vec<F> {
+        coset_evaluations(&self.evaluations, self.folding_factor, index)
+    }
+}
+
+/// Gathers the evaluations at the indices `index + j * evaluations.len() / folding_factor`.
+fn coset_evaluations<F: PrimeField>(
+    evaluations: &[F],
+    folding_factor: usize,
+    index: usize,
+) -> Vec<F> {
+    let stride = evaluations.len() / folding_factor;
+    (0..folding_factor)
+        .map(|j| evaluations[index + j * stride])
+        .collect()
 }
@@ -1,7 +1,18 @@
-use ark_ff::PrimeField;
-use sha2::{Digest, Sha256};
+use std::fmt::Debug;
 
-/// Computes a cryptographic hash of a single field element using SHA-256.
+use ark_ff::{BigInteger, PrimeField};
+use sha2::digest::Output;
+use sha2::Digest;
+
+/// Prefix of the preimage of a leaf hash, separating leaves from internal nodes.
+const LEAF_PREFIX: u8 = 0x00;
+/// Prefix of the preimage of an internal node hash.
+const NODE_PREFIX: u8 = 0x01;
+/// Prefix of the preimage of the hash of a list of elements.
+const ELEMENTS_PREFIX: u8 = 0x02;
+
+/// Encodes a field element canonically, as the little-endian bytes of its reduced
+/// representative, on the smallest number of bytes holding the modulus.
 ///
 /// # Arguments
 ///
@@ -9,28 +20,163 @@ use sha2::{Digest, Sha256};
 ///
 /// # Returns
 ///
-/// * `F` - A field element representing the hash value.
+/// * `Vec<u8>` - The canonical encoding of the field element.
 ///
-pub fn hash<F: PrimeField>(data: &F) -> F {
-    let mut hasher = Sha256::new();
-    hasher.update(data.to_string());
-    let h = hasher.finalize();
-    F::from_le_bytes_mod_order(&h)
+pub fn to_bytes<F: PrimeField>(data: &F) -> Vec<u8> {
+    let mut bytes = data.into_bigint().to_bytes_le();
+    bytes.truncate(F::MODULUS_BIT_SIZE.div_ceil(8) as usize);
+    bytes
 }
 
-/// Computes a cryptographic hash of a slice of field elements using SHA-256.
-///
-/// # Arguments
-///
-/// * `data` - A slice of field elements of type `F`.
-///
-/// # Returns
+/// Hash function used by the Merkle trees and the transcript, hashing elements of the field `F`
+/// into digests.
 ///
-/// * `F` - A field element representing the hash value.
+/// Merkle nodes and the transcript state are kept as full digests, and a digest is only reduced
+/// into the field where a challenge is drawn from it.
 ///
-pub fn hash_slice<F: PrimeField>(data: &[F]) -> F {
-    let mut hasher = Sha256::new();
-    data.iter().for_each(|d| hasher.update(d.to_string()));
-    let h = hasher.finalize();
-    F::from_le_bytes_mod_order(&h)
+/// Every `Digest` is a `Hasher` over the canonical byte encoding of the elements, and the
+/// algebraic permutations in `crate::hashes` are `Hasher`s over Goldilocks.
+pub trait Hasher<F: PrimeField> {
+    /// Output of the hash function.
+    type Digest: Clone + Debug + Default + PartialEq + Eq;
+
+    /// Computes the hash of a Merkle leaf.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - A slice of the field elements held by the leaf.
+    ///
+    /// # Returns
+    ///
+    /// * `Self::Digest` - The hash value.
+    ///
+    fn hash_leaf(data: &[F]) -> Self::Digest;
+
+    /// Computes the hash of an internal Merkle node from its children.
+    ///
+    /// # Arguments
+    ///
+    /// * `left` - A reference to the hash of the left child.
+    /// * `right` - A reference to the hash of the right child.
+    ///
+    /// # Returns
+    ///
+    /// * `Self::Digest` - The hash value.
+    ///
+    fn hash_node(left: &Self::Digest, right: &Self::Digest) -> Self::Digest;
+
+    /// Computes the hash of a list of field elements, separated from leaves and nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `elements` - A slice of field elements.
+    ///
+    /// # Returns
+    ///
+    /// * `Self::Digest` - The hash value.
+    ///
+    fn hash_elements(elements: &[F]) -> Self::Digest;
+
+    /// Reduces a digest into the field, to draw a challenge from it.
+    ///
+    /// # Arguments
+    ///
+    /// * `digest` - A reference to the digest.
+    ///
+    /// # Returns
+    ///
+    /// * `F` - A field element derived from the digest.
+    ///
+    fn reduce(digest: &Self::Digest) -> F;
+}
+
+/// Hashes `D(0x00 || le_bytes(d_0) || ...)` for leaves, `D(0x01 || left || right)` for internal
+/// nodes and `D(0x02 || le_bytes(e_0) || ...)` for lists of elements, keeping the output bytes.
+impl<D: Digest, F: PrimeField> Hasher<F> for D {
+    type Digest = Output<D>;
+
+    fn hash_leaf(data: &[F]) -> Output<D> {
+        digest::<D, F>(LEAF_PREFIX, data)
+    }
+
+    fn hash_node(left: &Output<D>, right: &Output<D>) -> Output<D> {
+        D::new()
+            .chain_update([NODE_PREFIX])
+            .chain_update(left)
+            .chain_update(right)
+            .finalize()
+    }
+
+    fn hash_elements(elements: &[F]) -> Output<D> {
+        digest::<D, F>(ELEMENTS_PREFIX, elements)
+    }
+
+    fn reduce(digest: &Output<D>) -> F {
+        F::from_le_bytes_mod_order(digest)
+    }
+}
+
+/// Hashes the prefix followed by the canonical encoding of the elements.
+fn digest<D: Digest, F: PrimeField>(prefix: u8, elements: &[F]) -> Output<D> {
+    let mut hasher = D::new();
+    hasher.update([prefix]);
+    elements
+        .iter()
+        .for_each(|element| hasher.update(to_bytes(element)));
+    hasher.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use sha2::Sha256;
+
+    use crate::fields::goldilocks::Fq;
+
+    use super::*;
+
+    #[test]
+    fn test_canonical_encoding() {
+        assert_eq!(to_bytes(&Fq::from(0x0102u64)), vec![2, 1, 0, 0, 0, 0, 0, 0]);
+        // -1 is encoded as its reduced representative p - 1
+        assert_eq!(
+            to_bytes(&-Fq::from(1u64)),
+            0xFFFF_FFFF_0000_0000u64.to_le_bytes().to_vec()
+        );
+    }
+
+    #[test]
+    fn test_hash_leaf() {
+        let data = Fq::from(42u64);
+        let mut preimage = vec![0x00];
+        preimage.extend(42u64.to_le_bytes());
+        let expected = Sha256::digest(&preimage);
+        assert_eq!(<Sha256 as Hasher<Fq>>::hash_leaf(&[data]), expected);
+        // the digest keeps the 256 bits of the output, and is only reduced for challenges
+        assert_eq!(
+            <Sha256 as Hasher<Fq>>::reduce(&expected),
+            Fq::from_le_bytes_mod_order(&expected)
+        );
+    }
+
+    #[test]
+    fn test_domain_separation() {
+        let left = <Sha256 as Hasher<Fq>>::hash_leaf(&[Fq::from(1u64)]);
+        let right = <Sha256 as Hasher<Fq>>::hash_leaf(&[Fq::from(2u64)]);
+        let mut preimage = vec![0x01];
+        preimage.extend(left);
+        preimage.extend(right);
+        let expected = Sha256::digest(&preimage);
+        assert_eq!(<Sha256 as Hasher<Fq>>::hash_node(&left, &right), expected);
+        assert_ne!(
+            <Sha256 as Hasher<Fq>>::hash_node(&left, &right),
+            <Sha256 as Hasher<Fq>>::hash_node(&right, &left)
+        );
+
+        // a leaf or a list holding the same elements does not collide with each other
+        let elements = [Fq::from(1u64), Fq::from(2u64)];
+        assert_ne!(
+            <Sha256 as Hasher<Fq>>::hash_leaf(&elements),
+            <Sha256 as Hasher<Fq>>::hash_elements(&elements)
+        );
+    }
 }
@@ -0,0 +1,267 @@
+use std::sync::OnceLock;
+
+use ark_ff::{Field, PrimeField};
+use sha2::{Digest, Sha256};
+
+use crate::fields::goldilocks::Fq;
+
+pub mod poseidon;
+pub mod rescue;
+
+/// Number of Goldilocks elements in the state of the permutations.
+pub const WIDTH: usize = 12;
+/// Number of state elements absorbing the input, the remaining ones forming the capacity.
+pub const RATE: usize = 8;
+/// Number of elements squeezed into a digest, 256 bits as the capacity.
+pub const DIGEST_SIZE: usize = WIDTH - RATE;
+/// Exponent of the S-box, the smallest one coprime to `p - 1`.
+const ALPHA: u64 = 7;
+
+/// Domain tag of the sponge hashing a Merkle leaf.
+const LEAF_TAG: u64 = 0;
+/// Domain tag of the sponge hashing an internal Merkle node.
+const NODE_TAG: u64 = 1;
+/// Domain tag of the sponge hashing a list of elements.
+const ELEMENTS_TAG: u64 = 2;
+
+/// A permutation of a state of `WIDTH` Goldilocks elements.
+pub trait Permutation {
+    /// Applies the permutation to the state in place.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The state to be permuted.
+    ///
+    fn permute(state: &mut [Fq; WIDTH]);
+}
+
+/// Hashes a list of elements with a sponge over the permutation `P`.
+///
+/// The capacity is initialised with the number of elements and a domain tag, the elements
+/// are absorbed `RATE` at a time, and the first `DIGEST_SIZE` elements of the state are squeezed,
+/// so that a digest holds 256 bits like the capacity.
+///
+/// # Arguments
+///
+/// * `elements` - A slice of Goldilocks elements.
+/// * `tag` - The domain tag of the sponge.
+///
+/// # Returns
+///
+/// * `[Fq; DIGEST_SIZE]` - The field elements of the hash value.
+///
+pub fn sponge<P: Permutation>(elements: &[Fq], tag: u64) -> [Fq; DIGEST_SIZE] {
+    let mut state = [Fq::ZERO; WIDTH];
+    state[RATE] = Fq::from(elements.len() as u64);
+    state[RATE + 1] = Fq::from(tag);
+    if elements.is_empty() {
+        P::permute(&mut state);
+    }
+    for chunk in elements.chunks(RATE) {
+        state
+            .iter_mut()
+            .zip(chunk)
+            .for_each(|(state, element)| *state += element);
+        P::permute(&mut state);
+    }
+    state[..DIGEST_SIZE].try_into().unwrap()
+}
+
+/// Implements `Hasher<Fq>` for a permutation, with one sponge domain per kind of input.
+macro_rules! impl_hasher {
+    ($permutation:ty) => {
+        impl $crate::hasher::Hasher<$crate::fields::goldilocks::Fq> for $permutation {
+            type Digest = [$crate::fields::goldilocks::Fq; $crate::hashes::DIGEST_SIZE];
+
+            fn hash_leaf(data: &[$crate::fields::goldilocks::Fq]) -> Self::Digest {
+                $crate::hashes::sponge::<Self>(data, $crate::hashes::LEAF_TAG)
+            }
+
+            fn hash_node(left: &Self::Digest, right: &Self::Digest) -> Self::Digest {
+                $crate::hashes::sponge::<Self>(&[*left, *right].concat(), $crate::hashes::NODE_TAG)
+            }
+
+            fn hash_elements(elements: &[$crate::fields::goldilocks::Fq]) -> Self::Digest {
+                $crate::hashes::sponge::<Self>(elements, $crate::hashes::ELEMENTS_TAG)
+            }
+
+            fn reduce(digest: &Self::Digest) -> $crate::fields::goldilocks::Fq {
+                digest[0]
+            }
+        }
+    };
+}
+pub(crate) use impl_hasher;
+
+/// Derives round constants in a nothing-up-my-sleeve way, by expanding the label with SHA-256
+/// in counter mode and keeping the 8-byte little-endian words below the modulus.
+///
+/// The round numbers of Poseidon and Rescue-Prime assume constants without structure, as any
+/// constants drawn from a hash function are, rather than the specific constants of a published
+/// instance.
+///
+/// # Arguments
+///
+/// * `label` - The label of the permutation the constants belong to.
+/// * `rounds` - The number of rounds, each needing `WIDTH` constants.
+///
+/// # Returns
+///
+/// * `Vec<[Fq; WIDTH]>` - The constants of each round.
+///
+fn round_constants(label: &[u8], rounds: usize) -> Vec<[Fq; WIDTH]> {
+    let modulus = Fq::MODULUS.0[0];
+    let mut constants = Vec::with_capacity(rounds * WIDTH);
+    let mut counter = 0u64;
+    while constants.len() < rounds * WIDTH {
+        let block = Sha256::new_with_prefix(label)
+            .chain_update(counter.to_le_bytes())
+            .finalize();
+        counter += 1;
+        for word in block.chunks(8) {
+            let word = u64::from_le_bytes(word.try_into().unwrap());
+            if word < modulus && constants.len() < rounds * WIDTH {
+                constants.push(Fq::from(word));
+            }
+        }
+    }
+    constants
+        .chunks(WIDTH)
+        .map(|round| round.try_into().unwrap())
+        .collect()
+}
+
+/// Returns the MDS matrix shared by the permutations, the Cauchy matrix `1 / (i + j + WIDTH)`.
+///
+/// Any square submatrix of a Cauchy matrix is invertible, so the matrix is MDS. The characteristic
+/// polynomials of its powers `M^l` for `l <= 2 * WIDTH` are irreducible, so none of them has a
+/// non-trivial invariant subspace: this is the check of the Poseidon reference parameter script
+/// against infinitely long subspace trails through the partial rounds.
+fn mds() -> &'static [[Fq; WIDTH]; WIDTH] {
+    static MDS: OnceLock<[[Fq; WIDTH]; WIDTH]> = OnceLock::new();
+    MDS.get_or_init(|| {
+        std::array::from_fn(|i| {
+            std::array::from_fn(|j| Fq::from((i + j + WIDTH) as u64).inverse().unwrap())
+        })
+    })
+}
+
+/// Multiplies the state by the MDS matrix in place.
+fn apply_mds(state: &mut [Fq; WIDTH]) {
+    let mds = mds();
+    *state = std::array::from_fn(|i| mds[i].iter().zip(state.iter()).map(|(m, s)| *m * s).sum());
+}
+
+/// Adds the round constants to the state in place.
+fn add_constants(state: &mut [Fq; WIDTH], constants: &[Fq; WIDTH]) {
+    state
+        .iter_mut()
+        .zip(constants)
+        .for_each(|(state, constant)| *state += constant);
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_ff::Zero;
+    use ark_poly::univariate::{DenseOrSparsePolynomial, DensePolynomial};
+    use ark_poly::{DenseUVPolynomial, Polynomial};
+
+    use super::*;
+
+    #[test]
+    fn test_round_constants() {
+        // the first word of SHA-256(label || 0), as it lies below the modulus
+        let block = Sha256::digest([b"label".as_slice(), &0u64.to_le_bytes()].concat());
+        let word = u64::from_le_bytes(block[..8].try_into().unwrap());
+        assert!(word < Fq::MODULUS.0[0]);
+        let constants = round_constants(b"label", 3);
+        assert_eq!(constants.len(), 3);
+        assert_eq!(constants[0][0], Fq::from(word));
+        assert_ne!(constants, round_constants(b"other label", 3));
+    }
+
+    /// Computes `a * b mod f`.
+    fn mul_mod(
+        a: &DensePolynomial<Fq>,
+        b: &DensePolynomial<Fq>,
+        f: &DensePolynomial<Fq>,
+    ) -> DensePolynomial<Fq> {
+        let product = a.naive_mul(b);
+        DenseOrSparsePolynomial::from(product)
+            .divide_with_q_and_r(&f.into())
+            .unwrap()
+            .1
+    }
+
+    /// Computes `g^p mod f`, where `p` is the modulus.
+    fn frobenius(g: &DensePolynomial<Fq>, f: &DensePolynomial<Fq>) -> DensePolynomial<Fq> {
+        let exponent = Fq::MODULUS.0[0];
+        let mut result = DensePolynomial::from_coefficients_vec(vec![Fq::ONE]);
+        for bit in (0..64).rev() {
+            result = mul_mod(&result, &result, f);
+            if exponent >> bit & 1 == 1 {
+                result = mul_mod(&result, g, f);
+            }
+        }
+        result
+    }
+
+    fn gcd(mut a: DensePolynomial<Fq>, mut b: DensePolynomial<Fq>) -> DensePolynomial<Fq> {
+        while !b.is_zero() {
+            let (_, r) = DenseOrSparsePolynomial::from(&a)
+                .divide_with_q_and_r(&(&b).into())
+                .unwrap();
+            (a, b) = (b, r);
+        }
+        a
+    }
+
+    /// Checks with Rabin's test that a polynomial of degree `WIDTH` is irreducible: `f` divides
+    /// `X^(p^WIDTH) - X`, and is coprime to `X^(p^(WIDTH / q)) - X` for the primes `q = 2, 3`.
+    fn is_irreducible(f: &DensePolynomial<Fq>) -> bool {
+        let x = DensePolynomial::from_coefficients_vec(vec![Fq::ZERO, Fq::ONE]);
+        let mut power = x.clone();
+        for k in 1..=WIDTH {
+            power = frobenius(&power, f);
+            if (k == WIDTH / 2 || k == WIDTH / 3) && gcd(f.clone(), &power - &x).degree() > 0 {
+                return false;
+            }
+        }
+        (&power - &x).is_zero()
+    }
+
+    /// Computes the characteristic polynomial of a matrix with the Faddeev-LeVerrier algorithm.
+    fn characteristic_polynomial(matrix: &[[Fq; WIDTH]; WIDTH]) -> DensePolynomial<Fq> {
+        let multiply = |a: &[[Fq; WIDTH]; WIDTH], b: &[[Fq; WIDTH]; WIDTH]| {
+            std::array::from_fn(|i| {
+                std::array::from_fn::<Fq, WIDTH, _>(|j| (0..WIDTH).map(|k| a[i][k] * b[k][j]).sum())
+            })
+        };
+        let mut coeffs = vec![Fq::ONE];
+        let mut m = [[Fq::ZERO; WIDTH]; WIDTH];
+        for k in 1..=WIDTH {
+            (0..WIDTH).for_each(|i| m[i][i] += coeffs[k - 1]);
+            m = multiply(matrix, &m);
+            let trace: Fq = (0..WIDTH).map(|i| m[i][i]).sum();
+            coeffs.push(-trace / Fq::from(k as u64));
+        }
+        coeffs.reverse();
+        DensePolynomial::from_coefficients_vec(coeffs)
+    }
+
+    #[test]
+    fn test_mds_subspace_trails() {
+        let mut power = *mds();
+        for _ in 0..2 * WIDTH {
+            assert!(is_irreducible(&characteristic_polynomial(&power)));
+            power = std::array::from_fn(|i| {
+                std::array::from_fn(|j| (0..WIDTH).map(|k| power[i][k] * mds()[k][j]).sum())
+            });
+        }
+
+        // a matrix with an invariant subspace is detected
+        let mut diagonal = [[Fq::ZERO; WIDTH]; WIDTH];
+        (0..WIDTH).for_each(|i| diagonal[i][i] = Fq::from(i as u64 + 1));
+        assert!(!is_irreducible(&characteristic_polynomial(&diagonal)));
+    }
+}
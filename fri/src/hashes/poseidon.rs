@@ -0,0 +1,157 @@
+use std::sync::OnceLock;
+
+use ark_ff::Field;
+
+use crate::fields::goldilocks::Fq;
+use crate::hashes::{
+    add_constants, apply_mds, impl_hasher, round_constants, Permutation, ALPHA, WIDTH,
+};
+
+/// Number of full rounds, half of them before the partial rounds and half after.
+///
+/// Statistical attacks need 6 full rounds to be ruled out, the Poseidon paper adds 2 as a margin.
+const FULL_ROUNDS: usize = 8;
+/// Number of partial rounds, applying the S-box to the first element only.
+///
+/// Algebraic attacks at 128 bits need 21 partial rounds for a width of 12 and `x^7` over a 64-bit
+/// field, the Poseidon paper adds 7.5% as a margin.
+const PARTIAL_ROUNDS: usize = 22;
+
+/// The Poseidon permutation over Goldilocks, with a state of 12 elements, the S-box `x^7`,
+/// 8 full rounds and 22 partial rounds.
+///
+/// The width, S-box and round numbers are those of the Poseidon instance over Goldilocks of
+/// Plonky2, derived with the security margins of "Poseidon: A New Hash Function for
+/// Zero-Knowledge Proof Systems" (Grassi et al., USENIX Security 2021, Section 5.4) for 128 bits
+/// of security. The analysis holds for any round constants without structure and any MDS matrix
+/// without infinitely long invariant subspace trails, which `crate::hashes::mds` satisfies.
+///
+/// The round constants are derived from the label `"fri-poseidon-goldilocks"`, so hashes
+/// differ from other Poseidon instances over the same field, such as the one of Plonky2.
+/// The known-answer tests below pin this instance.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Poseidon;
+
+impl Poseidon {
+    /// Returns the constants of each round.
+    fn round_constants() -> &'static [[Fq; WIDTH]] {
+        static CONSTANTS: OnceLock<Vec<[Fq; WIDTH]>> = OnceLock::new();
+        CONSTANTS.get_or_init(|| {
+            round_constants(b"fri-poseidon-goldilocks", FULL_ROUNDS + PARTIAL_ROUNDS)
+        })
+    }
+}
+
+impl Permutation for Poseidon {
+    fn permute(state: &mut [Fq; WIDTH]) {
+        for (round, constants) in Self::round_constants().iter().enumerate() {
+            add_constants(state, constants);
+            let partial = (FULL_ROUNDS / 2..FULL_ROUNDS / 2 + PARTIAL_ROUNDS).contains(&round);
+            if partial {
+                state[0] = state[0].pow([ALPHA]);
+            } else {
+                state.iter_mut().for_each(|x| *x = x.pow([ALPHA]));
+            }
+            apply_mds(state);
+        }
+    }
+}
+
+impl_hasher!(Poseidon);
+
+#[cfg(test)]
+mod tests {
+    use ark_ff::UniformRand;
+
+    use crate::hasher::Hasher;
+
+    use super::*;
+
+    #[test]
+    fn test_poseidon_permutation() {
+        let mut state = [Fq::ZERO; WIDTH];
+        Poseidon::permute(&mut state);
+        assert_ne!(state, [Fq::ZERO; WIDTH]);
+
+        // the permutation is deterministic and sensitive to every element
+        let rng = &mut rand::thread_rng();
+        let input: [Fq; WIDTH] = std::array::from_fn(|_| Fq::rand(rng));
+        let (mut a, mut b) = (input, input);
+        Poseidon::permute(&mut a);
+        Poseidon::permute(&mut b);
+        assert_eq!(a, b);
+        for i in 0..WIDTH {
+            let mut c = input;
+            c[i] += Fq::ONE;
+            Poseidon::permute(&mut c);
+            assert!(a.iter().zip(&c).all(|(a, c)| a != c));
+        }
+    }
+
+    #[test]
+    fn test_poseidon_hasher() {
+        let (x, y) = (Fq::from(1u64), Fq::from(2u64));
+        assert_ne!(Poseidon::hash_leaf(&[x]), Poseidon::hash_elements(&[x]));
+        let (left, right) = (Poseidon::hash_leaf(&[x]), Poseidon::hash_leaf(&[y]));
+        assert_ne!(
+            Poseidon::hash_node(&left, &right),
+            Poseidon::hash_elements(&[left, right].concat())
+        );
+        assert_ne!(
+            Poseidon::hash_node(&left, &right),
+            Poseidon::hash_node(&right, &left)
+        );
+        // inputs longer than the rate are absorbed in several permutations
+        let long = (0..20u64).map(Fq::from).collect::<Vec<_>>();
+        assert_ne!(
+            Poseidon::hash_elements(&long),
+            Poseidon::hash_elements(&long[..19])
+        );
+    }
+
+    #[test]
+    fn test_poseidon_known_answers() {
+        let mut state: [Fq; WIDTH] = std::array::from_fn(|i| Fq::from(i as u64));
+        Poseidon::permute(&mut state);
+        assert_eq!(
+            state,
+            [
+                0xdf53ed4e0b266bf1u64,
+                0x644fd0f7f9ac48f9u64,
+                0xbf933b52c9272a77u64,
+                0x1caf9bf8f8e10d8fu64,
+                0xaab397b035f4881fu64,
+                0x112b4c1ee5d9a3c5u64,
+                0x09c7b4a714ce4525u64,
+                0x07a2da20974666fdu64,
+                0x72560a39e81a250eu64,
+                0x9236deb685c1930au64,
+                0xe6c4d484a3052368u64,
+                0xc50eb13ff9aed473u64,
+            ]
+            .map(Fq::from)
+        );
+
+        assert_eq!(
+            Poseidon::hash_elements(&[]),
+            [
+                0x87bd6b94fa69287du64,
+                0x7bf5fe07d5768b61u64,
+                0x313f2d14934a66feu64,
+                0x3932135678040717u64,
+            ]
+            .map(Fq::from)
+        );
+        let long = (0..20u64).map(Fq::from).collect::<Vec<_>>();
+        assert_eq!(
+            Poseidon::hash_elements(&long),
+            [
+                0x7c18b8a9b03c6b7du64,
+                0x324bccd1b9cca9bcu64,
+                0x8008a5ecf6e13e12u64,
+                0xf4c751a263db9ec2u64,
+            ]
+            .map(Fq::from)
+        );
+    }
+}
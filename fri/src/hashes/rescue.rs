@@ -0,0 +1,134 @@
+use std::sync::OnceLock;
+
+use ark_ff::Field;
+
+use crate::fields::goldilocks::Fq;
+use crate::hashes::{
+    add_constants, apply_mds, impl_hasher, round_constants, Permutation, ALPHA, WIDTH,
+};
+
+/// Number of rounds, each made of a forward and a backward half-round.
+///
+/// Known attacks at 128 bits are ruled out after 5 rounds for a width of 12, a capacity of 4 and
+/// `x^7` over a 64-bit field, 7 rounds give a 40% margin.
+const ROUNDS: usize = 7;
+/// Exponent of the inverse S-box, the inverse of `ALPHA` modulo `p - 1`.
+const ALPHA_INV: u64 = 10540996611094048183;
+
+/// The Rescue-Prime permutation over Goldilocks, with a state of 12 elements and 7 rounds,
+/// alternating the S-boxes `x^7` and `x^(1/7)`.
+///
+/// The width, capacity, S-box and number of rounds are those of Rescue-Prime Optimized (Ashur et
+/// al., "Rescue-Prime Optimized", ePrint 2022/1577) and of the `Rp64_256` hash of Winterfell,
+/// whose analysis targets 128 bits of security. It holds for any MDS matrix and round constants
+/// without structure.
+///
+/// The round constants are derived from the label `"fri-rescue-prime-goldilocks"`, so hashes
+/// differ from other Rescue-Prime instances over the same field, such as `Rp64_256`.
+/// The known-answer tests below pin this instance.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RescuePrime;
+
+impl RescuePrime {
+    /// Returns the constants of each half-round.
+    fn round_constants() -> &'static [[Fq; WIDTH]] {
+        static CONSTANTS: OnceLock<Vec<[Fq; WIDTH]>> = OnceLock::new();
+        CONSTANTS.get_or_init(|| round_constants(b"fri-rescue-prime-goldilocks", 2 * ROUNDS))
+    }
+}
+
+impl Permutation for RescuePrime {
+    fn permute(state: &mut [Fq; WIDTH]) {
+        for constants in Self::round_constants().chunks(2) {
+            state.iter_mut().for_each(|x| *x = x.pow([ALPHA]));
+            apply_mds(state);
+            add_constants(state, &constants[0]);
+
+            state.iter_mut().for_each(|x| *x = x.pow([ALPHA_INV]));
+            apply_mds(state);
+            add_constants(state, &constants[1]);
+        }
+    }
+}
+
+impl_hasher!(RescuePrime);
+
+#[cfg(test)]
+mod tests {
+    use ark_ff::UniformRand;
+
+    use crate::hasher::Hasher;
+    use crate::hashes::poseidon::Poseidon;
+
+    use super::*;
+
+    #[test]
+    fn test_inverse_sbox() {
+        let rng = &mut rand::thread_rng();
+        for _ in 0..10 {
+            let x = Fq::rand(rng);
+            assert_eq!(x.pow([ALPHA]).pow([ALPHA_INV]), x);
+        }
+    }
+
+    #[test]
+    fn test_rescue_hasher() {
+        let (x, y) = (Fq::from(1u64), Fq::from(2u64));
+        assert_ne!(
+            RescuePrime::hash_leaf(&[x]),
+            RescuePrime::hash_elements(&[x])
+        );
+        let (left, right) = (RescuePrime::hash_leaf(&[x]), RescuePrime::hash_leaf(&[y]));
+        assert_ne!(
+            RescuePrime::hash_node(&left, &right),
+            RescuePrime::hash_node(&right, &left)
+        );
+        assert_ne!(RescuePrime::hash_leaf(&[x]), Poseidon::hash_leaf(&[x]));
+    }
+
+    #[test]
+    fn test_rescue_known_answers() {
+        let mut state: [Fq; WIDTH] = std::array::from_fn(|i| Fq::from(i as u64));
+        RescuePrime::permute(&mut state);
+        assert_eq!(
+            state,
+            [
+                0xd5db65c9dacc45ceu64,
+                0xf2a8235e5aa67847u64,
+                0xdef7ec35bc010113u64,
+                0x2483483eead41421u64,
+                0x642d4c323649ff2bu64,
+                0xa53056fd2033c10cu64,
+                0x6a7ee60a7f19f773u64,
+                0xc73d8f3915f22f1eu64,
+                0x5cbe3864cb079d88u64,
+                0x137eff5d9a9662b5u64,
+                0x4fae3311f0842350u64,
+                0x401a9fec99f48d97u64,
+            ]
+            .map(Fq::from)
+        );
+
+        assert_eq!(
+            RescuePrime::hash_elements(&[]),
+            [
+                0x7427378e7f06f879u64,
+                0xa1f59e92c0587392u64,
+                0xa826196602c6c0f5u64,
+                0x382a39d363b6ddc5u64,
+            ]
+            .map(Fq::from)
+        );
+        let long = (0..20u64).map(Fq::from).collect::<Vec<_>>();
+        assert_eq!(
+            RescuePrime::hash_elements(&long),
+            [
+                0x6073fd8cfb2ba4e0u64,
+                0x94a458c08e4b9ccfu64,
+                0x5af3ab1c35734c94u64,
+                0x9fdbc0d1215f3bbdu64,
+            ]
+            .map(Fq::from)
+        );
+    }
+}
@@ -1,7 +1,9 @@
 pub mod fiat_shamir;
 pub mod fields;
 mod fri_layer;
-mod hasher;
-mod merkle_tree;
+pub mod hasher;
+pub mod hashes;
+pub mod merkle_tree;
+pub mod pcs;
 pub mod prover;
 pub mod verifier;
@@ -1,63 +1,74 @@
-use ark_ff::PrimeField;
+use std::marker::PhantomData;
 
-use crate::hasher::{hash, hash_slice};
+use crate::hasher::Hasher;
+use ark_ff::PrimeField;
 
-/// A proof for Merkle tree membership, which includes the leaf index, leaf value, hash proofs, and root.
+/// A proof for Merkle tree membership, which includes the leaf index and hash proofs.
+///
+/// The proof carries neither the leaf nor the root, which the verifier must already hold.
 #[derive(Debug, Clone)]
-pub struct MerkleProof<F: PrimeField> {
+pub struct MerkleProof<F: PrimeField, H: Hasher<F>> {
     /// Index of the leaf the prover wants to reveal.
     pub index: usize,
-    /// Value of the leaf the prover wants to reveal.
-    pub leaf_val: F,
     /// Hash values of the neighboring nodes.
-    hash_proof: Vec<F>,
-    /// Root value of the committed Merkle tree.
-    root: F,
+    hash_proof: Vec<H::Digest>,
 }
 
 /// A Merkle tree structure that supports the creation of proofs and verification of membership.
+///
+/// Each leaf holds a list of field elements. Nodes are hashed with the hasher `H`, which
+/// separates leaves from internal nodes, and are kept as full digests.
 #[derive(Debug, Clone)]
-pub struct MerkleTree<F: PrimeField> {
+pub struct MerkleTree<F: PrimeField, H: Hasher<F>> {
     /// The internal nodes of the Merkle tree stored in levels.
-    internal_nodes: Vec<Vec<F>>,
+    internal_nodes: Vec<Vec<H::Digest>>,
     /// Values of the leaf nodes.
-    pub leaves: Vec<F>,
+    pub leaves: Vec<Vec<F>>,
     /// Depth of the Merkle tree.
     depth: usize,
+    /// Phantom data for annotation purposes.
+    _phantom_data: PhantomData<H>,
 }
 
-impl<F: PrimeField> MerkleTree<F> {
-    /// Constructs a new Merkle tree from the given evaluations (leaf values).
+impl<F: PrimeField, H: Hasher<F>> MerkleTree<F, H> {
+    /// Constructs a new Merkle tree from the given leaves.
     ///
     /// # Arguments
     ///
-    /// * `evaluations` - A vector of field elements representing the leaf values.
+    /// * `leaves` - A vector of leaves, each a list of field elements.
     ///
     /// # Returns
     ///
-    /// * `MerkleTree<F>` - A new Merkle tree instance.
+    /// * `MerkleTree<F, H>` - A new Merkle tree instance.
     ///
-    /// The method hashes the leaf values to create the first level of internal nodes,
+    /// The method hashes the leaves to create the first level of internal nodes,
     /// and iteratively hashes pairs of nodes to construct the upper levels of the tree.
-    pub fn new(mut evaluations: Vec<F>) -> Self {
-        let new_len = evaluations.len().next_power_of_two();
+    pub fn new(mut leaves: Vec<Vec<F>>) -> Self {
+        let new_len = leaves.len().next_power_of_two();
         let depth = new_len.ilog2() as usize;
 
-        let first_level = evaluations.iter().map(hash).collect::<Vec<_>>();
+        leaves.resize(new_len, vec![F::ZERO]); // Fill the rest of the tree with 0
+
+        let first_level = leaves
+            .iter()
+            .map(|leaf| H::hash_leaf(leaf))
+            .collect::<Vec<_>>();
 
         let mut internal_nodes = vec![first_level];
 
         for i in 0..depth {
-            let next_level = internal_nodes[i].chunks(2).map(hash_slice).collect();
+            let next_level = internal_nodes[i]
+                .chunks(2)
+                .map(|pair| H::hash_node(&pair[0], &pair[1]))
+                .collect();
             internal_nodes.push(next_level);
         }
 
-        evaluations.resize(new_len, F::ZERO); // Fill the rest of the tree with 0
-
         Self {
             internal_nodes,
-            leaves: evaluations,
+            leaves,
             depth,
+            _phantom_data: PhantomData,
         }
     }
 
@@ -65,9 +76,9 @@ impl<F: PrimeField> MerkleTree<F> {
     ///
     /// # Returns
     ///
-    /// * `F` - The root hash of the Merkle tree.
-    pub fn root(&self) -> F {
-        self.internal_nodes.last().unwrap()[0]
+    /// * `H::Digest` - The root hash of the Merkle tree.
+    pub fn root(&self) -> H::Digest {
+        self.internal_nodes.last().unwrap()[0].clone()
     }
 
     /// Generates a Merkle proof for a leaf at the given index.
@@ -78,75 +89,174 @@ impl<F: PrimeField> MerkleTree<F> {
     ///
     /// # Returns
     ///
-    /// * `MerkleProof<F>` - A proof containing the leaf index, leaf value, hash proofs, and root.
-    pub fn generate_proof(&self, index: usize) -> MerkleProof<F> {
-        let leaf_val = self.leaves[index];
+    /// * `MerkleProof<F, H>` - A proof containing the leaf index and hash proofs.
+    pub fn generate_proof(&self, index: usize) -> MerkleProof<F, H> {
         let mut hash_proof = Vec::with_capacity(self.depth);
         let mut cur_index = index;
         for i in 0..self.depth {
-            let neighbour = if cur_index % 2 == 0 {
+            let neighbour = if cur_index.is_multiple_of(2) {
                 // The current node is a left node, we need the right node.
-                self.internal_nodes[i][cur_index + 1]
+                &self.internal_nodes[i][cur_index + 1]
             } else {
-                self.internal_nodes[i][cur_index - 1]
+                &self.internal_nodes[i][cur_index - 1]
             };
-            hash_proof.push(neighbour);
+            hash_proof.push(neighbour.clone());
             cur_index /= 2;
         }
 
-        MerkleProof {
-            index,
-            leaf_val,
-            hash_proof,
-            root: self.root(),
-        }
+        MerkleProof { index, hash_proof }
     }
 }
 
-/// Verifies a Merkle proof against the root of the Merkle tree.
+/// Verifies a Merkle proof of a leaf against the expected root of the Merkle tree.
 ///
 /// # Arguments
 ///
 /// * `proof` - A reference to a MerkleProof instance.
+/// * `leaf` - A slice of the field elements held by the leaf.
+/// * `root` - A reference to the root the proof must lead to.
 ///
 /// # Returns
 ///
 /// * `bool` - `true` if the proof is valid, `false` otherwise.
 ///
 /// The function reconstructs the hash path from the leaf node to the root and checks if it matches the given root.
-pub fn verify_merkle_proof<F: PrimeField>(proof: &MerkleProof<F>) -> bool {
+pub fn verify_merkle_proof<H: Hasher<F>, F: PrimeField>(
+    proof: &MerkleProof<F, H>,
+    leaf: &[F],
+    root: &H::Digest,
+) -> bool {
     let mut cur_index = proof.index;
-    let mut cur_hash = hash(&proof.leaf_val);
-    for i in 0..proof.hash_proof.len() {
-        if cur_index % 2 == 0 {
+    let mut cur_hash = H::hash_leaf(leaf);
+    for neighbour in &proof.hash_proof {
+        if cur_index.is_multiple_of(2) {
             // The current node is a left node
-            let neighbour = proof.hash_proof[i];
-            cur_hash = hash_slice(&[cur_hash, neighbour]);
+            cur_hash = H::hash_node(&cur_hash, neighbour);
         } else {
-            let neighbour = proof.hash_proof[i];
-            cur_hash = hash_slice(&[neighbour, cur_hash]);
+            cur_hash = H::hash_node(neighbour, &cur_hash);
         }
         cur_index /= 2;
     }
-    cur_hash == proof.root
+    cur_hash == *root
 }
 
 #[cfg(test)]
 mod tests {
+    use sha2::Sha256;
+
     use crate::fields::goldilocks::Fq;
+    use crate::hashes::poseidon::Poseidon;
 
     use super::*;
 
+    /// Builds leaves holding a single element each.
+    fn to_leaves(values: &[u64]) -> Vec<Vec<Fq>> {
+        values.iter().map(|value| vec![Fq::from(*value)]).collect()
+    }
+
     #[test]
     fn test_merkle() {
-        let leaves = vec![1, 2, 3, 4];
-        let new_leaves: Vec<Fq> = leaves.into_iter().map(Fq::from).collect();
-        let tree = MerkleTree::new(new_leaves);
+        let leaves = to_leaves(&[1, 2, 3, 4]);
+        let tree = MerkleTree::<Fq, Sha256>::new(leaves.clone());
 
         let merkle_proof = tree.generate_proof(1);
         // merkle_proof.index = 2;
-        let verify = verify_merkle_proof(&merkle_proof);
+        let verify = verify_merkle_proof::<Sha256, Fq>(&merkle_proof, &leaves[1], &tree.root());
+
+        assert!(verify);
+    }
+
+    #[test]
+    fn test_merkle_forged_tree() {
+        let leaves = to_leaves(&[0, 1, 2, 3, 4, 5, 6, 7]);
+        let tree = MerkleTree::<Fq, Sha256>::new(leaves.clone());
 
-        assert_eq!(verify, true);
+        // a tree sharing the revealed leaf, but not the root
+        let mut forged_leaves = leaves.clone();
+        forged_leaves[5][0] += Fq::from(1u64);
+        let forged = MerkleTree::<Fq, Sha256>::new(forged_leaves);
+        let forged_proof = forged.generate_proof(2);
+        assert!(verify_merkle_proof::<Sha256, Fq>(
+            &forged_proof,
+            &leaves[2],
+            &forged.root()
+        ));
+        assert!(!verify_merkle_proof::<Sha256, Fq>(
+            &forged_proof,
+            &leaves[2],
+            &tree.root()
+        ));
+
+        // a proof of another leaf, or another value
+        let mut proof = tree.generate_proof(2);
+        proof.index = 3;
+        assert!(!verify_merkle_proof::<Sha256, Fq>(
+            &proof,
+            &leaves[2],
+            &tree.root()
+        ));
+        let proof = tree.generate_proof(2);
+        assert!(!verify_merkle_proof::<Sha256, Fq>(
+            &proof,
+            &leaves[3],
+            &tree.root()
+        ));
+    }
+
+    #[test]
+    fn test_merkle_root() {
+        let leaves = to_leaves(&[1, 2]);
+        let tree = MerkleTree::<Fq, Sha256>::new(leaves.clone());
+        let expected = <Sha256 as Hasher<Fq>>::hash_node(
+            &<Sha256 as Hasher<Fq>>::hash_leaf(&leaves[0]),
+            &<Sha256 as Hasher<Fq>>::hash_leaf(&leaves[1]),
+        );
+        assert_eq!(tree.root(), expected);
+        // the root keeps the whole output of the hash function
+        assert_eq!(tree.root().len(), 32);
+
+        // the leaves are padded with zeros up to a power of two
+        let tree = MerkleTree::<Fq, Sha256>::new(to_leaves(&[1, 2, 3]));
+        let padded = MerkleTree::<Fq, Sha256>::new(to_leaves(&[1, 2, 3, 0]));
+        assert_eq!(tree.root(), padded.root());
+        assert!(verify_merkle_proof::<Sha256, Fq>(
+            &tree.generate_proof(3),
+            &[Fq::from(0u64)],
+            &tree.root()
+        ));
+    }
+
+    #[test]
+    fn test_merkle_cosets() {
+        // leaves holding several elements, as the cosets of the FRI layers
+        let leaves = (0..4u64)
+            .map(|i| (0..4u64).map(|j| Fq::from(4 * i + j)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let tree = MerkleTree::<Fq, Sha256>::new(leaves.clone());
+        for (index, leaf) in leaves.iter().enumerate() {
+            assert!(verify_merkle_proof::<Sha256, Fq>(
+                &tree.generate_proof(index),
+                leaf,
+                &tree.root()
+            ));
+        }
+        assert!(!verify_merkle_proof::<Sha256, Fq>(
+            &tree.generate_proof(1),
+            &leaves[1][..3],
+            &tree.root()
+        ));
+    }
+
+    #[test]
+    fn test_merkle_poseidon() {
+        let leaves = to_leaves(&[0, 1, 2, 3, 4, 5, 6, 7]);
+        let tree = MerkleTree::<Fq, Poseidon>::new(leaves.clone());
+        for (index, leaf) in leaves.iter().enumerate() {
+            assert!(verify_merkle_proof::<Poseidon, Fq>(
+                &tree.generate_proof(index),
+                leaf,
+                &tree.root()
+            ));
+        }
     }
 }
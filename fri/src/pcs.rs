@@ -0,0 +1,479 @@
+use std::marker::PhantomData;
+
+use ark_ff::PrimeField;
+use ark_poly::univariate::{DenseOrSparsePolynomial, DensePolynomial};
+use ark_poly::{DenseUVPolynomial, EvaluationDomain, GeneralEvaluationDomain, Polynomial};
+
+use crate::fiat_shamir::transcript::Transcript;
+use crate::fri_layer::FriLayer;
+use crate::hasher::Hasher;
+use crate::merkle_tree::{verify_merkle_proof, MerkleProof};
+use crate::prover::{prove, Proof};
+use crate::verifier::{is_valid_domain_size, verify_with_transcript, FriConfig};
+
+/// A commitment to a polynomial, the Merkle root of its evaluations over a coset.
+#[derive(Clone, Debug)]
+pub struct FriCommitment<F: PrimeField, H: Hasher<F>> {
+    /// Root of the Merkle tree of the evaluations.
+    pub root: H::Digest,
+    /// Size of the coset the polynomial is evaluated over.
+    pub domain_size: usize,
+}
+
+// compared by value, whether or not the hasher itself can be compared
+impl<F: PrimeField, H: Hasher<F>> PartialEq for FriCommitment<F, H> {
+    fn eq(&self, other: &Self) -> bool {
+        self.root == other.root && self.domain_size == other.domain_size
+    }
+}
+
+impl<F: PrimeField, H: Hasher<F>> Eq for FriCommitment<F, H> {}
+
+/// An opening of a committed polynomial `f` at a point `z`.
+///
+/// The opening proves with FRI that the quotient `q(X) = (f(X) - y) / (X - z)` is a polynomial,
+/// and opens `f` over the queried cosets so that the verifier can check them against the first
+/// FRI layer of `q`.
+#[derive(Clone, Debug)]
+pub struct FriOpening<F: PrimeField, H: Hasher<F>> {
+    /// The claimed evaluation `y = f(z)`.
+    pub evaluation: F,
+    /// FRI proof that the quotient is low-degree.
+    pub quotient_proof: Proof<F, H>,
+    /// For each query, the evaluations of `f` over the queried coset,
+    pub evaluations: Vec<Vec<F>>,
+    /// and the authentication path of the coset.
+    pub auth_paths: Vec<MerkleProof<F, H>>,
+}
+
+/// A transparent polynomial commitment scheme built on FRI, with DEEP-style openings.
+///
+/// Polynomials are committed to with the Merkle root of their evaluations over a coset
+/// `blowup_factor` times larger than their number of coefficients.
+///
+/// `FriPcs` offers the commit, open and verify surface of `KzgScheme`, but does not implement
+/// the `PolynomialCommitmentScheme` trait of the `kzg` crate, so PLONK and Nova cannot be
+/// instantiated with it. Both protocols need a `HomomorphicCommitmentScheme`, whose commitments
+/// can be added and scaled, which Merkle roots cannot. The trait also needs commitments and
+/// openings with `CanonicalSerialize`, which the digests of `sha2` hashers do not implement.
+#[derive(Clone, Debug)]
+pub struct FriPcs<T: Hasher<F>, F: PrimeField> {
+    blowup_factor: usize,
+    folding_factor: usize,
+    max_remainder_degree: usize,
+    grinding_bits: usize,
+    number_of_queries: usize,
+
+    /// Phantom data for annotation purposes.
+    _phantom_data: PhantomData<(T, F)>,
+}
+
+impl<T: Hasher<F>, F: PrimeField> FriPcs<T, F> {
+    /// Constructs a new `FriPcs` with the given FRI parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `blowup_factor` - The ratio of the coset size to the number of coefficients.
+    /// * `folding_factor` - The number of points folded together in each FRI layer, a power of two.
+    /// * `max_remainder_degree` - The maximum degree of the final FRI polynomial, sent in clear.
+    /// * `grinding_bits` - The number of leading zero bits of the proof of work before the queries.
+    /// * `number_of_queries` - The number of queries of every opening.
+    ///
+    /// # Returns
+    ///
+    /// * `FriPcs<T, F>` - A new instance of the scheme.
+    pub fn new(
+        blowup_factor: usize,
+        folding_factor: usize,
+        max_remainder_degree: usize,
+        grinding_bits: usize,
+        number_of_queries: usize,
+    ) -> Self {
+        Self {
+            blowup_factor,
+            folding_factor,
+            max_remainder_degree,
+            grinding_bits,
+            number_of_queries,
+            _phantom_data: PhantomData,
+        }
+    }
+
+    /// Commits to a polynomial.
+    ///
+    /// # Arguments
+    ///
+    /// * `poly` - A reference to the polynomial to be committed to.
+    ///
+    /// # Returns
+    ///
+    /// * `FriCommitment<F, T>` - The commitment to the polynomial.
+    pub fn commit(&self, poly: &DensePolynomial<F>) -> FriCommitment<F, T> {
+        let domain_size = self.domain_size(poly);
+        let layer = FriLayer::<F, T>::from_poly(
+            poly,
+            F::GENERATOR,
+            domain_size,
+            self.layer_folding_factor(domain_size),
+        );
+        FriCommitment {
+            root: layer.merkle_tree.root(),
+            domain_size,
+        }
+    }
+
+    /// Opens a committed polynomial at a point.
+    ///
+    /// # Arguments
+    ///
+    /// * `poly` - A reference to the committed polynomial.
+    /// * `z` - The point at which the polynomial is opened, outside of the committed coset.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<FriOpening<F, T>, String>` - The opening, or an error if `z` lies in the coset.
+    pub fn open(&self, poly: &DensePolynomial<F>, z: F) -> Result<FriOpening<F, T>, String> {
+        let domain_size = self.domain_size(poly);
+        if Self::in_coset(z, domain_size) {
+            return Err(String::from("the point lies in the committed coset!"));
+        }
+        let layer = FriLayer::<F, T>::from_poly(
+            poly,
+            F::GENERATOR,
+            domain_size,
+            self.layer_folding_factor(domain_size),
+        );
+        let evaluation = poly.evaluate(&z);
+
+        // q(X) = (f(X) - y) / (X - z), the division is exact
+        let numerator = poly - &DensePolynomial::from_coefficients_slice(&[evaluation]);
+        let denominator = DensePolynomial::from_coefficients_slice(&[-z, F::ONE]);
+        let (quotient, _) = DenseOrSparsePolynomial::from(numerator)
+            .divide_with_q_and_r(&denominator.into())
+            .unwrap();
+
+        let transcript = Self::transcript(&layer.merkle_tree.root(), z, evaluation);
+        let quotient_proof = prove(
+            quotient,
+            domain_size,
+            self.blowup_factor,
+            self.folding_factor,
+            self.max_remainder_degree,
+            self.grinding_bits,
+            self.number_of_queries,
+            transcript,
+        );
+
+        let (evaluations, auth_paths) = quotient_proof
+            .decommitment_list
+            .iter()
+            .map(|decommitment| {
+                let index = decommitment.auth_paths[0].index;
+                (
+                    layer.coset_evaluations(index),
+                    layer.merkle_tree.generate_proof(index),
+                )
+            })
+            .unzip();
+
+        Ok(FriOpening {
+            evaluation,
+            quotient_proof,
+            evaluations,
+            auth_paths,
+        })
+    }
+
+    /// Verifies the opening of a committed polynomial at a point.
+    ///
+    /// The FRI proof is checked against the parameters of the scheme, so that it bounds the degree
+    /// of the quotient by `domain_size / blowup_factor`. Without this bound, a commitment to any
+    /// function over the coset, rather than to a polynomial, could be opened at any value.
+    ///
+    /// # Arguments
+    ///
+    /// * `commitment` - A reference to the commitment to the polynomial.
+    /// * `opening` - A reference to the opening to be verified.
+    /// * `z` - The point at which the polynomial was opened.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - `Ok` if the opening is valid, otherwise an error describing the failure.
+    pub fn verify(
+        &self,
+        commitment: &FriCommitment<F, T>,
+        opening: &FriOpening<F, T>,
+        z: F,
+    ) -> Result<(), String> {
+        let proof = &opening.quotient_proof;
+        if !is_valid_domain_size::<F>(commitment.domain_size) {
+            return Err(String::from("wrong domain size!"));
+        }
+        if proof.domain_size != commitment.domain_size {
+            return Err(String::from(
+                "the quotient proof does not match the commitment!",
+            ));
+        }
+        if Self::in_coset(z, commitment.domain_size) {
+            return Err(String::from("the point lies in the committed coset!"));
+        }
+        if opening.evaluations.len() != self.number_of_queries
+            || opening.auth_paths.len() != self.number_of_queries
+        {
+            return Err(String::from("wrong number of openings!"));
+        }
+
+        // the FRI queries are bound to the commitment, the point and the evaluation
+        let transcript = Self::transcript(&commitment.root, z, opening.evaluation);
+        let config = FriConfig::new(
+            self.blowup_factor,
+            self.folding_factor,
+            self.max_remainder_degree,
+            self.grinding_bits,
+            self.number_of_queries,
+        );
+        verify_with_transcript(proof, &config, transcript)?;
+
+        // q(x) = (f(x) - y) / (x - z) at every point of the queried cosets of the first layer
+        let domain = GeneralEvaluationDomain::<F>::new(commitment.domain_size).unwrap();
+        let stride = commitment.domain_size / self.layer_folding_factor(commitment.domain_size);
+        for ((decommitment, evals), path) in proof
+            .decommitment_list
+            .iter()
+            .zip(&opening.evaluations)
+            .zip(&opening.auth_paths)
+        {
+            if path.index != decommitment.auth_paths[0].index {
+                return Err(String::from("wrong index!"));
+            }
+            if evals.len() != decommitment.evaluations[0].len() {
+                return Err(String::from(
+                    "the evaluation does not correspond to given path!",
+                ));
+            }
+            if !verify_merkle_proof::<T, F>(path, evals, &commitment.root) {
+                return Err(String::from("verify Merkle path failed!"));
+            }
+            for (j, (quotient_eval, eval)) in
+                decommitment.evaluations[0].iter().zip(evals).enumerate()
+            {
+                let x = domain.element(path.index + j * stride) * F::GENERATOR;
+                if *quotient_eval * (x - z) != *eval - opening.evaluation {
+                    return Err(String::from("the quotient does not match the polynomial!"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Computes the size of the coset the polynomial is committed over.
+    fn domain_size(&self, poly: &DensePolynomial<F>) -> usize {
+        (poly.coeffs.len().max(1) * self.blowup_factor).next_power_of_two()
+    }
+
+    /// Computes the folding factor of the first FRI layer, capped by the domain size.
+    fn layer_folding_factor(&self, domain_size: usize) -> usize {
+        self.folding_factor.min(domain_size)
+    }
+
+    /// Checks whether a point lies in the coset of the given size.
+    fn in_coset(z: F, domain_size: usize) -> bool {
+        let domain = GeneralEvaluationDomain::<F>::new(domain_size).unwrap();
+        domain
+            .evaluate_vanishing_polynomial(z / F::GENERATOR)
+            .is_zero()
+    }
+
+    /// Starts the transcript of an opening, bound to the commitment, the point and the evaluation.
+    fn transcript(root: &T::Digest, z: F, evaluation: F) -> Transcript<T, F> {
+        let mut transcript = Transcript::new(F::ZERO);
+        transcript.digest_hash(root);
+        transcript.digest(z);
+        transcript.digest(evaluation);
+        transcript
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_ff::{FftField, Field, UniformRand};
+    use sha2::Sha256;
+
+    use crate::fields::goldilocks::Fq;
+    use crate::hashes::poseidon::Poseidon;
+
+    use super::*;
+
+    fn commit_open_verify<T: Hasher<Fq> + Clone>(folding_factor: usize) {
+        let rng = &mut rand::thread_rng();
+        let pcs = FriPcs::<T, Fq>::new(4, folding_factor, 0, 0, 8);
+        for degree in [0, 1, 7, 20] {
+            let poly = DensePolynomial::<Fq>::rand(degree, rng);
+            let commitment = pcs.commit(&poly);
+            let z = Fq::rand(rng);
+
+            let opening = pcs.open(&poly, z).unwrap();
+            assert_eq!(opening.evaluation, poly.evaluate(&z));
+            assert!(pcs.verify(&commitment, &opening, z).is_ok());
+            // a constant polynomial takes the same value at every point
+            if degree > 0 {
+                assert!(pcs.verify(&commitment, &opening, z + Fq::ONE).is_err());
+            }
+
+            let mut forged = opening.clone();
+            forged.evaluation += Fq::ONE;
+            assert!(pcs.verify(&commitment, &forged, z).is_err());
+        }
+    }
+
+    #[test]
+    fn test_fri_pcs() {
+        commit_open_verify::<Sha256>(2);
+        commit_open_verify::<Sha256>(8);
+        commit_open_verify::<Poseidon>(4);
+    }
+
+    #[test]
+    fn test_fri_pcs_other_polynomial() {
+        let rng = &mut rand::thread_rng();
+        let pcs = FriPcs::<Sha256, Fq>::new(4, 4, 3, 8, 8);
+        let poly = DensePolynomial::<Fq>::rand(15, rng);
+        let other = DensePolynomial::<Fq>::rand(15, rng);
+        let z = Fq::from(12345u64);
+
+        // an opening of another polynomial does not verify against the commitment
+        let opening = pcs.open(&other, z).unwrap();
+        assert_ne!(pcs.commit(&poly), pcs.commit(&other));
+        assert!(pcs.verify(&pcs.commit(&poly), &opening, z).is_err());
+
+        // nor does an opening whose cosets of f are taken from another tree
+        let mut forged = pcs.open(&poly, z).unwrap();
+        forged.evaluations = opening.evaluations.clone();
+        forged.auth_paths = opening.auth_paths.clone();
+        assert!(pcs.verify(&pcs.commit(&poly), &forged, z).is_err());
+    }
+
+    /// Opens a codeword over the coset the way `FriPcs::open` does, proving the quotient with FRI
+    /// under the given blowup factor and maximum remainder degree.
+    fn open_codeword(
+        pcs: &FriPcs<Sha256, Fq>,
+        poly: &DensePolynomial<Fq>,
+        domain_size: usize,
+        blowup_factor: usize,
+        max_remainder_degree: usize,
+        z: Fq,
+    ) -> (FriCommitment<Fq, Sha256>, FriOpening<Fq, Sha256>) {
+        let layer = FriLayer::<Fq, Sha256>::from_poly(poly, Fq::GENERATOR, domain_size, 2);
+        let evaluation = poly.evaluate(&z);
+        let numerator = poly - &DensePolynomial::from_coefficients_slice(&[evaluation]);
+        let denominator = DensePolynomial::from_coefficients_slice(&[-z, Fq::ONE]);
+        let (quotient, _) = DenseOrSparsePolynomial::from(numerator)
+            .divide_with_q_and_r(&denominator.into())
+            .unwrap();
+
+        let root = layer.merkle_tree.root();
+        let mut quotient_proof = prove(
+            quotient,
+            domain_size,
+            blowup_factor,
+            2,
+            max_remainder_degree,
+            0,
+            pcs.number_of_queries,
+            FriPcs::<Sha256, Fq>::transcript(&root, z, evaluation),
+        );
+        // the proof claims the parameters of the scheme
+        quotient_proof.blowup_factor = pcs.blowup_factor;
+        quotient_proof.max_remainder_degree = pcs.max_remainder_degree;
+
+        let (evaluations, auth_paths) = quotient_proof
+            .decommitment_list
+            .iter()
+            .map(|decommitment| {
+                let index = decommitment.auth_paths[0].index;
+                (
+                    layer.coset_evaluations(index),
+                    layer.merkle_tree.generate_proof(index),
+                )
+            })
+            .unzip();
+        let commitment = FriCommitment { root, domain_size };
+        let opening = FriOpening {
+            evaluation,
+            quotient_proof,
+            evaluations,
+            auth_paths,
+        };
+        (commitment, opening)
+    }
+
+    #[test]
+    fn test_fri_pcs_degree_bound() {
+        let rng = &mut rand::thread_rng();
+        let pcs = FriPcs::<Sha256, Fq>::new(4, 2, 0, 0, 16);
+        let z = Fq::rand(rng);
+
+        // a polynomial of degree smaller than 32 / 4 opens like `FriPcs::open`
+        let poly = DensePolynomial::<Fq>::rand(7, rng);
+        let (commitment, opening) = open_codeword(&pcs, &poly, 32, 4, 0, z);
+        assert_eq!(commitment, pcs.commit(&poly));
+        assert!(pcs.verify(&commitment, &opening, z).is_ok());
+
+        // any function over the coset of size 32 is the codeword of a polynomial of degree 31,
+        // whose quotient folds to a polynomial of degree 3 in the 3 layers of the scheme
+        let codeword = DensePolynomial::<Fq>::rand(31, rng);
+        let (commitment, opening) = open_codeword(&pcs, &codeword, 32, 1, 3, z);
+        assert_eq!(opening.quotient_proof.layers_root.len(), 3);
+        assert_eq!(
+            pcs.verify(&commitment, &opening, z),
+            Err(String::from(
+                "the degree of the final polynomial is too large!"
+            ))
+        );
+
+        // sending a constant final polynomial instead fails the folding checks
+        let mut forged = opening.clone();
+        forged.quotient_proof.final_poly.coeffs.truncate(1);
+        assert!(pcs.verify(&commitment, &forged, z).is_err());
+    }
+
+    #[test]
+    fn test_fri_pcs_point_in_coset() {
+        let pcs = FriPcs::<Sha256, Fq>::new(2, 2, 1, 0, 4);
+        let poly = DensePolynomial::from_coefficients_vec((1..=4).map(Fq::from).collect());
+        let commitment = pcs.commit(&poly);
+        assert_eq!(commitment.domain_size, 8);
+        let z = Fq::GENERATOR;
+        assert!(pcs.open(&poly, z).is_err());
+
+        let opening = pcs.open(&poly, Fq::from(3u64)).unwrap();
+        assert!(pcs.verify(&commitment, &opening, z).is_err());
+    }
+
+    #[test]
+    fn test_fri_pcs_forged_domain() {
+        let pcs = FriPcs::<Sha256, Fq>::new(2, 2, 1, 0, 4);
+        let poly = DensePolynomial::from_coefficients_vec((1..=4).map(Fq::from).collect());
+        let commitment = pcs.commit(&poly);
+        let z = Fq::from(3u64);
+        let opening = pcs.open(&poly, z).unwrap();
+        assert!(pcs.verify(&commitment, &opening, z).is_ok());
+
+        // the field has no radix-2 domain of this size
+        let mut forged_commitment = commitment.clone();
+        forged_commitment.domain_size = 1 << (Fq::TWO_ADICITY + 1);
+        let mut forged = opening.clone();
+        forged.quotient_proof.domain_size = forged_commitment.domain_size;
+        assert_eq!(
+            pcs.verify(&forged_commitment, &forged, z),
+            Err(String::from("wrong domain size!"))
+        );
+
+        let mut forged = opening;
+        forged.quotient_proof.coset = Fq::ZERO;
+        assert_eq!(
+            pcs.verify(&commitment, &forged, z),
+            Err(String::from("wrong coset!"))
+        );
+    }
+}
@@ -1,89 +1,136 @@
 use crate::fiat_shamir::transcript::Transcript;
+use crate::hasher::Hasher;
 use ark_ff::PrimeField;
 use ark_poly::univariate::DensePolynomial;
-use ark_poly::{DenseUVPolynomial, Polynomial};
-use sha2::Digest;
-use std::ops::Mul;
+use ark_poly::DenseUVPolynomial;
 
 use crate::fri_layer::FriLayer;
 use crate::merkle_tree::MerkleProof;
 
 #[derive(Clone, Debug)]
-pub struct Decommitment<F: PrimeField> {
-    pub evaluations: Vec<F>,
-    // A list of evaluations for each query index in all layers,
-    pub auth_paths: Vec<MerkleProof<F>>,
-    // and their authentication paths in Merkle Tree
-    pub sym_evaluations: Vec<F>,
-    // A list of evaluation for each symmetric query index in all layers,
-    pub sym_auth_paths: Vec<MerkleProof<F>>, // and their authentication paths in Merkle Tree
+pub struct Decommitment<F: PrimeField, H: Hasher<F>> {
+    pub evaluations: Vec<Vec<F>>,
+    // A list of evaluations over the folding coset of the query index in all layers,
+    pub auth_paths: Vec<MerkleProof<F, H>>, // and the authentication paths of the cosets in Merkle Tree
 }
 
 #[derive(Clone, Debug)]
-pub struct Proof<F: PrimeField> {
+pub struct Proof<F: PrimeField, H: Hasher<F>> {
     pub domain_size: usize,
     pub coset: F,
+    pub blowup_factor: usize,
+    pub folding_factor: usize,
+    pub max_remainder_degree: usize,
+    pub grinding_bits: usize,
     pub number_of_queries: usize,
-    pub layers_root: Vec<F>,
-    pub const_val: F,
-    pub decommitment_list: Vec<Decommitment<F>>,
+    pub layers_root: Vec<H::Digest>,
+    pub final_poly: DensePolynomial<F>,
+    pub pow_nonce: u64,
+    pub decommitment_list: Vec<Decommitment<F, H>>,
     // pub challenge_list: Vec<usize>
 }
 
-/// Reduce the power in half with formula new_coeff = even_coeff + `random_r` * odd_coeff
-fn fold_polynomial<F: PrimeField>(poly: &DensePolynomial<F>, random_r: F) -> DensePolynomial<F> {
-    let coeff = poly.coeffs.clone();
-    let even_coeff = coeff.iter().step_by(2).cloned().collect();
-    let odd_coeff_mul_r: Vec<F> = coeff.into_iter().skip(1).step_by(2).collect();
+/// Reduce the power by `folding_factor` with formula new_coeff[k] = sum_j `random_r`^j * coeff[folding_factor * k + j]
+fn fold_polynomial<F: PrimeField>(
+    poly: &DensePolynomial<F>,
+    random_r: F,
+    folding_factor: usize,
+) -> DensePolynomial<F> {
+    let new_coeff = poly
+        .coeffs
+        .chunks(folding_factor)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .rev()
+                .fold(F::ZERO, |acc, c| acc * random_r + c)
+        })
+        .collect();
+    DensePolynomial::from_coefficients_vec(new_coeff)
+}
 
-    let even_poly = DensePolynomial::from_coefficients_vec(even_coeff);
-    let odd_poly = DensePolynomial::from_coefficients_vec(odd_coeff_mul_r);
-    even_poly + odd_poly.mul(random_r)
+/// Compute the folding factor of each layer, and the degree bound of the final polynomial
+///
+/// The committed polynomial has degree smaller than `domain_size / blowup_factor`. Each layer folds
+/// the domain by `folding_factor`, or by the remaining domain size, until the degree bound is at most
+/// `max_remainder_degree + 1` and the remaining polynomial is sent in clear. At least one layer is
+/// committed, so that the queries always open the committed codeword.
+pub(crate) fn folding_schedule(
+    mut domain_size: usize,
+    blowup_factor: usize,
+    folding_factor: usize,
+    max_remainder_degree: usize,
+) -> (Vec<usize>, usize) {
+    let mut degree_bound = domain_size / blowup_factor;
+    let mut schedule = vec![];
+    loop {
+        let layer_folding_factor = folding_factor.min(domain_size);
+        schedule.push(layer_folding_factor);
+        domain_size /= layer_folding_factor;
+        degree_bound = degree_bound.div_ceil(layer_folding_factor);
+        if domain_size == 1 || degree_bound <= max_remainder_degree + 1 {
+            return (schedule, degree_bound);
+        }
+    }
 }
 
-/// Verify that `poly` have degree `k` or `number_layers`
+/// Verify that `poly` have degree smaller than the degree bound, folding it in each layer by the
+/// folding factor of the schedule
 ///
-/// Also create prove for the evaluation of the polynomial
-fn folding_phase<T: Digest + Default, F: PrimeField>(
+/// Also create prove for the evaluation of the polynomial, returning the final polynomial
+fn folding_phase<T: Hasher<F>, F: PrimeField>(
     mut poly: DensePolynomial<F>,
     mut coset: F,
     mut domain_size: usize,
-    number_layers: usize,
-) -> (F, Transcript<T, F>, Vec<FriLayer<F>>) {
-    let mut fri_layers: Vec<FriLayer<F>> = Vec::with_capacity(number_layers.ilog2() as usize + 1);
-    let mut transcript = Transcript::new(F::ZERO);
-
-    for _ in 0..number_layers {
-        let current_layer = FriLayer::from_poly(&poly, coset, domain_size);
-        transcript.digest(current_layer.merkle_tree.root());
+    schedule: &[usize],
+    transcript: &mut Transcript<T, F>,
+) -> (DensePolynomial<F>, Vec<FriLayer<F, T>>) {
+    let mut fri_layers: Vec<FriLayer<F, T>> = Vec::with_capacity(schedule.len());
+
+    for &layer_folding_factor in schedule {
+        let current_layer = FriLayer::from_poly(&poly, coset, domain_size, layer_folding_factor);
+        transcript.digest_hash(&current_layer.merkle_tree.root());
         eprintln!(
             "current_layer.merkle_tree.root() = {:#?}",
             current_layer.merkle_tree.root()
         );
         fri_layers.push(current_layer);
 
-        poly = fold_polynomial(&poly, transcript.generate_a_challenge());
-        coset = coset.square();
-        domain_size /= 2;
+        poly = fold_polynomial(
+            &poly,
+            transcript.generate_a_challenge(),
+            layer_folding_factor,
+        );
+        coset = coset.pow([layer_folding_factor as u64]);
+        domain_size /= layer_folding_factor;
     }
 
-    assert_eq!(poly.len(), 1);
-    let constant = poly.evaluate(&F::ZERO);
-    transcript.digest(constant);
+    digest_final_poly(transcript, &poly);
 
-    (constant, transcript, fri_layers)
+    (poly, fri_layers)
+}
+
+/// Digest the number of coefficients of the final polynomial, then its coefficients
+pub(crate) fn digest_final_poly<T: Hasher<F>, F: PrimeField>(
+    transcript: &mut Transcript<T, F>,
+    final_poly: &DensePolynomial<F>,
+) {
+    transcript.digest(F::from(final_poly.coeffs.len() as u64));
+    for coeff in &final_poly.coeffs {
+        transcript.digest(*coeff);
+    }
 }
 
 /// Create proof that prover did the folding phase correctly
 ///
-/// Evaluate current polynomial q(x) at two random symmetric point x1, x2 in subset Omega and
-/// verify if the next step is the result of a 1st degree polynomial of random_r with q(x1) and q(x2)
-fn query_phase<T: Digest + Default, F: PrimeField>(
+/// Open in every layer the coset of points folded together with a random query point of subset Omega,
+/// so that the verifier can interpolate them and check the folded value in the next layer
+fn query_phase<T: Hasher<F>, F: PrimeField>(
     number_of_queries: usize,
     domain_size: usize,
     transcript: &mut Transcript<T, F>,
-    fri_layers: &Vec<FriLayer<F>>,
-) -> (Vec<Decommitment<F>>, Vec<usize>) {
+    fri_layers: &Vec<FriLayer<F, T>>,
+) -> (Vec<Decommitment<F, T>>, Vec<usize>) {
     if fri_layers.is_empty() {
         return (vec![], vec![]);
     }
@@ -99,33 +146,19 @@ fn query_phase<T: Digest + Default, F: PrimeField>(
     for challenge in challenge_list.clone() {
         // generate decommitment for each challenge.
         let mut evaluations = vec![];
-        let mut sym_evaluations = vec![];
         let mut auth_paths = vec![];
-        let mut sym_auth_paths = vec![];
 
         for layer in fri_layers {
-            // index and sym_index will be symmetric of each other in layer.domain_size finite field
-            let index = challenge % layer.domain_size;
-            let sym_index = (index + layer.domain_size / 2) % layer.domain_size;
+            // the leaf bundling the query point together with the points it is folded with
+            let index = challenge % (layer.domain_size / layer.folding_factor);
 
-            let evaluation = layer.evaluations[index];
-            let sym_evaluation = layer.evaluations[sym_index];
-
-            let auth_path = layer.merkle_tree.generate_proof(index);
-            let sym_auth_path = layer.merkle_tree.generate_proof(sym_index);
-
-            evaluations.push(evaluation);
-            sym_evaluations.push(sym_evaluation);
-
-            auth_paths.push(auth_path);
-            sym_auth_paths.push(sym_auth_path);
+            evaluations.push(layer.coset_evaluations(index));
+            auth_paths.push(layer.merkle_tree.generate_proof(index));
         }
 
         let cur_decommitment = Decommitment {
             evaluations,
             auth_paths,
-            sym_evaluations,
-            sym_auth_paths,
         };
         decommitment_list.push(cur_decommitment);
     }
@@ -138,21 +171,71 @@ fn query_phase<T: Digest + Default, F: PrimeField>(
 /// Folding phase and query phase
 ///
 /// This is the only method you should call for proving
-pub fn generate_proof<T: Digest + Default, F: PrimeField>(
+///
+/// Before the query phase, the prover grinds a nonce whose hash with the transcript has
+/// `grinding_bits` leading zero bits, which adds `grinding_bits` bits of security to the queries
+///
+/// # Panics
+///
+/// This function will panic if `blowup_factor` is 0, if `folding_factor` is not a power of two
+/// greater than 1, or if `grinding_bits` is not smaller than the field size.
+pub fn generate_proof<T: Hasher<F>, F: PrimeField>(
     poly: DensePolynomial<F>,
     blowup_factor: usize,
+    folding_factor: usize,
+    max_remainder_degree: usize,
+    grinding_bits: usize,
     number_of_queries: usize,
-) -> Proof<F> {
+) -> Proof<F, T> {
     let domain_size = (poly.coeffs.len() * blowup_factor).next_power_of_two();
-    let coset = F::GENERATOR;
-    let number_of_layers = domain_size.ilog2() as usize;
+    prove(
+        poly,
+        domain_size,
+        blowup_factor,
+        folding_factor,
+        max_remainder_degree,
+        grinding_bits,
+        number_of_queries,
+        Transcript::<T, F>::new(F::ZERO),
+    )
+}
 
-    let (const_val, mut transcript, fri_layers) =
-        folding_phase::<T, F>(poly, coset, domain_size, number_of_layers);
+/// Generate a proof of FRI prover over the coset of the given size, continuing a transcript
+/// which may already bind the proof to other messages
+///
+/// The polynomial must have degree smaller than `domain_size / blowup_factor`
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn prove<T: Hasher<F>, F: PrimeField>(
+    poly: DensePolynomial<F>,
+    domain_size: usize,
+    blowup_factor: usize,
+    folding_factor: usize,
+    max_remainder_degree: usize,
+    grinding_bits: usize,
+    number_of_queries: usize,
+    mut transcript: Transcript<T, F>,
+) -> Proof<F, T> {
+    assert!(blowup_factor > 0, "the blowup factor must be positive");
+    assert!(
+        folding_factor > 1 && folding_factor.is_power_of_two(),
+        "the folding factor must be a power of two greater than 1"
+    );
+    let coset = F::GENERATOR;
+    let (schedule, degree_bound) = folding_schedule(
+        domain_size,
+        blowup_factor,
+        folding_factor,
+        max_remainder_degree,
+    );
+
+    let (final_poly, fri_layers) =
+        folding_phase::<T, F>(poly, coset, domain_size, &schedule, &mut transcript);
+    assert!(final_poly.coeffs.len() <= degree_bound);
+    let pow_nonce = transcript.grind(grinding_bits);
     let (decommitment_list, _) =
         query_phase(number_of_queries, domain_size, &mut transcript, &fri_layers);
 
-    let layers_root: Vec<F> = fri_layers
+    let layers_root: Vec<T::Digest> = fri_layers
         .into_iter()
         .map(|layer| layer.merkle_tree.root())
         .collect();
@@ -160,63 +243,131 @@ pub fn generate_proof<T: Digest + Default, F: PrimeField>(
     Proof {
         domain_size,
         coset,
+        blowup_factor,
+        folding_factor,
+        max_remainder_degree,
+        grinding_bits,
         number_of_queries,
         layers_root,
-        const_val,
+        final_poly,
+        pow_nonce,
         decommitment_list,
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use ark_ff::FftField;
+    use ark_ff::{FftField, Field};
     use ark_poly::univariate::DensePolynomial;
     use ark_poly::DenseUVPolynomial;
     use sha2::Sha256;
 
+    use crate::fiat_shamir::transcript::Transcript;
     use crate::fields::goldilocks::Fq;
-    use crate::prover::{fold_polynomial, folding_phase, query_phase};
+    use crate::prover::{fold_polynomial, folding_phase, folding_schedule, query_phase};
 
     #[test]
     fn test_fold_polynomial() {
         let coeff = vec![Fq::from(1), Fq::from(2), Fq::from(3), Fq::from(4)];
         let poly = DensePolynomial::from_coefficients_vec(coeff);
         let random_r = Fq::from(1);
-        let folded_poly = fold_polynomial::<Fq>(&poly, random_r);
+        let folded_poly = fold_polynomial::<Fq>(&poly, random_r, 2);
 
         let res_coeff = vec![Fq::from(3), Fq::from(7)];
         assert_eq!(
             folded_poly,
             DensePolynomial::from_coefficients_vec(res_coeff)
         );
+
+        // folding by 4 takes the powers of random_r: 1 + 2r + 3r^2 + 4r^3 and 5 + 6r
+        let coeff = (1..=6).map(Fq::from).collect::<Vec<_>>();
+        let poly = DensePolynomial::from_coefficients_vec(coeff);
+        let folded_poly = fold_polynomial::<Fq>(&poly, Fq::from(2), 4);
+        let res_coeff = vec![Fq::from(49), Fq::from(17)];
+        assert_eq!(
+            folded_poly,
+            DensePolynomial::from_coefficients_vec(res_coeff)
+        );
     }
 
     #[test]
     fn test_commit_phase() {
         let coeff = vec![Fq::from(1), Fq::from(2), Fq::from(3), Fq::from(4)];
         let poly = DensePolynomial::from_coefficients_vec(coeff);
-        let number_of_layers: usize = 2;
         let coset = Fq::GENERATOR;
-        let (_const_val, _transcript, fri_layers) =
-            folding_phase::<Sha256, Fq>(poly, coset, 4, number_of_layers);
+        let (_final_poly, fri_layers) =
+            folding_phase::<Sha256, Fq>(poly, coset, 4, &[2, 2], &mut Transcript::new(Fq::ZERO));
 
+        assert_eq!(fri_layers.len(), 2);
         assert_eq!(fri_layers[1].coset, Fq::from(49));
         assert_eq!(fri_layers[1].domain_size, 2);
     }
 
+    #[test]
+    fn test_commit_phase_folding_factor() {
+        let coeff = (1..=16).map(Fq::from).collect::<Vec<_>>();
+        let poly = DensePolynomial::from_coefficients_vec(coeff);
+        let coset = Fq::GENERATOR;
+        let (_final_poly, fri_layers) =
+            folding_phase::<Sha256, Fq>(poly, coset, 32, &[8, 4], &mut Transcript::new(Fq::ZERO));
+
+        // 32 points are folded by 8 then by the remaining 4
+        assert_eq!(fri_layers.len(), 2);
+        assert_eq!(fri_layers[0].merkle_tree.leaves.len(), 4);
+        assert_eq!(fri_layers[1].domain_size, 4);
+        assert_eq!(fri_layers[1].folding_factor, 4);
+        assert_eq!(fri_layers[1].coset, coset.pow([8]));
+    }
+
+    #[test]
+    fn test_folding_schedule() {
+        // folding to a constant
+        assert_eq!(folding_schedule(16, 2, 2, 0), (vec![2, 2, 2], 1));
+        assert_eq!(folding_schedule(32, 2, 4, 0), (vec![4, 4], 1));
+        // stopping once the degree bound is at most max_remainder_degree + 1
+        assert_eq!(folding_schedule(1024, 4, 2, 7), (vec![2, 2, 2, 2, 2], 8));
+        assert_eq!(folding_schedule(1024, 4, 16, 7), (vec![16, 16], 1));
+        // committing a layer even when the polynomial fits in the final polynomial
+        assert_eq!(folding_schedule(16, 2, 4, 15), (vec![4], 2));
+    }
+
+    #[test]
+    fn test_final_poly() {
+        let coeff = (1..=32).map(Fq::from).collect::<Vec<_>>();
+        let poly = DensePolynomial::from_coefficients_vec(coeff);
+        let (final_poly, fri_layers) = folding_phase::<Sha256, Fq>(
+            poly,
+            Fq::GENERATOR,
+            64,
+            &[2, 2],
+            &mut Transcript::new(Fq::ZERO),
+        );
+        assert_eq!(fri_layers.len(), 2);
+        assert_eq!(final_poly.coeffs.len(), 8);
+    }
+
     #[test]
     fn test_query_phase() {
-        let domain_size = 4;
+        let domain_size = 8;
         let coeff = vec![Fq::from(1), Fq::from(2), Fq::from(3), Fq::from(4)];
         let poly = DensePolynomial::from_coefficients_vec(coeff);
-        let number_of_layers: usize = 2;
         let coset = Fq::GENERATOR;
-        let (_const_val, mut transcript, fri_layers) =
-            folding_phase::<Sha256, Fq>(poly, coset, domain_size, number_of_layers);
-        let (decommitment_list, _) = query_phase(1, domain_size, &mut transcript, &fri_layers);
+        let mut transcript = Transcript::new(Fq::ZERO);
+        let (_final_poly, fri_layers) =
+            folding_phase::<Sha256, Fq>(poly, coset, domain_size, &[4, 2], &mut transcript);
+        let (decommitment_list, challenge_list) =
+            query_phase(1, domain_size, &mut transcript, &fri_layers);
         let decommitment = decommitment_list[0].clone();
-        let auth_paths_layer2 = decommitment.auth_paths[0].index;
-        let sym_auth_paths_layer2 = decommitment.sym_auth_paths[0].index;
-        assert_eq!((auth_paths_layer2 + 2) % domain_size, sym_auth_paths_layer2);
+
+        // the opened coset holds the query point and the point at distance domain_size / 4
+        let index = decommitment.auth_paths[0].index;
+        assert_eq!(index, challenge_list[0] % 2);
+        assert_eq!(
+            decommitment.evaluations[0],
+            (0..4)
+                .map(|j| fri_layers[0].evaluations[index + 2 * j])
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(decommitment.evaluations[1].len(), 2);
     }
 }
@@ -1,125 +1,258 @@
 use crate::fiat_shamir::transcript::Transcript;
+use crate::hasher::Hasher;
 use ark_ff::PrimeField;
-use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
-use sha2::Digest;
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain, Polynomial};
 
 use crate::merkle_tree::verify_merkle_proof;
-use crate::prover::{Decommitment, Proof};
+use crate::prover::{digest_final_poly, folding_schedule, Decommitment, Proof};
+
+/// Parameters the verifier expects the proofs to be generated with.
+///
+/// A proof repeats the parameters it was generated with, but the verifier takes them from its
+/// own configuration, as a prover could otherwise pick weaker ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FriConfig {
+    /// The ratio of the domain size to the degree bound of the polynomial.
+    pub blowup_factor: usize,
+    /// The number of points folded together in each layer, a power of two.
+    pub folding_factor: usize,
+    /// The maximum degree of the final polynomial, sent in clear.
+    pub max_remainder_degree: usize,
+    /// The number of leading zero bits of the proof of work before the queries.
+    pub grinding_bits: usize,
+    /// The number of queries.
+    pub number_of_queries: usize,
+}
+
+impl FriConfig {
+    /// Constructs a new `FriConfig` with the given FRI parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `blowup_factor` - The ratio of the domain size to the degree bound of the polynomial.
+    /// * `folding_factor` - The number of points folded together in each layer, a power of two.
+    /// * `max_remainder_degree` - The maximum degree of the final polynomial, sent in clear.
+    /// * `grinding_bits` - The number of leading zero bits of the proof of work before the queries.
+    /// * `number_of_queries` - The number of queries.
+    ///
+    /// # Returns
+    ///
+    /// * `FriConfig` - A new instance of the parameters.
+    pub fn new(
+        blowup_factor: usize,
+        folding_factor: usize,
+        max_remainder_degree: usize,
+        grinding_bits: usize,
+        number_of_queries: usize,
+    ) -> Self {
+        Self {
+            blowup_factor,
+            folding_factor,
+            max_remainder_degree,
+            grinding_bits,
+            number_of_queries,
+        }
+    }
+}
 
 /// Verify proof of FRI prover of 2 phase
 ///
 /// Folding phase and query phase
 ///
 /// This is the only method you should call for verifying
-pub fn verify<T: Digest + Default, F: PrimeField>(proof: Proof<F>) -> Result<(), String> {
+///
+/// The proof must have been generated with the parameters of `config`
+pub fn verify<T: Hasher<F>, F: PrimeField>(
+    proof: Proof<F, T>,
+    config: &FriConfig,
+) -> Result<(), String> {
+    verify_with_transcript(&proof, config, Transcript::<T, F>::new(F::ZERO))
+}
+
+/// Verify proof of FRI prover, continuing the transcript the prover started from
+pub(crate) fn verify_with_transcript<T: Hasher<F>, F: PrimeField>(
+    proof: &Proof<F, T>,
+    config: &FriConfig,
+    mut transcript: Transcript<T, F>,
+) -> Result<(), String> {
+    if config.blowup_factor == 0 {
+        return Err(String::from("wrong blowup factor!"));
+    }
+    if config.folding_factor < 2 || !config.folding_factor.is_power_of_two() {
+        return Err(String::from("wrong folding factor!"));
+    }
+    if proof.blowup_factor != config.blowup_factor
+        || proof.folding_factor != config.folding_factor
+        || proof.max_remainder_degree != config.max_remainder_degree
+        || proof.grinding_bits != config.grinding_bits
+        || proof.number_of_queries != config.number_of_queries
+    {
+        return Err(String::from("the proof does not match the parameters!"));
+    }
+    if proof.coset != F::GENERATOR {
+        return Err(String::from("wrong coset!"));
+    }
+    if !is_valid_domain_size::<F>(proof.domain_size) {
+        return Err(String::from("wrong domain size!"));
+    }
+    // every layer down to the final polynomial is committed, and opened by every query
+    let (schedule, degree_bound) = folding_schedule(
+        proof.domain_size,
+        config.blowup_factor,
+        config.folding_factor,
+        config.max_remainder_degree,
+    );
+    let number_of_layers = proof.layers_root.len();
+    if number_of_layers != schedule.len() {
+        return Err(String::from("wrong number of layers!"));
+    }
+    if proof.final_poly.coeffs.len() > degree_bound {
+        return Err(String::from(
+            "the degree of the final polynomial is too large!",
+        ));
+    }
+    if proof.decommitment_list.len() != config.number_of_queries {
+        return Err(String::from("wrong number of decommitments!"));
+    }
+    if proof.decommitment_list.iter().any(|decommitment| {
+        decommitment.evaluations.len() != number_of_layers
+            || decommitment.auth_paths.len() != number_of_layers
+    }) {
+        return Err(String::from("wrong number of layers in a decommitment!"));
+    }
+
     // regenerate random_r list
-    let merkle_roots = proof.layers_root;
-    let mut transcript = Transcript::<T, F>::new(F::ZERO);
-    let random_r_list = merkle_roots
-        .into_iter()
+    let random_r_list = proof
+        .layers_root
+        .iter()
         .map(|root| {
-            transcript.digest(root);
+            transcript.digest_hash(root);
             transcript.generate_a_challenge()
         })
         .collect::<Vec<_>>();
-    transcript.digest(proof.const_val);
+    digest_final_poly(&mut transcript, &proof.final_poly);
+    if config.grinding_bits >= F::MODULUS_BIT_SIZE as usize
+        || !transcript.verify_grinding(proof.pow_nonce, config.grinding_bits)
+    {
+        return Err(String::from("wrong proof-of-work nonce!"));
+    }
 
     // regenerate challenge list
     let new_challenge_list = transcript
-        .generate_challenge_list_usize(proof.number_of_queries)
+        .generate_challenge_list_usize(config.number_of_queries)
         .into_iter()
         .map(|v| v % proof.domain_size)
         .collect::<Vec<_>>();
 
     // verify each query
-    for (challenge, decommitment) in new_challenge_list
-        .into_iter()
-        .zip(proof.decommitment_list.into_iter())
-    {
-        verify_query(
-            &challenge,
-            &decommitment,
-            &random_r_list,
-            proof.domain_size,
-            proof.const_val,
-            proof.coset,
-        )?
+    for (challenge, decommitment) in new_challenge_list.iter().zip(&proof.decommitment_list) {
+        verify_query::<T, F>(challenge, decommitment, &random_r_list, &schedule, proof)?
     }
     Ok(())
 }
 
-fn verify_query<F: PrimeField>(
+/// Whether the field has a radix-2 domain of the size, the only domains the prover commits over
+pub(crate) fn is_valid_domain_size<F: PrimeField>(domain_size: usize) -> bool {
+    domain_size.is_power_of_two() && domain_size.trailing_zeros() <= F::TWO_ADICITY
+}
+
+fn verify_query<T: Hasher<F>, F: PrimeField>(
     challenge: &usize,
-    decommitment: &Decommitment<F>,
+    decommitment: &Decommitment<F, T>,
     random_r_list: &[F],
-    domain_size: usize,
-    const_val: F,
-    coset: F,
+    schedule: &[usize],
+    proof: &Proof<F, T>,
 ) -> Result<(), String> {
-    let mut cur_domain_size = domain_size;
-    let mut cur_coset = coset;
-    let two = F::from(2u128);
+    let mut cur_domain_size = proof.domain_size;
+    let mut cur_coset = proof.coset;
+    // the value the previous layer folds the query point to
+    let mut folded_value = None;
 
-    for (((((i, eval), path), sym_eval), sym_path), random_r) in decommitment
+    for ((((evals, path), random_r), root), &folding_factor) in decommitment
         .evaluations
         .iter()
-        .enumerate()
         .zip(decommitment.auth_paths.iter())
-        .zip(decommitment.sym_evaluations.iter())
-        .zip(decommitment.sym_auth_paths.iter())
         .zip(random_r_list.iter())
+        .zip(proof.layers_root.iter())
+        .zip(schedule.iter())
     {
-        let index = challenge % cur_domain_size;
-        let sym_index = (index + cur_domain_size / 2) % cur_domain_size;
+        let stride = cur_domain_size / folding_factor;
+        let index = challenge % stride;
         let cur_domain = <GeneralEvaluationDomain<F>>::new(cur_domain_size).unwrap();
 
-        // verify path of merkle root
-        if index != path.index || sym_index != sym_path.index {
+        // verify path of merkle root, which must be the committed root of the layer
+        if index != path.index {
             return Err(String::from("wrong index!"));
         }
 
-        if *eval != path.leaf_val || *sym_eval != sym_path.leaf_val {
+        if evals.len() != folding_factor {
             return Err(String::from(
                 "the evaluation does not correspond to given path!",
             ));
         }
 
-        if !verify_merkle_proof(path) || !verify_merkle_proof(sym_path) {
+        if !verify_merkle_proof::<T, F>(path, evals, root) {
             return Err(String::from("verify Merkle path failed!"));
         }
 
-        // verify folding
-        // Another way to compute q_fold let q_fold = (*eval + sym_eval) / two + (*random_r * (*eval - sym_eval)/(two * w_i));
-        let w_i = cur_domain.element(index) * cur_coset;
-
-        let q_fold =
-            (*random_r + w_i) * eval / (two * w_i) - (*random_r - w_i) * sym_eval / (two * w_i);
-
-        if i != decommitment.evaluations.len() - 1 {
-            let next_layer_evaluation = decommitment.evaluations[i + 1];
-            if q_fold != next_layer_evaluation {
+        // the query point sits at position (challenge % cur_domain_size) / stride of its coset
+        if let Some(folded_value) = folded_value {
+            if evals[(challenge % cur_domain_size) / stride] != folded_value {
                 return Err(String::from("folding wrong!"));
             }
-            cur_domain_size /= 2;
-            cur_coset = cur_coset.square();
-            continue;
         }
 
-        if q_fold != const_val {
-            // end of the folding process, the result must be equal to constant value
-            return Err(String::from("folding wrong!"));
-        }
+        // verify folding
+        // The folded value is the polynomial of degree < folding_factor interpolating the coset, evaluated at random_r
+        let points = (0..folding_factor)
+            .map(|j| cur_domain.element(index + j * stride) * cur_coset)
+            .collect::<Vec<_>>();
+        folded_value = Some(interpolate_at(&points, evals, *random_r));
+
+        cur_domain_size /= folding_factor;
+        cur_coset = cur_coset.pow([folding_factor as u64]);
+    }
+
+    // end of the folding process, the result must be the evaluation of the final polynomial
+    let cur_domain = <GeneralEvaluationDomain<F>>::new(cur_domain_size).unwrap();
+    let point = cur_domain.element(challenge % cur_domain_size) * cur_coset;
+    if folded_value != Some(proof.final_poly.evaluate(&point)) {
+        return Err(String::from("folding wrong!"));
     }
     Ok(())
 }
 
+/// Evaluates at `x` the polynomial of degree smaller than `points.len()` taking the values `evals`
+/// at `points`, with the Lagrange interpolation formula
+fn interpolate_at<F: PrimeField>(points: &[F], evals: &[F], x: F) -> F {
+    points
+        .iter()
+        .zip(evals)
+        .enumerate()
+        .map(|(i, (x_i, eval))| {
+            let (numerator, denominator) = points
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .fold((F::ONE, F::ONE), |(num, den), (_, x_j)| {
+                    (num * (x - x_j), den * (*x_i - x_j))
+                });
+            *eval * numerator / denominator
+        })
+        .sum()
+}
+
 #[cfg(test)]
 mod tests {
+    use ark_ff::{FftField, Field};
     use ark_poly::univariate::DensePolynomial;
     use ark_poly::DenseUVPolynomial;
     use sha2::Sha256;
 
     use crate::fields::goldilocks::Fq;
+    use crate::hashes::poseidon::Poseidon;
+    use crate::hashes::rescue::RescuePrime;
+    use crate::merkle_tree::MerkleTree;
     use crate::prover::generate_proof;
 
     use super::*;
@@ -131,8 +264,9 @@ mod tests {
 
         let blowup_factor: usize = 2;
         let number_of_queries: usize = 2;
-        let proof = generate_proof::<Sha256, Fq>(poly, blowup_factor, number_of_queries);
-        let result = verify::<Sha256, Fq>(proof);
+        let proof = generate_proof::<Sha256, Fq>(poly, blowup_factor, 2, 0, 0, number_of_queries);
+        let config = FriConfig::new(blowup_factor, 2, 0, 0, number_of_queries);
+        let result = verify::<Sha256, Fq>(proof, &config);
         assert!(result.is_ok());
     }
 
@@ -150,11 +284,264 @@ mod tests {
 
         let blowup_factor: usize = 2;
         let number_of_queries: usize = 2;
-        let proof = generate_proof::<Sha256, Fq>(poly, blowup_factor, number_of_queries);
-        let result = verify::<Sha256, Fq>(proof);
+        let proof = generate_proof::<Sha256, Fq>(poly, blowup_factor, 2, 0, 0, number_of_queries);
+        let config = FriConfig::new(blowup_factor, 2, 0, 0, number_of_queries);
+        let result = verify::<Sha256, Fq>(proof, &config);
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_verifier_algebraic_hashes() {
+        let coeff = (1..=8).map(Fq::from).collect::<Vec<_>>();
+        let poly = DensePolynomial::from_coefficients_vec(coeff);
+
+        let proof = generate_proof::<Poseidon, Fq>(poly.clone(), 2, 2, 0, 4, 2);
+        assert!(verify::<Poseidon, Fq>(proof, &FriConfig::new(2, 2, 0, 4, 2)).is_ok());
+
+        let proof = generate_proof::<RescuePrime, Fq>(poly, 2, 4, 1, 4, 2);
+        assert!(verify::<RescuePrime, Fq>(proof, &FriConfig::new(2, 4, 1, 4, 2)).is_ok());
+    }
+
+    /// Generates a proof for a polynomial of degree 7, with a blowup factor of 2, and the
+    /// parameters it is verified with.
+    fn proof_of_degree_7() -> (Proof<Fq, Sha256>, FriConfig) {
+        let coeff = (1..=8).map(Fq::from).collect::<Vec<_>>();
+        let poly = DensePolynomial::from_coefficients_vec(coeff);
+        let proof = generate_proof::<Sha256, Fq>(poly, 2, 2, 0, 0, 4);
+        (proof, FriConfig::new(2, 2, 0, 0, 4))
+    }
+
+    #[test]
+    fn test_verifier_forged_tree() {
+        let (proof, config) = proof_of_degree_7();
+        assert!(verify::<Sha256, Fq>(proof.clone(), &config).is_ok());
+
+        // the opened values are kept, but the path leads to a tree with other leaves
+        let mut forged = proof.clone();
+        let decommitment = &mut forged.decommitment_list[0];
+        let index = decommitment.auth_paths[0].index;
+        let mut leaves = vec![vec![Fq::from(0u64); 2]; proof.domain_size / 2];
+        leaves[index] = decommitment.evaluations[0].clone();
+        let tree = MerkleTree::<Fq, Sha256>::new(leaves);
+        decommitment.auth_paths[0] = tree.generate_proof(index);
+        assert!(verify_merkle_proof::<Sha256, Fq>(
+            &decommitment.auth_paths[0],
+            &decommitment.evaluations[0],
+            &tree.root()
+        ));
+        assert_eq!(
+            verify::<Sha256, Fq>(forged, &config),
+            Err(String::from("verify Merkle path failed!"))
+        );
+
+        // an opened value is changed
+        let mut forged = proof.clone();
+        forged.decommitment_list[0].evaluations[1][0] += Fq::from(1u64);
+        assert_eq!(
+            verify::<Sha256, Fq>(forged, &config),
+            Err(String::from("verify Merkle path failed!"))
+        );
+
+        // an opened value is dropped
+        let mut forged = proof.clone();
+        forged.decommitment_list[0].evaluations[1].pop();
+        assert_eq!(
+            verify::<Sha256, Fq>(forged, &config),
+            Err(String::from(
+                "the evaluation does not correspond to given path!"
+            ))
+        );
+
+        // the paths of a layer are swapped with those of another layer
+        let mut forged = proof.clone();
+        let decommitment = &mut forged.decommitment_list[1];
+        decommitment.auth_paths.swap(1, 2);
+        assert!(verify::<Sha256, Fq>(forged, &config).is_err());
+
+        // the committed roots are swapped
+        let mut forged = proof.clone();
+        forged.layers_root.swap(0, 1);
+        assert!(verify::<Sha256, Fq>(forged, &config).is_err());
+    }
+
+    #[test]
+    fn test_verifier_truncated_proof() {
+        let (proof, config) = proof_of_degree_7();
+
+        let mut forged = proof.clone();
+        forged.layers_root.pop();
+        assert!(verify::<Sha256, Fq>(forged, &config).is_err());
+
+        let mut forged = proof.clone();
+        forged.decommitment_list.pop();
+        assert!(verify::<Sha256, Fq>(forged, &config).is_err());
+
+        let mut forged = proof.clone();
+        forged.decommitment_list[0].auth_paths.pop();
+        assert!(verify::<Sha256, Fq>(forged, &config).is_err());
+
+        let mut forged = proof;
+        forged.domain_size *= 2;
+        assert!(verify::<Sha256, Fq>(forged, &config).is_err());
+    }
+
+    #[test]
+    fn test_verifier_forged_domain() {
+        let (proof, config) = proof_of_degree_7();
+
+        // a zero coset would make every queried point zero
+        let mut forged = proof.clone();
+        forged.coset = Fq::ZERO;
+        assert_eq!(
+            verify::<Sha256, Fq>(forged, &config),
+            Err(String::from("wrong coset!"))
+        );
+
+        let mut forged = proof.clone();
+        forged.coset = Fq::ONE;
+        assert_eq!(
+            verify::<Sha256, Fq>(forged, &config),
+            Err(String::from("wrong coset!"))
+        );
+
+        // the field has no radix-2 domain of this size
+        let mut forged = proof;
+        forged.domain_size = 1 << (Fq::TWO_ADICITY + 1);
+        assert_eq!(
+            verify::<Sha256, Fq>(forged, &config),
+            Err(String::from("wrong domain size!"))
+        );
+    }
+
+    #[test]
+    fn test_verifier_folding_factor() {
+        let coeff = (1..=20).map(Fq::from).collect::<Vec<_>>();
+        let poly = DensePolynomial::from_coefficients_vec(coeff);
+
+        for folding_factor in [2, 4, 8, 16] {
+            let proof = generate_proof::<Sha256, Fq>(poly.clone(), 4, folding_factor, 0, 0, 4);
+            let config = FriConfig::new(4, folding_factor, 0, 0, 4);
+            // a degree bound of 32 is folded to 1 in 5, 3, 2 and 2 layers
+            assert_eq!(
+                proof.layers_root.len(),
+                5usize.div_ceil(folding_factor.ilog2() as usize)
+            );
+            assert!(verify::<Sha256, Fq>(proof.clone(), &config).is_ok());
+
+            let mut forged = proof.clone();
+            forged.folding_factor = 2 * folding_factor;
+            assert_eq!(
+                verify::<Sha256, Fq>(forged, &config),
+                Err(String::from("the proof does not match the parameters!"))
+            );
+            let other = FriConfig::new(4, 2 * folding_factor, 0, 0, 4);
+            assert!(verify::<Sha256, Fq>(proof.clone(), &other).is_err());
+
+            let invalid = FriConfig::new(4, 3, 0, 0, 4);
+            assert_eq!(
+                verify::<Sha256, Fq>(proof, &invalid),
+                Err(String::from("wrong folding factor!"))
+            );
+        }
+    }
+
+    #[test]
+    fn test_verifier_blowup_factor() {
+        let coeff = (1..=16).map(Fq::from).collect::<Vec<_>>();
+        let poly = DensePolynomial::from_coefficients_vec(coeff);
+
+        // without blowup, the degree bound of 16 is folded to 1 in 4 layers
+        let proof = generate_proof::<Sha256, Fq>(poly, 1, 2, 0, 0, 4);
+        assert_eq!(proof.layers_root.len(), 4);
+        assert!(verify::<Sha256, Fq>(proof.clone(), &FriConfig::new(1, 2, 0, 0, 4)).is_ok());
+
+        // a verifier expecting a blowup factor of 2 tests a degree bound of 8
+        let config = FriConfig::new(2, 2, 0, 0, 4);
+        assert!(verify::<Sha256, Fq>(proof.clone(), &config).is_err());
+        let mut forged = proof;
+        forged.blowup_factor = 2;
+        assert_eq!(
+            verify::<Sha256, Fq>(forged, &config),
+            Err(String::from("wrong number of layers!"))
+        );
+    }
+
+    #[test]
+    fn test_verifier_final_poly() {
+        let coeff = (1..=64).map(Fq::from).collect::<Vec<_>>();
+        let poly = DensePolynomial::from_coefficients_vec(coeff);
+
+        // a degree bound of 64 is folded to 8 in 3 layers, then sent in clear
+        let proof = generate_proof::<Sha256, Fq>(poly.clone(), 2, 2, 7, 0, 4);
+        let config = FriConfig::new(2, 2, 7, 0, 4);
+        assert_eq!(proof.layers_root.len(), 3);
+        assert_eq!(proof.final_poly.coeffs.len(), 8);
+        assert!(verify::<Sha256, Fq>(proof.clone(), &config).is_ok());
+
+        let mut forged = proof.clone();
+        forged.final_poly.coeffs[7] += Fq::from(1u64);
+        assert!(verify::<Sha256, Fq>(forged, &config).is_err());
+
+        // the final polynomial exceeds the degree bound of the remaining layers
+        let mut forged = proof.clone();
+        forged.max_remainder_degree = 3;
+        forged.layers_root.pop();
+        assert!(verify::<Sha256, Fq>(forged, &config).is_err());
+        let mut forged = proof.clone();
+        forged.final_poly.coeffs.push(Fq::from(1u64));
+        assert_eq!(
+            verify::<Sha256, Fq>(forged, &config),
+            Err(String::from(
+                "the degree of the final polynomial is too large!"
+            ))
+        );
+
+        // a degree bound of 64 is folded by 4 to 16 in a single layer
+        let proof = generate_proof::<Sha256, Fq>(poly, 4, 4, 15, 0, 4);
+        assert_eq!(proof.layers_root.len(), 1);
+        assert!(verify::<Sha256, Fq>(proof, &FriConfig::new(4, 4, 15, 0, 4)).is_ok());
+    }
+
+    #[test]
+    fn test_verifier_grinding() {
+        let coeff = (1..=16).map(Fq::from).collect::<Vec<_>>();
+        let poly = DensePolynomial::from_coefficients_vec(coeff);
+
+        let proof = generate_proof::<Sha256, Fq>(poly, 2, 2, 1, 12, 2);
+        let config = FriConfig::new(2, 2, 1, 12, 2);
+        assert!(verify::<Sha256, Fq>(proof.clone(), &config).is_ok());
+
+        let mut forged = proof.clone();
+        forged.pow_nonce += 1;
+        assert_eq!(
+            verify::<Sha256, Fq>(forged, &config),
+            Err(String::from("wrong proof-of-work nonce!"))
+        );
+
+        // the prover cannot lower the proof of work
+        let mut forged = proof.clone();
+        forged.grinding_bits = 0;
+        assert_eq!(
+            verify::<Sha256, Fq>(forged, &config),
+            Err(String::from("the proof does not match the parameters!"))
+        );
+
+        let coeff = (1..=16).map(Fq::from).collect::<Vec<_>>();
+        let poly = DensePolynomial::from_coefficients_vec(coeff);
+        let mut forged = generate_proof::<Sha256, Fq>(poly, 2, 2, 1, 0, 2);
+        forged.grinding_bits = 12;
+        assert!(verify::<Sha256, Fq>(forged, &config).is_err());
+
+        // the nonce does not satisfy a harder proof of work
+        let harder = FriConfig::new(2, 2, 1, 64, 2);
+        let mut forged = proof;
+        forged.grinding_bits = 64;
+        assert_eq!(
+            verify::<Sha256, Fq>(forged, &harder),
+            Err(String::from("wrong proof-of-work nonce!"))
+        );
+    }
+
     #[test]
     fn test_verifier3() {
         let coeff = vec![Fq::from(1), Fq::from(2), Fq::from(3), Fq::from(4)];
@@ -162,10 +549,12 @@ mod tests {
 
         let blowup_factor: usize = 2;
         let number_of_queries: usize = 2;
-        let mut proof = generate_proof::<Sha256, Fq>(poly, blowup_factor, number_of_queries);
+        let mut proof =
+            generate_proof::<Sha256, Fq>(poly, blowup_factor, 2, 0, 0, number_of_queries);
 
-        proof.const_val -= Fq::from(1);
-        let result = verify::<Sha256, Fq>(proof);
+        proof.final_poly.coeffs[0] -= Fq::from(1);
+        let config = FriConfig::new(blowup_factor, 2, 0, 0, number_of_queries);
+        let result = verify::<Sha256, Fq>(proof, &config);
         assert!(result.is_err());
     }
 }
@@ -0,0 +1,105 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+
+use ark_bls12_381::Bls12_381;
+use clap::{Parser, Subcommand};
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use sha2::{Digest, Sha256};
+
+use kzg::ceremony::Ceremony;
+
+/// Local driver of a multi-party trusted setup ceremony over BLS12-381.
+///
+/// Participants pass the ceremony file around, each one running `contribute`
+/// on the latest file and handing the output to the next participant.
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Start a new ceremony for a circuit size
+    New {
+        #[arg(long)]
+        circuit_size: usize,
+        #[arg(long)]
+        output: String,
+    },
+    /// Add a contribution to a ceremony file
+    Contribute {
+        #[arg(long)]
+        input: String,
+        #[arg(long)]
+        output: String,
+        /// Extra entropy mixed into the secret
+        #[arg(long, default_value = "")]
+        entropy: String,
+    },
+    /// Verify the whole chain of contributions of a ceremony file
+    Verify {
+        #[arg(long)]
+        input: String,
+    },
+}
+
+fn read(path: &str) -> Result<Ceremony<Bls12_381>, String> {
+    let file = File::open(path).map_err(|e| format!("Cannot open {}: {}", path, e))?;
+    Ceremony::read(BufReader::new(file))
+}
+
+fn write(ceremony: &Ceremony<Bls12_381>, path: &str) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| format!("Cannot create {}: {}", path, e))?;
+    ceremony.write(BufWriter::new(file))
+}
+
+fn run(cli: Cli) -> Result<(), String> {
+    match cli.command {
+        Command::New {
+            circuit_size,
+            output,
+        } => {
+            write(&Ceremony::new(circuit_size), &output)?;
+            println!("Started a ceremony for circuit size {}", circuit_size);
+        }
+        Command::Contribute {
+            input,
+            output,
+            entropy,
+        } => {
+            let mut ceremony = read(&input)?;
+            let mut seed = [0u8; 32];
+            rand::thread_rng().fill_bytes(&mut seed);
+            let seed = Sha256::new()
+                .chain_update(seed)
+                .chain_update(entropy.as_bytes())
+                .finalize();
+            let contribution = ceremony.contribute(&mut StdRng::from_seed(seed.into()));
+            write(&ceremony, &output)?;
+            println!(
+                "Contribution {} added, public key: {}",
+                ceremony.contributions().len(),
+                contribution.public_key
+            );
+        }
+        Command::Verify { input } => {
+            let ceremony = read(&input)?;
+            ceremony.verify()?;
+            println!(
+                "Ceremony is valid: {} contributions, {} powers",
+                ceremony.contributions().len(),
+                ceremony.srs().g1_points().len()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run(Cli::parse()) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
@@ -1,4 +1,4 @@
-use ark_bls12_381::Fr;
+use ark_bls12_381::{Bls12_381, Fr};
 use ark_poly::univariate::DensePolynomial;
 use ark_poly::{DenseUVPolynomial, Polynomial};
 use kzg::scheme::KzgScheme;
@@ -6,7 +6,7 @@ use kzg::srs::Srs;
 
 fn main() {
     // trusted setup
-    let srs = Srs::new(10);
+    let srs = Srs::<Bls12_381>::new(10);
     let scheme = KzgScheme::new(srs);
 
     // polynomial x^3 + 3x + 5
@@ -17,10 +17,10 @@ fn main() {
     assert_eq!(v, Fr::from(9));
 
     // commit poly
-    let commitment = scheme.commit(&poly);
+    let commitment = scheme.commit(&poly).unwrap();
     // opening point at p = 4.
     let opening_pos = Fr::from(4);
-    let opening = scheme.open(&poly, opening_pos);
+    let opening = scheme.open(&poly, opening_pos).unwrap();
 
     assert!(scheme.verify(&commitment, &opening, opening_pos));
 }
@@ -0,0 +1,46 @@
+use std::time::Instant;
+
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::DenseUVPolynomial;
+use ark_serialize::{CanonicalSerialize, Compress};
+use kzg::ipa::{IpaParams, IpaScheme};
+use kzg::scheme::KzgScheme;
+use kzg::srs::Srs;
+
+/// Compares proof size and verifier time of KZG and IPA openings of the same polynomial.
+fn main() {
+    let rng = &mut rand::thread_rng();
+    for degree in [15, 255, 1023] {
+        let poly = DensePolynomial::<Fr>::rand(degree, rng);
+        let z = Fr::from(7);
+
+        // trusted setup
+        let kzg = KzgScheme::new(Srs::<Bls12_381>::new(degree));
+        let commitment = kzg.commit(&poly).unwrap();
+        let opening = kzg.open(&poly, z).unwrap();
+        let start = Instant::now();
+        assert!(kzg.verify(&commitment, &opening, z));
+        let kzg_time = start.elapsed();
+
+        // transparent setup
+        let ipa = IpaScheme::new(IpaParams::<Bls12_381>::new(degree));
+        let commitment = ipa.commit(&poly).unwrap();
+        let opening_ipa = ipa.open(&poly, z).unwrap();
+        let start = Instant::now();
+        assert!(ipa.verify(&commitment, &opening_ipa, z));
+        let ipa_time = start.elapsed();
+
+        println!("degree {}", degree);
+        println!(
+            "  kzg: {} bytes, verified in {:?}",
+            opening.serialized_size(Compress::Yes),
+            kzg_time
+        );
+        println!(
+            "  ipa: {} bytes, verified in {:?}",
+            opening_ipa.serialized_size(Compress::Yes),
+            ipa_time
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use std::io::{Read, Write};
+use std::ops::Mul;
+
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{One, PrimeField, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+use crate::srs::Srs;
+use crate::types::{G1Point, G2Point, ScalarField};
+
+/// A single participant's contribution to the trusted setup ceremony.
+///
+/// The participant multiplies the secret of the previous SRS by a fresh secret `s`,
+/// publishes `s` in G1 and proves knowledge of it with a Schnorr proof
+/// bound to the state it contributed to.
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Contribution<E: Pairing> {
+    /// Generator point in G1 multiplied by the participant's secret.
+    pub public_key: G1Point<E>,
+    /// Commitment point of the Schnorr proof of knowledge.
+    pub pok_commitment: G1Point<E>,
+    /// Response of the Schnorr proof of knowledge.
+    pub pok_response: ScalarField<E>,
+    /// Generator point in G2 multiplied by the accumulated secret after this contribution.
+    pub g2s_after: G2Point<E>,
+}
+
+/// Multi-party trusted setup ceremony producing an `Srs`.
+///
+/// The ceremony starts from an SRS whose secret is one. Each participant rerandomizes
+/// the current powers with a fresh secret and appends a `Contribution`, so the final secret
+/// stays unknown as long as one participant discards theirs. Anyone can check the whole
+/// chain of contributions with `Ceremony::verify`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ceremony<E: Pairing> {
+    /// The SRS after the latest contribution.
+    srs: Srs<E>,
+    /// All contributions, in order.
+    contributions: Vec<Contribution<E>>,
+}
+
+impl<E: Pairing> Ceremony<E> {
+    /// Starts a new ceremony for the specified circuit size.
+    ///
+    /// # Parameters
+    ///
+    /// - `circuit_size`: The size of the circuit.
+    ///
+    /// # Returns
+    ///
+    /// A new `Ceremony` instance without contributions.
+    pub fn new(circuit_size: usize) -> Self {
+        Self {
+            srs: Srs::new_from_secret(ScalarField::<E>::one(), circuit_size),
+            contributions: vec![],
+        }
+    }
+
+    /// Returns the SRS after the latest contribution.
+    pub fn srs(&self) -> Srs<E> {
+        self.srs.clone()
+    }
+
+    /// Returns all contributions, in order.
+    pub fn contributions(&self) -> &[Contribution<E>] {
+        &self.contributions
+    }
+
+    /// Contributes a fresh secret sampled from `rng`.
+    ///
+    /// # Parameters
+    ///
+    /// - `rng`: Source of randomness for the secret and the proof of knowledge.
+    ///
+    /// # Returns
+    ///
+    /// The published contribution.
+    pub fn contribute<R: RngCore>(&mut self, rng: &mut R) -> Contribution<E> {
+        let mut secret = ScalarField::<E>::rand(rng);
+        while secret.is_zero() {
+            secret = ScalarField::<E>::rand(rng);
+        }
+        let nonce = ScalarField::<E>::rand(rng);
+        self.contribute_with_secret(secret, nonce)
+    }
+
+    /// Contributes the provided secret, using `nonce` for the proof of knowledge.
+    ///
+    /// # Parameters
+    ///
+    /// - `secret`: The participant's secret, must be non-zero and discarded afterwards.
+    /// - `nonce`: Random nonce of the Schnorr proof, must never be reused.
+    ///
+    /// # Returns
+    ///
+    /// The published contribution.
+    pub fn contribute_with_secret(
+        &mut self,
+        secret: ScalarField<E>,
+        nonce: ScalarField<E>,
+    ) -> Contribution<E> {
+        let g1 = G1Point::<E>::generator();
+        let g2s_before = self.srs.g2s();
+        let public_key = g1.mul(secret).into_affine();
+        let pok_commitment = g1.mul(nonce).into_affine();
+        let challenge = Self::challenge(&g2s_before, &public_key, &pok_commitment);
+
+        self.srs.rerandomize(secret);
+        let contribution = Contribution {
+            public_key,
+            pok_commitment,
+            pok_response: nonce + challenge * secret,
+            g2s_after: self.srs.g2s(),
+        };
+        self.contributions.push(contribution.clone());
+        contribution
+    }
+
+    /// Verifies the whole chain of contributions and the resulting SRS.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if every proof of knowledge is valid, every contribution extends the previous one,
+    /// and the SRS powers match the last contribution, otherwise an error describing the failure.
+    pub fn verify(&self) -> Result<(), String> {
+        if self.contributions.is_empty() {
+            return Err("The ceremony has no contributions".to_string());
+        }
+        let g1 = G1Point::<E>::generator();
+        let g2 = G2Point::<E>::generator();
+        let mut g2s_before = g2;
+        for (i, contribution) in self.contributions.iter().enumerate() {
+            if contribution.public_key.is_zero() {
+                return Err(format!("Contribution {} has a zero secret", i));
+            }
+            // Schnorr: response * g1 = commitment + challenge * public_key
+            let challenge = Self::challenge(
+                &g2s_before,
+                &contribution.public_key,
+                &contribution.pok_commitment,
+            );
+            let lhs = g1.mul(contribution.pok_response);
+            let rhs = contribution.pok_commitment + contribution.public_key.mul(challenge);
+            if lhs != rhs {
+                return Err(format!("Invalid proof of knowledge in contribution {}", i));
+            }
+            // the new secret is the previous one multiplied by the participant's secret
+            let lhs = E::pairing(contribution.public_key, g2s_before);
+            let rhs = E::pairing(g1, contribution.g2s_after);
+            if lhs != rhs {
+                return Err(format!(
+                    "Contribution {} does not extend the previous one",
+                    i
+                ));
+            }
+            g2s_before = contribution.g2s_after;
+        }
+
+        if self.srs.g2s() != g2s_before {
+            return Err("The SRS does not match the last contribution".to_string());
+        }
+        self.srs.verify().map_err(|e| e.to_string())
+    }
+
+    /// Writes the ceremony state so that it can be passed to the next participant.
+    ///
+    /// # Parameters
+    ///
+    /// - `writer`: Destination of the ceremony state.
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<(), String> {
+        self.srs
+            .g1_points()
+            .serialize_compressed(&mut writer)
+            .and_then(|_| self.srs.g2s().serialize_compressed(&mut writer))
+            .and_then(|_| self.contributions.serialize_compressed(&mut writer))
+            .map_err(|e| format!("Failed to write ceremony: {}", e))
+    }
+
+    /// Reads a ceremony state written by `Ceremony::write`, validating every point.
+    ///
+    /// The chain of contributions is not verified, call `Ceremony::verify` for that.
+    ///
+    /// # Parameters
+    ///
+    /// - `reader`: Source of the ceremony state.
+    ///
+    /// # Returns
+    ///
+    /// The ceremony, or an error if the state is malformed.
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, String> {
+        let read_error = |e| format!("Failed to read ceremony: {}", e);
+        let g1_points =
+            Vec::<G1Point<E>>::deserialize_compressed(&mut reader).map_err(read_error)?;
+        let g2s_point = G2Point::<E>::deserialize_compressed(&mut reader).map_err(read_error)?;
+        let contributions =
+            Vec::<Contribution<E>>::deserialize_compressed(&mut reader).map_err(read_error)?;
+        if g1_points.is_empty() {
+            return Err("The ceremony SRS is empty".to_string());
+        }
+        Ok(Self {
+            srs: Srs::from_points(g1_points, G2Point::<E>::generator(), g2s_point),
+            contributions,
+        })
+    }
+
+    /// Derives the Fiat-Shamir challenge of a proof of knowledge,
+    /// bound to the state the contribution was made on.
+    fn challenge(
+        g2s_before: &G2Point<E>,
+        public_key: &G1Point<E>,
+        pok_commitment: &G1Point<E>,
+    ) -> ScalarField<E> {
+        let mut data = Vec::new();
+        g2s_before
+            .serialize_compressed(&mut data)
+            .and_then(|_| public_key.serialize_compressed(&mut data))
+            .and_then(|_| pok_commitment.serialize_compressed(&mut data))
+            .expect("serializing into a Vec should be infallible");
+        ScalarField::<E>::from_le_bytes_mod_order(&Sha256::digest(&data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_bn254::Bn254;
+
+    use super::*;
+
+    #[test]
+    fn ceremony_test() {
+        let mut ceremony = Ceremony::<Bls12_381>::new(5);
+        assert!(ceremony.verify().is_err());
+
+        let secrets = [Fr::from(3u64), Fr::from(5u64), Fr::from(7u64)];
+        for (i, secret) in secrets.iter().enumerate() {
+            ceremony.contribute_with_secret(*secret, Fr::from(100 + i as u64));
+            assert!(ceremony.verify().is_ok());
+        }
+        assert_eq!(ceremony.contributions().len(), 3);
+        assert_eq!(
+            ceremony.srs(),
+            Srs::new_from_secret(Fr::from(3u64 * 5 * 7), 5)
+        );
+
+        let mut ceremony = Ceremony::<Bn254>::new(5);
+        ceremony.contribute(&mut rand::thread_rng());
+        ceremony.contribute(&mut rand::thread_rng());
+        assert!(ceremony.verify().is_ok());
+    }
+
+    #[test]
+    fn ceremony_file_test() {
+        let mut ceremony = Ceremony::<Bls12_381>::new(4);
+        let mut file = Vec::new();
+        ceremony.write(&mut file).unwrap();
+
+        // each participant reads the previous state, contributes and passes it on
+        for _ in 0..3 {
+            let mut next = Ceremony::<Bls12_381>::read(&file[..]).unwrap();
+            next.contribute(&mut rand::thread_rng());
+            file.clear();
+            next.write(&mut file).unwrap();
+            ceremony = next;
+        }
+        let loaded = Ceremony::<Bls12_381>::read(&file[..]).unwrap();
+        assert_eq!(loaded, ceremony);
+        assert!(loaded.verify().is_ok());
+
+        assert!(Ceremony::<Bls12_381>::read(&file[..file.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn reject_forged_ceremony_test() {
+        let mut ceremony = Ceremony::<Bls12_381>::new(4);
+        ceremony.contribute(&mut rand::thread_rng());
+        ceremony.contribute(&mut rand::thread_rng());
+
+        // a proof of knowledge that does not match the public key
+        let mut forged = ceremony.clone();
+        forged.contributions[1].pok_response += Fr::one();
+        assert!(forged.verify().is_err());
+
+        // a contribution replaced by one made on another state
+        let mut other = Ceremony::<Bls12_381>::new(4);
+        other.contribute(&mut rand::thread_rng());
+        let mut forged = ceremony.clone();
+        forged.contributions[0] = other.contributions[0].clone();
+        assert!(forged.verify().is_err());
+
+        // an SRS that was not produced by the contributions
+        let mut forged = ceremony.clone();
+        forged.srs = Srs::new(4);
+        assert!(forged.verify().is_err());
+
+        // an SRS whose powers do not follow the last contribution
+        let mut forged = ceremony.clone();
+        let mut g1_points = ceremony.srs.g1_points();
+        g1_points.swap(2, 3);
+        forged.srs = Srs::from_points(g1_points, ceremony.srs.g2(), ceremony.srs.g2s());
+        assert!(forged.verify().is_err());
+    }
+}
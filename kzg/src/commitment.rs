@@ -1,12 +1,15 @@
+use ark_ec::pairing::Pairing;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
 use crate::types::G1Point;
 
 /// Commitment contains result
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct KzgCommitment(pub G1Point);
+#[derive(Debug, Clone, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct KzgCommitment<E: Pairing>(pub G1Point<E>);
 
-impl KzgCommitment {
+impl<E: Pairing> KzgCommitment<E> {
     /// A reference to the inner `G1Point` contained within the commitment.
-    pub fn inner(&self) -> &G1Point {
+    pub fn inner(&self) -> &G1Point<E> {
         &self.0
     }
 }
@@ -15,18 +18,23 @@ impl KzgCommitment {
 mod tests {
     use std::ops::Mul;
 
-    use ark_bls12_381::Fr;
+    use ark_bls12_377::Bls12_377;
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_bn254::Bn254;
+    use ark_ec::pairing::Pairing;
     use ark_ec::{AffineRepr, CurveGroup};
-    use ark_ff::One;
-    use ark_poly::{DenseUVPolynomial, Polynomial};
+    use ark_ff::{One, Zero};
+    use ark_poly::{DenseUVPolynomial, EvaluationDomain, Polynomial};
     use rand::rngs::StdRng;
     use rand::{Rng, SeedableRng};
 
     use crate::commitment::KzgCommitment;
-    use crate::opening::KzgOpening;
+    use crate::error::KzgError;
+    use crate::opening::{KzgMultiPointOpening, KzgOpening};
     use crate::scheme::KzgScheme;
     use crate::srs::Srs;
-    use crate::types::{G1Point, Poly};
+    use crate::types::{G1Point, Poly, ScalarField};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
 
     #[test]
     /// Tests the commitment functionality in the KZG scheme.
@@ -35,21 +43,21 @@ mod tests {
     /// opening the commitment, and verifying the opening.
     fn commit() {
         let secret = Fr::from(2);
-        let srs = Srs::new_from_secret(secret, 10);
+        let srs = Srs::<Bls12_381>::new_from_secret(secret, 10);
         let scheme = KzgScheme::new(srs);
-        let poly = Poly::from_coefficients_slice(&[1.into(), 2.into(), 3.into()]);
-        let commitment = scheme.commit(&poly);
+        let poly = Poly::<Bls12_381>::from_coefficients_slice(&[1.into(), 2.into(), 3.into()]);
+        let commitment = scheme.commit(&poly).unwrap();
         let d = Fr::one();
 
         assert_eq!(poly.evaluate(&d), 6.into());
 
         assert_eq!(
             commitment.0,
-            G1Point::generator()
+            G1Point::<Bls12_381>::generator()
                 .mul(poly.evaluate(&secret))
                 .into_affine()
         );
-        let opening = scheme.open(&poly, d);
+        let opening = scheme.open(&poly, d).unwrap();
         assert!(scheme.verify(&commitment, &opening, d));
     }
 
@@ -59,14 +67,14 @@ mod tests {
     /// This test validates the correctness of scalar multiplying a commitment
     /// by a factor in the KZG scheme.
     fn scalar_mul() {
-        let srs = Srs::new(5);
+        let srs = Srs::<Bls12_381>::new(5);
         let scheme = KzgScheme::new(srs);
         let coeffs = [1, 2, 3, 4, 5].map(Fr::from);
-        let poly = Poly::from_coefficients_slice(&coeffs);
-        let commit1 = scheme.commit(&poly);
+        let poly = Poly::<Bls12_381>::from_coefficients_slice(&coeffs);
+        let commit1 = scheme.commit(&poly).unwrap();
         let factor = Fr::from(9);
         let poly2 = poly.mul(factor);
-        let commit2 = scheme.commit(&poly2);
+        let commit2 = scheme.commit(&poly2).unwrap();
         assert_eq!(commit1 * factor, commit2);
     }
 
@@ -76,12 +84,12 @@ mod tests {
     /// This test validates the correctness of aggregating multiple commitments
     /// by a random challenge
     fn aggregate_commitments() {
-        let srs = Srs::new(5);
+        let srs = Srs::<Bls12_381>::new(5);
         let scheme = KzgScheme::new(srs);
-        let f1 = Poly::from_coefficients_slice(&[1, 2, 3, 4, 5].map(Fr::from));
-        let f2 = Poly::from_coefficients_slice(&[1, 2, 3, 4, 8].map(Fr::from));
-        let c1 = scheme.commit(&f1);
-        let c2 = scheme.commit(&f2);
+        let f1 = Poly::<Bls12_381>::from_coefficients_slice(&[1, 2, 3, 4, 5].map(Fr::from));
+        let f2 = Poly::<Bls12_381>::from_coefficients_slice(&[1, 2, 3, 4, 8].map(Fr::from));
+        let c1 = scheme.commit(&f1).unwrap();
+        let c2 = scheme.commit(&f2).unwrap();
         let challenge: u128 = StdRng::from_entropy().gen();
         let challenge = Fr::from(challenge);
         let batch = KzgScheme::aggregate_commitments(&vec![&c1, &c2], &challenge);
@@ -93,14 +101,14 @@ mod tests {
     ///
     /// This test validates the correctness of verifying multiple proofs
     fn batch_verify() {
-        let srs = Srs::new(5);
+        let srs = Srs::<Bls12_381>::new(5);
         let scheme = KzgScheme::new(srs);
-        let f1 = Poly::from_coefficients_slice(&[1, 2, 3, 4, 5].map(Fr::from));
-        let f2 = Poly::from_coefficients_slice(&[1, 8, 3, 4, 8].map(Fr::from));
-        let f3 = Poly::from_coefficients_slice(&[12, 8, 3, 9, 8].map(Fr::from));
-        let f4 = Poly::from_coefficients_slice(&[95, 8, 0, 9, 8].map(Fr::from));
-        let f5 = Poly::from_coefficients_slice(&[12, 0, 3, 9, 0].map(Fr::from));
-        let f: Vec<Poly> = vec![f1, f2, f3, f4, f5];
+        let f1 = Poly::<Bls12_381>::from_coefficients_slice(&[1, 2, 3, 4, 5].map(Fr::from));
+        let f2 = Poly::<Bls12_381>::from_coefficients_slice(&[1, 8, 3, 4, 8].map(Fr::from));
+        let f3 = Poly::<Bls12_381>::from_coefficients_slice(&[12, 8, 3, 9, 8].map(Fr::from));
+        let f4 = Poly::<Bls12_381>::from_coefficients_slice(&[95, 8, 0, 9, 8].map(Fr::from));
+        let f5 = Poly::<Bls12_381>::from_coefficients_slice(&[12, 0, 3, 9, 0].map(Fr::from));
+        let f: Vec<Poly<Bls12_381>> = vec![f1, f2, f3, f4, f5];
         let z = [
             Fr::from(12),
             Fr::from(4),
@@ -108,13 +116,366 @@ mod tests {
             Fr::from(13),
             Fr::from(9),
         ];
-        let openings: Vec<KzgOpening> = f
+        let openings: Vec<KzgOpening<Bls12_381>> = f
             .iter()
             .zip(z)
-            .map(|(f_i, z_i)| scheme.open(f_i, z_i))
+            .map(|(f_i, z_i)| scheme.open(f_i, z_i).unwrap())
             .collect();
-        let c: Vec<KzgCommitment> = f.iter().map(|f_i| scheme.commit(f_i)).collect();
-        let mut rng = StdRng::from_entropy();
-        assert!(scheme.batch_verify(c.as_slice(), &z, &openings, &mut rng));
+        let c: Vec<KzgCommitment<Bls12_381>> =
+            f.iter().map(|f_i| scheme.commit(f_i).unwrap()).collect();
+        assert!(scheme.batch_verify(c.as_slice(), &z, &openings));
+
+        // a wrong evaluation, a wrong point or a missing opening
+        let mut forged = openings.clone();
+        forged[2].1 += Fr::one();
+        assert!(!scheme.batch_verify(c.as_slice(), &z, &forged));
+        let mut forged = z;
+        forged[4] += Fr::one();
+        assert!(!scheme.batch_verify(c.as_slice(), &forged, &openings));
+        assert!(!scheme.batch_verify(c.as_slice(), &z, &openings[..4]));
+    }
+
+    /// Commits, opens and verifies a polynomial over an arbitrary pairing-friendly curve.
+    fn commit_open_verify<E: Pairing>() {
+        let srs = Srs::<E>::new(5);
+        let scheme = KzgScheme::new(srs);
+        let poly =
+            Poly::<E>::from_coefficients_slice(&[1u64, 2, 3, 4, 5].map(ScalarField::<E>::from));
+        let commitment = scheme.commit(&poly).unwrap();
+        let z = ScalarField::<E>::from(7u64);
+        let opening = scheme.open(&poly, z).unwrap();
+        assert_eq!(opening.1, poly.evaluate(&z));
+        assert!(scheme.verify(&commitment, &opening, z));
+        assert!(!scheme.verify(&commitment, &opening, z + ScalarField::<E>::one()));
+    }
+
+    #[test]
+    /// Tests the scheme over the curves supported by the crate.
+    ///
+    /// This test runs the commit-open-verify flow over BLS12-381, BN254 and BLS12-377.
+    fn generic_curves() {
+        commit_open_verify::<Bls12_381>();
+        commit_open_verify::<Bn254>();
+        commit_open_verify::<Bls12_377>();
+    }
+
+    #[test]
+    fn serialize_commitment() {
+        let scheme = KzgScheme::new(Srs::<Bls12_381>::new(8));
+        let poly = Poly::<Bls12_381>::rand(6, &mut rand::thread_rng());
+        let commitment = scheme.commit(&poly).unwrap();
+        let opening = scheme.open(&poly, Fr::from(3u64)).unwrap();
+
+        for compress in [Compress::Yes, Compress::No] {
+            let mut bytes = Vec::new();
+            commitment
+                .serialize_with_mode(&mut bytes, compress)
+                .unwrap();
+            let loaded = KzgCommitment::<Bls12_381>::deserialize_with_mode(
+                &bytes[..],
+                compress,
+                Validate::Yes,
+            )
+            .unwrap();
+            assert_eq!(loaded, commitment);
+
+            let mut bytes = Vec::new();
+            opening.serialize_with_mode(&mut bytes, compress).unwrap();
+            let loaded =
+                KzgOpening::<Bls12_381>::deserialize_with_mode(&bytes[..], compress, Validate::Yes)
+                    .unwrap();
+            assert_eq!(loaded, opening);
+            assert!(scheme.verify(&commitment, &loaded, Fr::from(3u64)));
+        }
+    }
+
+    #[test]
+    fn reject_non_subgroup_commitment() {
+        let commitment = KzgCommitment::<Bls12_381>(crate::srs::tests::non_subgroup_point());
+        for compress in [Compress::Yes, Compress::No] {
+            let mut bytes = Vec::new();
+            commitment
+                .serialize_with_mode(&mut bytes, compress)
+                .unwrap();
+            assert!(KzgCommitment::<Bls12_381>::deserialize_with_mode(
+                &bytes[..],
+                compress,
+                Validate::Yes
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn msm_commit() {
+        let secret = Fr::from(7u64);
+        let scheme = KzgScheme::new(Srs::<Bls12_381>::new_from_secret(secret, 16));
+        let poly = Poly::<Bls12_381>::rand(12, &mut rand::thread_rng());
+        let expected = G1Point::<Bls12_381>::generator()
+            .mul(poly.evaluate(&secret))
+            .into_affine();
+        assert_eq!(scheme.commit(&poly).unwrap().0, expected);
+        assert!(scheme
+            .commit(&Poly::<Bls12_381>::zero())
+            .unwrap()
+            .0
+            .is_zero());
+    }
+
+    #[test]
+    fn commit_evaluations() {
+        let secret = Fr::from(5u64);
+        let srs = Srs::<Bls12_381>::new_from_secret(secret, 8)
+            .with_lagrange_basis(8)
+            .unwrap();
+        let domain = srs.lagrange_domain().unwrap();
+        let scheme = KzgScheme::new(srs);
+
+        let evaluations = (0..8)
+            .map(|_| Fr::from(rand::thread_rng().gen::<u64>()))
+            .collect::<Vec<_>>();
+        let poly = Poly::<Bls12_381>::from_coefficients_vec(domain.ifft(&evaluations));
+        let commitment = scheme.commit_evaluations(&evaluations).unwrap();
+        assert_eq!(commitment, scheme.commit(&poly).unwrap());
+
+        // a point outside the domain and every point of the domain
+        let points = std::iter::once(Fr::from(123u64)).chain(domain.elements());
+        for z in points {
+            let opening = scheme.open_evaluations(&evaluations, z).unwrap();
+            assert_eq!(opening, scheme.open(&poly, z).unwrap());
+            assert!(scheme.verify(&commitment, &opening, z));
+        }
+
+        // missing trailing evaluations are zero
+        let mut padded = evaluations[..5].to_vec();
+        padded.resize(8, Fr::zero());
+        assert_eq!(
+            scheme.commit_evaluations(&evaluations[..5]).unwrap(),
+            scheme.commit_evaluations(&padded).unwrap()
+        );
+        assert_eq!(
+            scheme.open_evaluations(&evaluations[..5], 9u64).unwrap(),
+            scheme.open_evaluations(&padded, 9u64).unwrap()
+        );
+    }
+
+    #[test]
+    fn multi_point_opening() {
+        let scheme = KzgScheme::new(Srs::<Bls12_381>::new(16));
+        let rng = &mut rand::thread_rng();
+        let polynomials = [3, 10, 0, 7]
+            .map(|degree| Poly::<Bls12_381>::rand(degree, rng))
+            .to_vec();
+        let commitments = polynomials
+            .iter()
+            .map(|poly| scheme.commit(poly).unwrap())
+            .collect::<Vec<_>>();
+        let points = vec![
+            vec![Fr::from(1u64), Fr::from(2u64)],
+            vec![Fr::from(2u64), Fr::from(3u64), Fr::from(4u64)],
+            vec![Fr::from(5u64)],
+            vec![
+                Fr::from(1u64),
+                Fr::from(5u64),
+                Fr::from(6u64),
+                Fr::from(7u64),
+            ],
+        ];
+
+        let (opening, evaluations) = scheme
+            .open_multi_point(&polynomials, &commitments, &points)
+            .unwrap();
+        for ((poly, points), evaluations) in polynomials.iter().zip(&points).zip(&evaluations) {
+            let expected = points.iter().map(|x| poly.evaluate(x)).collect::<Vec<_>>();
+            assert_eq!(evaluations, &expected);
+        }
+        assert!(scheme.verify_multi_point(&commitments, &points, &evaluations, &opening));
+
+        // a wrong evaluation
+        let mut forged = evaluations.clone();
+        forged[1][2] += Fr::one();
+        assert!(!scheme.verify_multi_point(&commitments, &points, &forged, &opening));
+        // a wrong point
+        let mut forged = points.clone();
+        forged[3][0] = Fr::from(8u64);
+        assert!(!scheme.verify_multi_point(&commitments, &forged, &evaluations, &opening));
+        // a wrong commitment
+        let mut forged = commitments.clone();
+        forged.swap(0, 1);
+        assert!(!scheme.verify_multi_point(&forged, &points, &evaluations, &opening));
+        // a wrong proof
+        let forged = KzgMultiPointOpening(opening.1, opening.0);
+        assert!(!scheme.verify_multi_point(&commitments, &points, &evaluations, &forged));
+        // missing evaluations
+        assert!(!scheme.verify_multi_point(&commitments, &points, &evaluations[..3], &opening));
+    }
+
+    #[test]
+    fn batch_opening() {
+        let scheme = KzgScheme::new(Srs::<Bls12_381>::new(16));
+        let rng = &mut rand::thread_rng();
+        let polynomials = [5, 12, 0, 9]
+            .map(|degree| Poly::<Bls12_381>::rand(degree, rng))
+            .to_vec();
+        let commitments = polynomials
+            .iter()
+            .map(|poly| scheme.commit(poly).unwrap())
+            .collect::<Vec<_>>();
+        let z = Fr::from(rng.gen::<u64>());
+
+        let opening = scheme.open_batch(&polynomials, &commitments, z).unwrap();
+        let expected = polynomials
+            .iter()
+            .map(|poly| poly.evaluate(&z))
+            .collect::<Vec<_>>();
+        assert_eq!(opening.evals(), &expected[..]);
+        assert!(scheme.verify_batch(&commitments, &opening, z));
+
+        // a wrong evaluation
+        let mut forged = opening.clone();
+        forged.1[3] += Fr::one();
+        assert!(!scheme.verify_batch(&commitments, &forged, z));
+        // a wrong point
+        assert!(!scheme.verify_batch(&commitments, &opening, z + Fr::one()));
+        // a wrong commitment
+        let mut forged = commitments.clone();
+        forged.swap(1, 2);
+        assert!(!scheme.verify_batch(&forged, &opening, z));
+        // missing evaluations
+        let mut forged = opening.clone();
+        forged.1.pop();
+        assert!(!scheme.verify_batch(&commitments, &forged, z));
+    }
+
+    #[test]
+    fn hiding_commitment() {
+        let (secret, gamma) = (Fr::from(3u64), Fr::from(19u64));
+        let srs = Srs::<Bls12_381>::new_hiding_from_secrets(secret, gamma, 8);
+        let h = srs.h_points()[0];
+        assert_eq!(
+            h,
+            G1Point::<Bls12_381>::generator().mul(gamma).into_affine()
+        );
+        let mut file = Vec::new();
+        srs.write(&mut file, Compress::Yes).unwrap();
+        assert_eq!(Srs::<Bls12_381>::read(&file[..]).unwrap(), srs);
+
+        let scheme = KzgScheme::new(srs);
+        let rng = &mut rand::thread_rng();
+        let poly = Poly::<Bls12_381>::rand(6, rng);
+        let blinder = Poly::<Bls12_381>::rand(1, rng);
+        let commitment = scheme.commit_hiding(&poly, &blinder).unwrap();
+        let expected = scheme.commit(&poly).unwrap().0 + h.mul(blinder.evaluate(&secret));
+        assert_eq!(commitment.0, expected.into_affine());
+        // the same polynomial with another blinder gives another commitment
+        let other_blinder = Poly::<Bls12_381>::rand(1, rng);
+        assert_ne!(
+            commitment,
+            scheme.commit_hiding(&poly, &other_blinder).unwrap()
+        );
+
+        let z = Fr::from(rng.gen::<u64>());
+        let opening = scheme.open_hiding(&poly, &blinder, z).unwrap();
+        assert_eq!(opening.eval(), poly.evaluate(&z));
+        assert!(scheme.verify_hiding(&commitment, &opening, z));
+        assert!(!scheme.verify_hiding(&commitment, &opening, z + Fr::one()));
+
+        // a wrong evaluation of the polynomial or of the blinder
+        let mut forged = opening.clone();
+        forged.1 += Fr::one();
+        assert!(!scheme.verify_hiding(&commitment, &forged, z));
+        let mut forged = opening.clone();
+        forged.2 += Fr::one();
+        assert!(!scheme.verify_hiding(&commitment, &forged, z));
+
+        // an SRS without the second generator cannot verify hiding openings
+        let scheme = KzgScheme::new(Srs::<Bls12_381>::new_from_secret(secret, 8));
+        assert!(!scheme.verify_hiding(&commitment, &opening, z));
+    }
+
+    #[test]
+    fn degree_bound() {
+        let srs = Srs::<Bls12_381>::new_with_g2_powers(8);
+        let scheme = KzgScheme::new(srs);
+        let rng = &mut rand::thread_rng();
+        let poly = Poly::<Bls12_381>::rand(5, rng);
+        let commitment = scheme.commit(&poly).unwrap();
+
+        for bound in [6, 7, 11, 12, 20] {
+            let proof = scheme.open_degree_bound(&poly, bound).unwrap();
+            assert!(scheme.verify_degree_bound(&commitment, &proof, bound));
+        }
+        // the proof for one bound does not hold for a smaller one
+        let proof = scheme.open_degree_bound(&poly, 6).unwrap();
+        assert!(!scheme.verify_degree_bound(&commitment, &proof, 5));
+        assert!(!scheme.verify_degree_bound(&commitment, &proof, 0));
+        // nor for another polynomial of higher degree
+        let other = scheme.commit(&Poly::<Bls12_381>::rand(7, rng)).unwrap();
+        assert!(!scheme.verify_degree_bound(&other, &proof, 6));
+
+        assert_eq!(
+            scheme.open_degree_bound(&poly, 5),
+            Err(KzgError::DegreeBoundExceeded {
+                degree: 5,
+                bound: 5
+            })
+        );
+        // an SRS without powers in G2 cannot prove degree bounds
+        let scheme = KzgScheme::new(Srs::<Bls12_381>::new(8));
+        assert_eq!(
+            scheme.open_degree_bound(&poly, 6),
+            Err(KzgError::MissingG2Powers)
+        );
+        assert!(!scheme.verify_degree_bound(&commitment, &proof, 6));
+    }
+
+    #[test]
+    fn reject_invalid_inputs() {
+        let scheme = KzgScheme::new(Srs::<Bls12_381>::new(4));
+        let poly = Poly::<Bls12_381>::rand(8, &mut rand::thread_rng());
+        let error = KzgError::DegreeTooLarge {
+            degree: 8,
+            max_degree: 6,
+        };
+        assert_eq!(scheme.commit(&poly), Err(error.clone()));
+        assert_eq!(scheme.open(&poly, 3u64), Err(error));
+        assert_eq!(
+            scheme.commit_evaluations(&[Fr::one()]),
+            Err(KzgError::MissingLagrangeBasis)
+        );
+        assert_eq!(
+            scheme.commit_hiding(&poly, &poly),
+            Err(KzgError::DegreeTooLarge {
+                degree: 8,
+                max_degree: 6
+            })
+        );
+        let small = Poly::<Bls12_381>::rand(2, &mut rand::thread_rng());
+        assert_eq!(
+            scheme.commit_hiding(&small, &small),
+            Err(KzgError::MissingHidingPowers)
+        );
+
+        // the zero polynomial can be committed to and opened
+        let zero = Poly::<Bls12_381>::zero();
+        let opening = scheme.open(&zero, 3u64).unwrap();
+        assert!(scheme.verify(&scheme.commit(&zero).unwrap(), &opening, 3u64));
+
+        let commitment = scheme.commit(&small).unwrap();
+        assert_eq!(
+            scheme.open_batch(std::slice::from_ref(&small), &[], 3u64),
+            Err(KzgError::LengthMismatch {
+                expected: 0,
+                found: 1
+            })
+        );
+        let points = vec![vec![Fr::one(), Fr::one()]];
+        assert_eq!(
+            scheme.open_multi_point(&[small], std::slice::from_ref(&commitment), &points),
+            Err(KzgError::DuplicatePoints)
+        );
+        let evaluations = vec![vec![Fr::one(), Fr::one()]];
+        let opening = KzgMultiPointOpening(commitment.0, commitment.0);
+        assert!(!scheme.verify_multi_point(&[commitment], &points, &evaluations, &opening));
     }
 }
@@ -0,0 +1,120 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Errors raised by the KZG scheme on inputs it cannot commit to or open.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KzgError {
+    /// The polynomial has a higher degree than the SRS supports.
+    DegreeTooLarge { degree: usize, max_degree: usize },
+    /// The SRS holds no points in G1.
+    EmptySrs,
+    /// The SRS has no precomputed Lagrange basis points.
+    MissingLagrangeBasis,
+    /// There are more evaluations than points in the Lagrange basis domain.
+    TooManyEvaluations {
+        evaluations: usize,
+        domain_size: usize,
+    },
+    /// The SRS has no powers of the second generator needed by hiding commitments.
+    MissingHidingPowers,
+    /// Inputs that go together have different lengths.
+    LengthMismatch { expected: usize, found: usize },
+    /// A polynomial is opened several times at the same point.
+    DuplicatePoints,
+    /// A linear combination has no terms.
+    EmptyCombination,
+    /// Openings that are combined were made at different points.
+    DistinctPoints,
+    /// The multilinear polynomial has more variables than the SRS supports.
+    TooManyVariables {
+        num_vars: usize,
+        max_num_vars: usize,
+    },
+    /// A position lies outside of the committed vector.
+    PositionOutOfRange { position: usize, size: usize },
+    /// The SRS has no powers of the secret in G2 needed by degree-bound proofs.
+    MissingG2Powers,
+    /// The polynomial does not have a degree below the bound.
+    DegreeBoundExceeded { degree: usize, bound: usize },
+    /// The field has no radix-2 domain of the size.
+    DomainTooLarge { size: usize },
+    /// The SRS holds fewer powers than needed.
+    SrsTooSmall { powers: usize, needed: usize },
+    /// The SRS points do not derive from the standard generators and a single secret.
+    MalformedSrs(&'static str),
+    /// The file does not follow its format.
+    MalformedFile(&'static str),
+    /// The file has a format version that is not supported.
+    UnsupportedVersion(u32),
+    /// The file was generated for another curve.
+    CurveMismatch,
+    /// Reading or writing failed, holding the underlying error message.
+    Serialization(String),
+}
+
+impl Display for KzgError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KzgError::DegreeTooLarge { degree, max_degree } => write!(
+                f,
+                "Polynomial of degree {} exceeds the maximum degree {} of the SRS",
+                degree, max_degree
+            ),
+            KzgError::EmptySrs => write!(f, "The SRS is empty"),
+            KzgError::MissingLagrangeBasis => write!(f, "The SRS has no Lagrange basis points"),
+            KzgError::TooManyEvaluations {
+                evaluations,
+                domain_size,
+            } => write!(
+                f,
+                "{} evaluations exceed the domain size {}",
+                evaluations, domain_size
+            ),
+            KzgError::MissingHidingPowers => {
+                write!(f, "The SRS does not support hiding commitments")
+            }
+            KzgError::LengthMismatch { expected, found } => {
+                write!(f, "Expected {} inputs, found {}", expected, found)
+            }
+            KzgError::DuplicatePoints => write!(f, "A polynomial is opened twice at a point"),
+            KzgError::EmptyCombination => write!(f, "There is nothing to combine"),
+            KzgError::DistinctPoints => write!(f, "The openings are at different points"),
+            KzgError::TooManyVariables {
+                num_vars,
+                max_num_vars,
+            } => write!(
+                f,
+                "Polynomial in {} variables exceeds the maximum of {} variables of the SRS",
+                num_vars, max_num_vars
+            ),
+            KzgError::PositionOutOfRange { position, size } => write!(
+                f,
+                "Position {} is outside of a vector of size {}",
+                position, size
+            ),
+            KzgError::MissingG2Powers => {
+                write!(f, "The SRS does not support degree-bound proofs")
+            }
+            KzgError::DegreeBoundExceeded { degree, bound } => write!(
+                f,
+                "Polynomial of degree {} is not below the bound {}",
+                degree, bound
+            ),
+            KzgError::DomainTooLarge { size } => {
+                write!(f, "The field has no radix-2 domain of size {}", size)
+            }
+            KzgError::SrsTooSmall { powers, needed } => {
+                write!(f, "The SRS holds {} powers, {} are needed", powers, needed)
+            }
+            KzgError::MalformedSrs(reason) => write!(f, "Malformed SRS: {}", reason),
+            KzgError::MalformedFile(reason) => write!(f, "Malformed file: {}", reason),
+            KzgError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported file version {}", version)
+            }
+            KzgError::CurveMismatch => write!(f, "The file was generated for a different curve"),
+            KzgError::Serialization(reason) => write!(f, "Failed to read or write: {}", reason),
+        }
+    }
+}
+
+impl Error for KzgError {}
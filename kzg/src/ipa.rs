@@ -0,0 +1,638 @@
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Mul, Sub};
+
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{batch_inversion, Field, One, Zero};
+use ark_poly::Polynomial;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::KzgError;
+use crate::pcs::PolynomialCommitmentScheme;
+use crate::transcript::Transcript;
+use crate::types::{G1Point, Poly, ScalarField};
+
+/// Public parameters of the inner product argument (IPA) commitment scheme.
+///
+/// The `IpaParams` struct holds independent generators in G1, derived by hashing
+/// so that nobody knows a relation between them. No trusted setup is needed.
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct IpaParams<E: Pairing> {
+    /// Generators committing to the coefficients, a power of two of them.
+    generators: Vec<G1Point<E>>,
+    /// Generator committing to the inner product.
+    u: G1Point<E>,
+}
+
+impl<E: Pairing> IpaParams<E> {
+    /// Derives the parameters for polynomials up to the specified degree.
+    ///
+    /// # Parameters
+    ///
+    /// - `max_degree`: The maximum degree of the polynomials to commit to.
+    ///
+    /// # Returns
+    ///
+    /// A new `IpaParams` instance, with the number of generators rounded up to a power of two.
+    pub fn new(max_degree: usize) -> Self {
+        let size = (max_degree + 1).next_power_of_two();
+        let generators = (0..size as u64)
+            .map(|i| hash_to_curve::<E>(b"ipa-generator", i))
+            .collect();
+        Self {
+            generators,
+            u: hash_to_curve::<E>(b"ipa-inner-product", 0),
+        }
+    }
+
+    /// Returns the generators committing to the coefficients.
+    ///
+    /// # Returns
+    ///
+    /// A vector containing points in G1.
+    pub fn generators(&self) -> Vec<G1Point<E>> {
+        self.generators.clone()
+    }
+
+    /// Returns the generator committing to the inner product.
+    ///
+    /// # Returns
+    ///
+    /// A point in G1.
+    pub fn u(&self) -> G1Point<E> {
+        self.u
+    }
+
+    /// Returns the maximum degree of the polynomials the parameters can commit to.
+    ///
+    /// # Returns
+    ///
+    /// The maximum degree, one less than the number of generators.
+    pub fn max_degree(&self) -> usize {
+        self.generators.len() - 1
+    }
+}
+
+/// Represents a Pedersen commitment to the coefficients of a polynomial.
+#[derive(Debug, Clone, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct IpaCommitment<E: Pairing>(pub G1Point<E>);
+
+impl<E: Pairing> Add for IpaCommitment<E> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self((self.0 + rhs.0).into())
+    }
+}
+
+impl<E: Pairing> Sub for IpaCommitment<E> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self((self.0.into_group() - rhs.0).into())
+    }
+}
+
+impl<E: Pairing> Mul<ScalarField<E>> for IpaCommitment<E> {
+    type Output = Self;
+
+    fn mul(self, rhs: ScalarField<E>) -> Self::Output {
+        Self(self.0.mul(rhs).into())
+    }
+}
+
+/// Represents an opening at a point, proven with an inner product argument.
+///
+/// The proof holds two points in G1 per halving round, logarithmic in the number
+/// of generators, and the last coefficient left after folding.
+#[derive(Debug, Clone, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct IpaOpening<E: Pairing> {
+    /// Left cross terms, one per round.
+    pub l_vec: Vec<G1Point<E>>,
+    /// Right cross terms, one per round.
+    pub r_vec: Vec<G1Point<E>>,
+    /// The coefficient left after all rounds.
+    pub a: ScalarField<E>,
+    /// The evaluation of the polynomial at the point.
+    pub eval: ScalarField<E>,
+}
+
+impl<E: Pairing> IpaOpening<E> {
+    /// Retrieves the evaluation associated with the opening.
+    ///
+    /// # Returns
+    ///
+    /// The evaluation (`ScalarField`) of the opening.
+    pub fn eval(&self) -> ScalarField<E> {
+        self.eval
+    }
+}
+
+/// Represents a batched opening of many polynomials at the same point.
+///
+/// `IpaBatchOpening` encapsulates the opening of a random linear combination of the
+/// polynomials, and the evaluation of each polynomial at the point.
+#[derive(Debug, Clone, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct IpaBatchOpening<E: Pairing>(pub IpaOpening<E>, pub Vec<ScalarField<E>>);
+
+impl<E: Pairing> IpaBatchOpening<E> {
+    /// Retrieves the evaluations associated with the opening.
+    ///
+    /// # Returns
+    ///
+    /// The evaluations (`ScalarField`) of the opened polynomials, in order.
+    pub fn evals(&self) -> &[ScalarField<E>] {
+        &self.1
+    }
+}
+
+/// Implements a transparent polynomial commitment scheme based on the
+/// Bulletproofs inner product argument.
+///
+/// The `IpaScheme` struct offers the same commit, open and verify surface as `KzgScheme`,
+/// without a trusted setup, at the cost of logarithmic proofs and a linear verifier.
+#[derive(Debug, Clone)]
+pub struct IpaScheme<E: Pairing>(IpaParams<E>);
+
+impl<E: Pairing> IpaScheme<E> {
+    /// Creates a new instance of `IpaScheme` with the given parameters.
+    ///
+    /// # Parameters
+    ///
+    /// - `params`: The public parameters used in the scheme.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `IpaScheme`.
+    pub fn new(params: IpaParams<E>) -> Self {
+        Self(params)
+    }
+
+    /// Commits to a polynomial.
+    ///
+    /// # Parameters
+    ///
+    /// - `polynomial`: The polynomial to be committed to.
+    ///
+    /// # Returns
+    ///
+    /// The commitment to the polynomial, or an error if its degree exceeds the parameters.
+    pub fn commit(&self, polynomial: &Poly<E>) -> Result<IpaCommitment<E>, KzgError> {
+        self.check_degree(polynomial)?;
+        let coeffs = &polynomial.coeffs;
+        let commitment = E::G1::msm_unchecked(&self.0.generators[..coeffs.len()], coeffs);
+        Ok(IpaCommitment(commitment.into_affine()))
+    }
+
+    /// Opens the commitment to a polynomial at a specified point.
+    ///
+    /// The evaluation is the inner product of the coefficients with the powers of `z`,
+    /// which the prover proves by halving both vectors and the generators in every round.
+    ///
+    /// # Parameters
+    ///
+    /// - `polynomial`: The polynomial to be opened.
+    /// - `z`: The point at which the polynomial is opened.
+    ///
+    /// # Returns
+    ///
+    /// The opening at the specified point, or an error if the degree of the polynomial
+    /// exceeds the parameters.
+    pub fn open(
+        &self,
+        polynomial: &Poly<E>,
+        z: impl Into<ScalarField<E>>,
+    ) -> Result<IpaOpening<E>, KzgError> {
+        let z = z.into();
+        let commitment = self.commit(polynomial)?;
+        let eval = polynomial.evaluate(&z);
+        let mut transcript = Self::transcript(&commitment, z, eval);
+        let u = self.0.u.mul(transcript.challenge());
+
+        let size = self.0.generators.len();
+        let mut a = polynomial.coeffs.clone();
+        a.resize(size, ScalarField::<E>::zero());
+        let mut b = powers(z, size);
+        let mut g = self.0.generators.clone();
+        let (mut l_vec, mut r_vec) = (vec![], vec![]);
+        while a.len() > 1 {
+            let half = a.len() / 2;
+            let (a_lo, a_hi) = a.split_at(half);
+            let (b_lo, b_hi) = b.split_at(half);
+            let (g_lo, g_hi) = g.split_at(half);
+            let l = E::G1::msm_unchecked(g_hi, a_lo) + u * inner_product(a_lo, b_hi);
+            let r = E::G1::msm_unchecked(g_lo, a_hi) + u * inner_product(a_hi, b_lo);
+            let [l, r] = [l.into_affine(), r.into_affine()];
+            transcript.append(&l);
+            transcript.append(&r);
+            l_vec.push(l);
+            r_vec.push(r);
+
+            let x = transcript.challenge();
+            let x_inv = x.inverse().expect("the challenge is zero");
+            a = fold(a_lo, a_hi, x, x_inv);
+            b = fold(b_lo, b_hi, x_inv, x);
+            let folded = g_lo
+                .iter()
+                .zip(g_hi)
+                .map(|(lo, hi)| lo.mul(x_inv) + hi.mul(x))
+                .collect::<Vec<_>>();
+            g = E::G1::normalize_batch(&folded);
+        }
+
+        Ok(IpaOpening {
+            l_vec,
+            r_vec,
+            a: a[0],
+            eval,
+        })
+    }
+
+    /// Verifies the correctness of an opening.
+    ///
+    /// The verifier folds the commitment with the cross terms of every round, and checks it
+    /// against the generators folded with the challenges in a single multi-scalar multiplication.
+    ///
+    /// # Parameters
+    ///
+    /// - `commitment`: The commitment to be verified.
+    /// - `opening`: The opening to be verified.
+    /// - `z`: The point at which the polynomial was opened.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the opening is valid, otherwise `false`.
+    pub fn verify(
+        &self,
+        commitment: &IpaCommitment<E>,
+        opening: &IpaOpening<E>,
+        z: impl Into<ScalarField<E>> + Debug + Display,
+    ) -> bool {
+        let size = self.0.generators.len();
+        let rounds = opening.l_vec.len();
+        if !size.is_power_of_two()
+            || rounds != size.trailing_zeros() as usize
+            || opening.r_vec.len() != rounds
+        {
+            return false;
+        }
+        let z = z.into();
+        let mut transcript = Self::transcript(commitment, z, opening.eval);
+        let xi = transcript.challenge();
+
+        let mut challenges = Vec::with_capacity(rounds);
+        for (l, r) in opening.l_vec.iter().zip(&opening.r_vec) {
+            transcript.append(l);
+            transcript.append(r);
+            challenges.push(transcript.challenge());
+        }
+        if challenges.iter().any(|x| x.is_zero()) {
+            return false;
+        }
+        let mut inverses = challenges.clone();
+        batch_inversion(&mut inverses);
+
+        // s_i is the product of x_j or x_j^-1 along the bits of i, the first round being the top bit
+        let mut s = vec![ScalarField::<E>::one()];
+        for (x, x_inv) in challenges.iter().zip(&inverses).rev() {
+            s = s
+                .iter()
+                .map(|s_i| *s_i * x_inv)
+                .chain(s.iter().map(|s_i| *s_i * x))
+                .collect();
+        }
+        let b = inner_product(&s, &powers(z, size));
+
+        // P + sum_j (x_j^2 * L_j + x_j^-2 * R_j) = a * <s, G> + a * b * U
+        let mut bases = self.0.generators.clone();
+        let mut scalars = s.iter().map(|s_i| -opening.a * s_i).collect::<Vec<_>>();
+        bases.extend(opening.l_vec.iter().chain(&opening.r_vec));
+        scalars.extend(challenges.iter().map(|x| x.square()));
+        scalars.extend(inverses.iter().map(|x_inv| x_inv.square()));
+        bases.extend([commitment.0, self.0.u]);
+        scalars.extend([ScalarField::<E>::one(), xi * (opening.eval - opening.a * b)]);
+        E::G1::msm_unchecked(&bases, &scalars).is_zero()
+    }
+
+    /// Opens many polynomials at the same point, by opening their linear combination
+    /// with the powers of a challenge bound to the commitments and evaluations.
+    ///
+    /// # Parameters
+    ///
+    /// - `polynomials`: The polynomials to be opened.
+    /// - `commitments`: The commitments to the polynomials, bound to the challenge.
+    /// - `z`: The point at which the polynomials are opened.
+    ///
+    /// # Returns
+    ///
+    /// The opening, holding the evaluation of every polynomial at `z`, or an error
+    /// if the polynomials and commitments differ in number or a degree exceeds the parameters.
+    pub fn open_batch(
+        &self,
+        polynomials: &[Poly<E>],
+        commitments: &[IpaCommitment<E>],
+        z: impl Into<ScalarField<E>>,
+    ) -> Result<IpaBatchOpening<E>, KzgError> {
+        if commitments.len() != polynomials.len() {
+            return Err(KzgError::LengthMismatch {
+                expected: commitments.len(),
+                found: polynomials.len(),
+            });
+        }
+        let z = z.into();
+        let evaluations = polynomials
+            .iter()
+            .map(|polynomial| polynomial.evaluate(&z))
+            .collect::<Vec<_>>();
+        let v = Self::batch_transcript(commitments, z, &evaluations).challenge();
+
+        let mut combined = Poly::<E>::zero();
+        for (polynomial, pow) in polynomials.iter().zip(powers(v, polynomials.len())) {
+            self.check_degree(polynomial)?;
+            combined += &(polynomial * pow);
+        }
+        Ok(IpaBatchOpening(self.open(&combined, z)?, evaluations))
+    }
+
+    /// Verifies an opening produced by `IpaScheme::open_batch`.
+    ///
+    /// # Parameters
+    ///
+    /// - `commitments`: The commitments to the polynomials.
+    /// - `opening`: The opening to be verified, holding the claimed evaluations.
+    /// - `z`: The point at which the polynomials were opened.
+    ///
+    /// # Returns
+    ///
+    /// `true` if every claimed evaluation is valid, otherwise `false`.
+    pub fn verify_batch(
+        &self,
+        commitments: &[IpaCommitment<E>],
+        opening: &IpaBatchOpening<E>,
+        z: impl Into<ScalarField<E>> + Debug + Display,
+    ) -> bool {
+        let IpaBatchOpening(opening, evaluations) = opening;
+        if commitments.len() != evaluations.len() {
+            return false;
+        }
+        let z = z.into();
+        let v = Self::batch_transcript(commitments, z, evaluations).challenge();
+
+        let scalars = powers(v, commitments.len());
+        if opening.eval != inner_product(&scalars, evaluations) {
+            return false;
+        }
+        let bases = commitments.iter().map(|c| c.0).collect::<Vec<_>>();
+        let commitment = IpaCommitment(E::G1::msm_unchecked(&bases, &scalars).into_affine());
+        self.verify(&commitment, opening, z)
+    }
+
+    /// Absorbs the commitments, point and evaluations of a batched opening into a new transcript.
+    fn batch_transcript(
+        commitments: &[IpaCommitment<E>],
+        z: ScalarField<E>,
+        evaluations: &[ScalarField<E>],
+    ) -> Transcript<E> {
+        let mut transcript = Transcript::new(b"ipa-batch-opening");
+        transcript.append(commitments);
+        transcript.append(&z);
+        transcript.append(evaluations);
+        transcript
+    }
+
+    /// Checks that the parameters hold enough generators to commit to the polynomial.
+    fn check_degree(&self, polynomial: &Poly<E>) -> Result<(), KzgError> {
+        if polynomial.degree() > self.0.max_degree() {
+            return Err(KzgError::DegreeTooLarge {
+                degree: polynomial.degree(),
+                max_degree: self.0.max_degree(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Absorbs the commitment, point and evaluation of an opening into a new transcript.
+    fn transcript(
+        commitment: &IpaCommitment<E>,
+        z: ScalarField<E>,
+        eval: ScalarField<E>,
+    ) -> Transcript<E> {
+        let mut transcript = Transcript::new(b"ipa-opening");
+        transcript.append(commitment);
+        transcript.append(&z);
+        transcript.append(&eval);
+        transcript
+    }
+}
+
+impl<E: Pairing> PolynomialCommitmentScheme for IpaScheme<E> {
+    type Field = ScalarField<E>;
+    type Params = IpaParams<E>;
+    type Commitment = IpaCommitment<E>;
+    type Opening = IpaOpening<E>;
+    type BatchOpening = IpaBatchOpening<E>;
+    type Error = KzgError;
+
+    fn setup(max_degree: usize) -> IpaParams<E> {
+        IpaParams::new(max_degree)
+    }
+
+    fn new(params: IpaParams<E>) -> Self {
+        Self(params)
+    }
+
+    fn max_degree(&self) -> usize {
+        self.0.max_degree()
+    }
+
+    fn commit(&self, polynomial: &Poly<E>) -> Result<IpaCommitment<E>, KzgError> {
+        Self::commit(self, polynomial)
+    }
+
+    fn open(&self, polynomial: &Poly<E>, z: ScalarField<E>) -> Result<IpaOpening<E>, KzgError> {
+        Self::open(self, polynomial, z)
+    }
+
+    fn verify(
+        &self,
+        commitment: &IpaCommitment<E>,
+        opening: &IpaOpening<E>,
+        z: ScalarField<E>,
+    ) -> bool {
+        Self::verify(self, commitment, opening, z)
+    }
+
+    fn evaluation(opening: &IpaOpening<E>) -> ScalarField<E> {
+        opening.eval
+    }
+
+    fn batch_open(
+        &self,
+        polynomials: &[Poly<E>],
+        commitments: &[IpaCommitment<E>],
+        z: ScalarField<E>,
+    ) -> Result<IpaBatchOpening<E>, KzgError> {
+        self.open_batch(polynomials, commitments, z)
+    }
+
+    fn batch_verify(
+        &self,
+        commitments: &[IpaCommitment<E>],
+        opening: &IpaBatchOpening<E>,
+        z: ScalarField<E>,
+    ) -> bool {
+        self.verify_batch(commitments, opening, z)
+    }
+
+    fn batch_evaluations(opening: &IpaBatchOpening<E>) -> Vec<ScalarField<E>> {
+        opening.evals().to_vec()
+    }
+}
+
+/// Hashes a label and an index to a point in G1 with an unknown discrete logarithm,
+/// by trying successive candidate x-coordinates and clearing the cofactor.
+fn hash_to_curve<E: Pairing>(label: &[u8], index: u64) -> G1Point<E> {
+    let size = G1Point::<E>::zero().compressed_size();
+    (0u64..)
+        .find_map(|attempt| {
+            let mut bytes = Vec::with_capacity(size);
+            for block in 0u64.. {
+                if bytes.len() >= size {
+                    break;
+                }
+                let mut hasher = Sha256::new();
+                hasher.update(label);
+                hasher.update(index.to_le_bytes());
+                hasher.update(attempt.to_le_bytes());
+                hasher.update(block.to_le_bytes());
+                bytes.extend(hasher.finalize());
+            }
+            bytes.truncate(size);
+            G1Point::<E>::from_random_bytes(&bytes)
+                .map(|point| point.clear_cofactor())
+                .filter(|point| !point.is_zero())
+        })
+        .expect("a point is found after a few attempts")
+}
+
+/// Computes `1, z, z^2, ..., z^(size - 1)`.
+fn powers<F: Field>(z: F, size: usize) -> Vec<F> {
+    let mut cur = F::one();
+    (0..size)
+        .map(|_| {
+            let res = cur;
+            cur *= z;
+            res
+        })
+        .collect()
+}
+
+/// Computes the inner product of two vectors.
+fn inner_product<F: Field>(a: &[F], b: &[F]) -> F {
+    a.iter().zip(b).map(|(a_i, b_i)| *a_i * b_i).sum()
+}
+
+/// Folds the halves of a vector into `lo * x + hi * y`.
+fn fold<F: Field>(lo: &[F], hi: &[F], x: F, y: F) -> Vec<F> {
+    lo.iter().zip(hi).map(|(l, h)| *l * x + *h * y).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_bn254::Bn254;
+    use ark_poly::DenseUVPolynomial;
+    use ark_serialize::Compress;
+    use rand::Rng;
+
+    use super::*;
+
+    /// Commits, opens and verifies polynomials of every degree up to the maximum.
+    fn commit_open_verify<E: Pairing>(max_degree: usize) {
+        let scheme = IpaScheme::new(IpaParams::<E>::new(max_degree));
+        let rng = &mut rand::thread_rng();
+        for degree in 0..=max_degree {
+            let poly = Poly::<E>::rand(degree, rng);
+            let commitment = scheme.commit(&poly).unwrap();
+            let z = ScalarField::<E>::from(rng.gen::<u64>());
+            let opening = scheme.open(&poly, z).unwrap();
+            assert_eq!(opening.eval(), poly.evaluate(&z));
+            assert!(scheme.verify(&commitment, &opening, z));
+            if degree > 0 {
+                assert!(!scheme.verify(&commitment, &opening, z + ScalarField::<E>::one()));
+            }
+        }
+    }
+
+    #[test]
+    fn ipa_commitment() {
+        commit_open_verify::<Bls12_381>(7);
+        commit_open_verify::<Bn254>(4);
+        commit_open_verify::<Bls12_381>(0);
+    }
+
+    #[test]
+    fn ipa_params() {
+        let params = IpaParams::<Bls12_381>::new(5);
+        assert_eq!(params, IpaParams::<Bls12_381>::new(7));
+        assert_eq!(params.max_degree(), 7);
+        let mut points = params.generators();
+        points.push(params.u());
+        for (i, point) in points.iter().enumerate() {
+            assert!(point.is_on_curve());
+            assert!(point.is_in_correct_subgroup_assuming_on_curve());
+            assert!(!points[..i].contains(point));
+        }
+    }
+
+    #[test]
+    fn ipa_proof_size() {
+        let scheme = IpaScheme::new(IpaParams::<Bls12_381>::new(63));
+        let poly = Poly::<Bls12_381>::rand(63, &mut rand::thread_rng());
+        let opening = scheme.open(&poly, 5u64).unwrap();
+        assert_eq!(opening.l_vec.len(), 6);
+        let point_size = G1Point::<Bls12_381>::zero().compressed_size();
+        let expected = 2 * (8 + 6 * point_size) + 2 * 32;
+        assert_eq!(opening.serialized_size(Compress::Yes), expected);
+    }
+
+    #[test]
+    fn reject_forged_ipa_opening() {
+        let scheme = IpaScheme::new(IpaParams::<Bls12_381>::new(15));
+        let poly = Poly::<Bls12_381>::rand(12, &mut rand::thread_rng());
+        let commitment = scheme.commit(&poly).unwrap();
+        let z = Fr::from(11u64);
+        let opening = scheme.open(&poly, z).unwrap();
+        assert!(scheme.verify(&commitment, &opening, z));
+
+        // a wrong evaluation, coefficient or cross term
+        let mut forged = opening.clone();
+        forged.eval += Fr::one();
+        assert!(!scheme.verify(&commitment, &forged, z));
+        let mut forged = opening.clone();
+        forged.a += Fr::one();
+        assert!(!scheme.verify(&commitment, &forged, z));
+        let mut forged = opening.clone();
+        forged.l_vec.swap(0, 1);
+        assert!(!scheme.verify(&commitment, &forged, z));
+        // a missing round
+        let mut forged = opening.clone();
+        forged.l_vec.pop();
+        forged.r_vec.pop();
+        assert!(!scheme.verify(&commitment, &forged, z));
+        // more rounds than the generators allow, enough to overflow a shift by the round count
+        let mut forged = opening.clone();
+        forged.l_vec.resize(64, opening.l_vec[0]);
+        forged.r_vec.resize(64, opening.r_vec[0]);
+        assert!(!scheme.verify(&commitment, &forged, z));
+        // another commitment
+        let other = Poly::<Bls12_381>::from_coefficients_slice(&[Fr::one()]);
+        let other = scheme.commit(&other).unwrap();
+        assert!(!scheme.verify(&(commitment + other), &opening, z));
+
+        let poly = Poly::<Bls12_381>::rand(16, &mut rand::thread_rng());
+        assert!(scheme.commit(&poly).is_err());
+        assert!(scheme.open(&poly, z).is_err());
+    }
+}
@@ -1,5 +1,13 @@
+pub mod ceremony;
 pub mod commitment;
+pub mod error;
+pub mod ipa;
+pub mod multilinear;
 pub mod opening;
+pub mod pcs;
+mod ptau;
 pub mod scheme;
 pub mod srs;
+pub mod transcript;
 pub mod types;
+pub mod vector;
@@ -0,0 +1,423 @@
+use std::ops::{Add, Mul, Sub};
+
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{One, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::cfg_iter;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::error::KzgError;
+use crate::types::{G1Point, G2Point, MultilinearPoly, ScalarField};
+
+/// Structured Reference String (SRS) of the multilinear KZG scheme of
+/// Papamanthou, Shi and Tamassia (PST).
+///
+/// The `MultilinearSrs` struct holds, for every number of variables `k` up to `n`, the
+/// generator in G1 multiplied by the multilinear Lagrange basis over the boolean hypercube
+/// `{0, 1}^k`, evaluated at the last `k` secrets. A polynomial in `k` variables is therefore
+/// bound to the secrets `tau_{n-k+1}, ..., tau_n`.
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MultilinearSrs<E: Pairing> {
+    /// Lagrange basis points in G1, indexed by the number of variables.
+    g1_tables: Vec<Vec<G1Point<E>>>,
+    /// Generator point in G1.
+    g1: G1Point<E>,
+    /// Generator point in G2.
+    g2: G2Point<E>,
+    /// Generator point in G2 multiplied by each secret.
+    g2_points: Vec<G2Point<E>>,
+}
+
+impl<E: Pairing> MultilinearSrs<E> {
+    /// Generates a new SRS with random secrets for polynomials in the specified number of variables.
+    ///
+    /// # Parameters
+    ///
+    /// - `num_vars`: The maximum number of variables of the polynomials.
+    ///
+    /// # Returns
+    ///
+    /// A new `MultilinearSrs` instance.
+    pub fn new(num_vars: usize) -> Self {
+        let rng = &mut rand::thread_rng();
+        let secrets = (0..num_vars)
+            .map(|_| ScalarField::<E>::rand(rng))
+            .collect::<Vec<_>>();
+        Self::new_from_secrets(&secrets)
+    }
+
+    /// Generates a new SRS with the provided secrets, one per variable.
+    ///
+    /// # Parameters
+    ///
+    /// - `secrets`: The secrets used for generating the SRS.
+    ///
+    /// # Returns
+    ///
+    /// A new `MultilinearSrs` instance.
+    pub fn new_from_secrets(secrets: &[ScalarField<E>]) -> Self {
+        let g1 = G1Point::<E>::generator();
+        let g2 = G2Point::<E>::generator();
+
+        // Each table prepends the next secret, from the last one, as the lowest variable.
+        let mut tables = vec![vec![ScalarField::<E>::one()]];
+        for secret in secrets.iter().rev() {
+            let previous = tables.last().unwrap();
+            let table = previous
+                .iter()
+                .flat_map(|eq| [*eq * (ScalarField::<E>::one() - secret), *eq * secret])
+                .collect::<Vec<_>>();
+            tables.push(table);
+        }
+        let g1_tables = tables
+            .iter()
+            .map(|table| {
+                let points = cfg_iter!(table).map(|eq| g1.mul(eq)).collect::<Vec<_>>();
+                E::G1::normalize_batch(&points)
+            })
+            .collect();
+
+        let g2_points = secrets
+            .iter()
+            .map(|secret| g2.mul(secret))
+            .collect::<Vec<_>>();
+        Self {
+            g1_tables,
+            g1,
+            g2,
+            g2_points: E::G2::normalize_batch(&g2_points),
+        }
+    }
+
+    /// Returns the maximum number of variables of the polynomials the SRS can commit to.
+    pub fn num_vars(&self) -> usize {
+        self.g2_points.len()
+    }
+
+    /// Returns the Lagrange basis points for polynomials in the specified number of variables.
+    ///
+    /// # Parameters
+    ///
+    /// - `num_vars`: The number of variables of the polynomial.
+    ///
+    /// # Returns
+    ///
+    /// A slice of `2^num_vars` points in G1, or an error if the SRS supports fewer variables.
+    pub fn lagrange_points(&self, num_vars: usize) -> Result<&[G1Point<E>], KzgError> {
+        self.g1_tables
+            .get(num_vars)
+            .map(Vec::as_slice)
+            .ok_or(KzgError::TooManyVariables {
+                num_vars,
+                max_num_vars: self.num_vars(),
+            })
+    }
+
+    /// Returns the generator point in G1.
+    pub fn g1(&self) -> G1Point<E> {
+        self.g1
+    }
+
+    /// Returns the generator point in G2.
+    pub fn g2(&self) -> G2Point<E> {
+        self.g2
+    }
+
+    /// Returns the generator point in G2 multiplied by each secret.
+    pub fn g2_points(&self) -> &[G2Point<E>] {
+        &self.g2_points
+    }
+}
+
+/// Represents a commitment to a multilinear polynomial.
+#[derive(Debug, Clone, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MultilinearCommitment<E: Pairing>(pub G1Point<E>);
+
+impl<E: Pairing> Add for MultilinearCommitment<E> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self((self.0 + rhs.0).into())
+    }
+}
+
+impl<E: Pairing> Sub for MultilinearCommitment<E> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self((self.0.into_group() - rhs.0).into())
+    }
+}
+
+impl<E: Pairing> Mul<ScalarField<E>> for MultilinearCommitment<E> {
+    type Output = Self;
+
+    fn mul(self, rhs: ScalarField<E>) -> Self::Output {
+        Self(self.0.mul(rhs).into())
+    }
+}
+
+/// Represents an opening of a multilinear polynomial at a point in `F^k`.
+///
+/// The proof holds one witness in G1 per variable, committing to the quotient
+/// by `X_i - z_i` left after fixing the preceding variables to the point.
+#[derive(Debug, Clone, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct MultilinearOpening<E: Pairing> {
+    /// Commitments to the quotients, one per variable.
+    pub witnesses: Vec<G1Point<E>>,
+    /// The evaluation of the polynomial at the point.
+    pub eval: ScalarField<E>,
+}
+
+impl<E: Pairing> MultilinearOpening<E> {
+    /// Retrieves the evaluation associated with the opening.
+    ///
+    /// # Returns
+    ///
+    /// The evaluation (`ScalarField`) of the opening.
+    pub fn eval(&self) -> ScalarField<E> {
+        self.eval
+    }
+}
+
+/// Implements the multilinear KZG commitment scheme of Papamanthou, Shi and Tamassia.
+///
+/// Polynomials are given by their evaluations over the boolean hypercube, the first
+/// variable selecting the lowest bit of the index.
+#[derive(Debug, Clone)]
+pub struct MultilinearKzgScheme<E: Pairing>(MultilinearSrs<E>);
+
+impl<E: Pairing> MultilinearKzgScheme<E> {
+    /// Creates a new instance of `MultilinearKzgScheme` with the given SRS.
+    ///
+    /// # Parameters
+    ///
+    /// - `srs`: The structured reference string (SRS) used in the scheme.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `MultilinearKzgScheme`.
+    pub fn new(srs: MultilinearSrs<E>) -> Self {
+        Self(srs)
+    }
+
+    /// Commits to a multilinear polynomial.
+    ///
+    /// # Parameters
+    ///
+    /// - `polynomial`: The polynomial to be committed to.
+    ///
+    /// # Returns
+    ///
+    /// The commitment to the polynomial, or an error if it has more variables than the SRS.
+    pub fn commit(
+        &self,
+        polynomial: &MultilinearPoly<E>,
+    ) -> Result<MultilinearCommitment<E>, KzgError> {
+        let points = self.0.lagrange_points(polynomial.num_vars)?;
+        let commitment = E::G1::msm_unchecked(points, &polynomial.evaluations);
+        Ok(MultilinearCommitment(commitment.into_affine()))
+    }
+
+    /// Opens a multilinear polynomial at a specified point.
+    ///
+    /// # Parameters
+    ///
+    /// - `polynomial`: The polynomial to be opened.
+    /// - `point`: The point at which the polynomial is opened, one coordinate per variable.
+    ///
+    /// # Returns
+    ///
+    /// The opening at the specified point, or an error if the point does not match the
+    /// number of variables or the polynomial has more variables than the SRS.
+    pub fn open(
+        &self,
+        polynomial: &MultilinearPoly<E>,
+        point: &[ScalarField<E>],
+    ) -> Result<MultilinearOpening<E>, KzgError> {
+        let num_vars = polynomial.num_vars;
+        if point.len() != num_vars {
+            return Err(KzgError::LengthMismatch {
+                expected: num_vars,
+                found: point.len(),
+            });
+        }
+        self.0.lagrange_points(num_vars)?;
+
+        // f(X) - f(z) = sum_i (X_i - z_i) * q_i(X_{i+1}, ..., X_k), where q_i is the difference
+        // of the two halves of f with X_1, ..., X_{i-1} fixed to the point.
+        let mut evaluations = polynomial.evaluations.clone();
+        let mut witnesses = Vec::with_capacity(num_vars);
+        for (i, z_i) in point.iter().enumerate() {
+            let (low, high): (Vec<_>, Vec<_>) =
+                evaluations.chunks(2).map(|pair| (pair[0], pair[1])).unzip();
+            let quotient = low
+                .iter()
+                .zip(&high)
+                .map(|(low, high)| *high - low)
+                .collect::<Vec<_>>();
+            let points = self.0.lagrange_points(num_vars - i - 1)?;
+            witnesses.push(E::G1::msm_unchecked(points, &quotient));
+            evaluations = low
+                .iter()
+                .zip(&quotient)
+                .map(|(low, quotient)| *low + *quotient * z_i)
+                .collect();
+        }
+
+        Ok(MultilinearOpening {
+            witnesses: E::G1::normalize_batch(&witnesses),
+            eval: evaluations[0],
+        })
+    }
+
+    /// Verifies the correctness of an opening.
+    ///
+    /// # Parameters
+    ///
+    /// - `commitment`: The commitment to be verified.
+    /// - `opening`: The opening to be verified.
+    /// - `point`: The point at which the polynomial was opened.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the opening is valid, otherwise `false`.
+    pub fn verify(
+        &self,
+        commitment: &MultilinearCommitment<E>,
+        opening: &MultilinearOpening<E>,
+        point: &[ScalarField<E>],
+    ) -> bool {
+        let num_vars = point.len();
+        let max_num_vars = self.0.num_vars();
+        if opening.witnesses.len() != num_vars || num_vars > max_num_vars {
+            return false;
+        }
+        let g2 = self.0.g2();
+        let g2_points = &self.0.g2_points()[max_num_vars - num_vars..];
+
+        // e(C - [y]_1, [1]_2) = prod_i e(W_i, [tau_i - z_i]_2)
+        let mut g1_points = vec![(commitment.0.into_group() - self.0.g1().mul(opening.eval))];
+        g1_points.extend(opening.witnesses.iter().map(|w| -w.into_group()));
+        let mut h_points = vec![g2.into_group()];
+        h_points.extend(
+            g2_points
+                .iter()
+                .zip(point)
+                .map(|(tau, z)| tau.into_group() - g2.mul(z)),
+        );
+        E::multi_pairing(
+            E::G1::normalize_batch(&g1_points),
+            E::G2::normalize_batch(&h_points),
+        )
+        .is_zero()
+    }
+
+    /// Returns the maximum number of variables of the polynomials the scheme can commit to.
+    pub fn num_vars(&self) -> usize {
+        self.0.num_vars()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_bn254::Bn254;
+    use ark_poly::{DenseMultilinearExtension, MultilinearExtension};
+
+    use super::*;
+
+    fn commit_open_verify<E: Pairing>() {
+        let rng = &mut rand::thread_rng();
+        let scheme = MultilinearKzgScheme::<E>::new(MultilinearSrs::new(5));
+        assert_eq!(scheme.num_vars(), 5);
+        for num_vars in 0..=5 {
+            let poly = DenseMultilinearExtension::<ScalarField<E>>::rand(num_vars, rng);
+            let point = (0..num_vars)
+                .map(|_| ScalarField::<E>::rand(rng))
+                .collect::<Vec<_>>();
+            let commitment = scheme.commit(&poly).unwrap();
+            let opening = scheme.open(&poly, &point).unwrap();
+            assert_eq!(opening.eval(), poly.evaluate(&point).unwrap());
+            assert_eq!(opening.witnesses.len(), num_vars);
+            assert!(scheme.verify(&commitment, &opening, &point));
+
+            let mut forged = opening.clone();
+            forged.eval += ScalarField::<E>::one();
+            assert!(!scheme.verify(&commitment, &forged, &point));
+            if num_vars > 0 {
+                let mut wrong_point = point.clone();
+                wrong_point[0] += ScalarField::<E>::one();
+                assert!(!scheme.verify(&commitment, &opening, &wrong_point));
+                assert!(!scheme.verify(&commitment, &opening, &point[1..]));
+            }
+        }
+    }
+
+    #[test]
+    fn multilinear_commitment() {
+        commit_open_verify::<Bls12_381>();
+        commit_open_verify::<Bn254>();
+    }
+
+    #[test]
+    fn multilinear_commitment_at_secrets() {
+        let secrets = [3u64, 5, 7, 11].map(Fr::from);
+        let srs = MultilinearSrs::<Bls12_381>::new_from_secrets(&secrets);
+        let scheme = MultilinearKzgScheme::new(srs.clone());
+        let evaluations = [1u64, 2, 3, 4].map(Fr::from);
+        let poly = DenseMultilinearExtension::from_evaluations_slice(2, &evaluations);
+
+        // a polynomial in two variables is bound to the last two secrets
+        let commitment = scheme.commit(&poly).unwrap();
+        let expected = srs.g1().mul(poly.evaluate(&secrets[2..]).unwrap());
+        assert_eq!(commitment.0, expected.into_affine());
+
+        // commitments are additively homomorphic
+        let doubled = DenseMultilinearExtension::from_evaluations_vec(
+            2,
+            evaluations.iter().map(|e| *e * Fr::from(3u64)).collect(),
+        );
+        let sum = &poly + &doubled;
+        assert_eq!(
+            scheme.commit(&sum).unwrap(),
+            commitment.clone() + commitment.clone() * Fr::from(3u64)
+        );
+        assert_eq!(
+            scheme.commit(&doubled).unwrap(),
+            scheme.commit(&sum).unwrap() - commitment
+        );
+    }
+
+    #[test]
+    fn reject_invalid_multilinear_inputs() {
+        let rng = &mut rand::thread_rng();
+        let scheme = MultilinearKzgScheme::<Bls12_381>::new(MultilinearSrs::new(3));
+        let poly = DenseMultilinearExtension::<Fr>::rand(4, rng);
+        assert_eq!(
+            scheme.commit(&poly),
+            Err(KzgError::TooManyVariables {
+                num_vars: 4,
+                max_num_vars: 3
+            })
+        );
+        assert_eq!(
+            scheme.open(&poly, &[Fr::one(); 4]),
+            Err(KzgError::TooManyVariables {
+                num_vars: 4,
+                max_num_vars: 3
+            })
+        );
+
+        let poly = DenseMultilinearExtension::<Fr>::rand(3, rng);
+        assert_eq!(
+            scheme.open(&poly, &[Fr::one(); 2]),
+            Err(KzgError::LengthMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+}
@@ -1,45 +1,301 @@
-use std::ops::Add;
+use std::ops::{Add, Mul};
 
-use ark_bls12_381::Fr;
+use ark_ec::pairing::Pairing;
+use ark_ec::{CurveGroup, VariableBaseMSM};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
-use crate::types::G1Point;
+use crate::error::KzgError;
+use crate::types::{G1Point, ScalarField};
 
 /// Represents an opening at a point with its corresponding evaluation.
 ///
 /// `KzgOpening` encapsulates a `G1Point` representing the corresponding point
-/// of quotient polynomial and an `Fr` representing the evaluation.
-#[derive(Debug, Clone)]
-pub struct KzgOpening(pub G1Point, pub Fr);
+/// of quotient polynomial, a `ScalarField` element representing the evaluation
+/// and the point at which the polynomial was opened.
+#[derive(Debug, Clone, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct KzgOpening<E: Pairing>(pub G1Point<E>, pub ScalarField<E>, pub ScalarField<E>);
 
-impl KzgOpening {
+impl<E: Pairing> KzgOpening<E> {
     /// Retrieves the evaluation associated with the opening.
     ///
     /// # Returns
     ///
-    /// The evaluation (`Fr`) of the opening.
-    pub fn eval(self) -> Fr {
+    /// The evaluation (`ScalarField`) of the opening.
+    pub fn eval(self) -> ScalarField<E> {
         self.1
     }
+
+    /// Retrieves the point at which the polynomial was opened.
+    ///
+    /// # Returns
+    ///
+    /// The point (`ScalarField`) of the opening.
+    pub fn point(&self) -> ScalarField<E> {
+        self.2
+    }
 }
 
-impl Add for KzgOpening {
+impl<E: Pairing> KzgOpening<E> {
+    /// Combines openings of several polynomials at the same point into the opening
+    /// of their linear combination `sum_i scalars_i * f_i`.
+    ///
+    /// # Parameters
+    ///
+    /// - `openings`: The openings to be combined.
+    /// - `scalars`: The coefficient of each opening.
+    ///
+    /// # Returns
+    ///
+    /// The combined opening, which verifies against the commitments combined with the same
+    /// scalars at the shared point, or an error if the lengths differ, nothing is combined
+    /// or the openings are at different points.
+    pub fn linear_combination(
+        openings: &[Self],
+        scalars: &[ScalarField<E>],
+    ) -> Result<Self, KzgError> {
+        if openings.len() != scalars.len() {
+            return Err(KzgError::LengthMismatch {
+                expected: openings.len(),
+                found: scalars.len(),
+            });
+        }
+        let Some(first) = openings.first() else {
+            return Err(KzgError::EmptyCombination);
+        };
+        if openings.iter().any(|opening| opening.2 != first.2) {
+            return Err(KzgError::DistinctPoints);
+        }
+
+        let witnesses = openings.iter().map(|opening| opening.0).collect::<Vec<_>>();
+        let witness = E::G1::msm_unchecked(&witnesses, scalars).into_affine();
+        let eval = openings
+            .iter()
+            .zip(scalars)
+            .map(|(opening, scalar)| opening.1 * scalar)
+            .sum();
+        Ok(Self(witness, eval, first.2))
+    }
+}
+
+impl<E: Pairing> Add for KzgOpening<E> {
     type Output = Self;
 
-    /// Combines two openings by adding their evaluations and doubling the witness point.
+    /// Combines two openings at the same point into the opening of the sum of their polynomials,
+    /// by adding their witness points and their evaluations.
     ///
     /// # Parameters
     ///
     /// - `self`: The first `KzgOpening` instance.
-    /// - `rhs`: The second `KzgOpening` instance to be added.
+    /// - `rhs`: The second `KzgOpening` instance to be added, at the same point.
     ///
     /// # Returns
     ///
     /// A new `KzgOpening` instance representing the combined opening.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the openings are at different points, `KzgOpening::linear_combination`
+    /// returns an error instead.
     fn add(self, rhs: Self) -> Self::Output {
-        // Add evaluations
+        assert!(self.2 == rhs.2, "The openings are at different points");
+        let witness = self.0 + rhs.0;
         let eval = self.1 + rhs.1;
-        // Double the witness point
-        let witness = self.0 + self.0;
-        Self(witness.into(), eval)
+        Self(witness.into(), eval, self.2)
+    }
+}
+
+impl<E: Pairing> Mul<ScalarField<E>> for KzgOpening<E> {
+    type Output = Self;
+
+    /// Scales an opening into the opening of its polynomial multiplied by `rhs`.
+    ///
+    /// # Parameters
+    ///
+    /// - `self`: The `KzgOpening` instance.
+    /// - `rhs`: The scalar multiplying the polynomial.
+    ///
+    /// # Returns
+    ///
+    /// A new `KzgOpening` instance representing the scaled opening.
+    fn mul(self, rhs: ScalarField<E>) -> Self::Output {
+        let witness = self.0 * rhs;
+        let eval = self.1 * rhs;
+        Self(witness.into(), eval, self.2)
+    }
+}
+
+/// Represents an opening of a hiding commitment at a point.
+///
+/// `KzgHidingOpening` encapsulates a `G1Point` representing the blinded quotient polynomial,
+/// the evaluation of the polynomial and the evaluation of its blinder.
+#[derive(Debug, Clone, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct KzgHidingOpening<E: Pairing>(pub G1Point<E>, pub ScalarField<E>, pub ScalarField<E>);
+
+impl<E: Pairing> KzgHidingOpening<E> {
+    /// Retrieves the evaluation associated with the opening.
+    ///
+    /// # Returns
+    ///
+    /// The evaluation (`ScalarField`) of the opened polynomial.
+    pub fn eval(&self) -> ScalarField<E> {
+        self.1
+    }
+}
+
+/// Represents an opening of many polynomials at a single point with their evaluations.
+///
+/// `KzgBatchOpening` encapsulates a `G1Point` representing the quotient of the random
+/// linear combination of the polynomials, and the evaluation of every polynomial.
+#[derive(Debug, Clone, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct KzgBatchOpening<E: Pairing>(pub G1Point<E>, pub Vec<ScalarField<E>>);
+
+impl<E: Pairing> KzgBatchOpening<E> {
+    /// Retrieves the evaluations associated with the opening.
+    ///
+    /// # Returns
+    ///
+    /// The evaluations (`ScalarField`) of the opened polynomials, in order.
+    pub fn evals(&self) -> &[ScalarField<E>] {
+        &self.1
+    }
+}
+
+/// Represents a batched opening of many polynomials at several points.
+///
+/// `KzgMultiPointOpening` encapsulates the two `G1Point`s of a BDFG20 (Shplonk) proof:
+/// the commitment to the combined quotient polynomial, and the witness of the
+/// linearized polynomial at the verifier's challenge.
+#[derive(Debug, Clone, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct KzgMultiPointOpening<E: Pairing>(pub G1Point<E>, pub G1Point<E>);
+
+/// Represents a proof that a committed polynomial has degree below a bound.
+///
+/// `KzgDegreeProof` encapsulates the commitment to the polynomial shifted by
+/// `X^(D + 1 - d)`, with `D` the maximum degree of the SRS and `d` the bound.
+#[derive(Debug, Clone, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct KzgDegreeProof<E: Pairing>(pub G1Point<E>);
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_poly::DenseUVPolynomial;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::scheme::KzgScheme;
+    use crate::srs::Srs;
+    use crate::types::Poly;
+
+    fn poly(coeffs: &[u64]) -> Poly<Bls12_381> {
+        Poly::<Bls12_381>::from_coefficients_vec(coeffs.iter().map(|c| Fr::from(*c)).collect())
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn add_openings(
+            a in vec(any::<u64>(), 1..8),
+            b in vec(any::<u64>(), 1..8),
+            z in any::<u64>(),
+        ) {
+            let scheme = KzgScheme::new(Srs::<Bls12_381>::new(8));
+            let (a, b, z) = (poly(&a), poly(&b), Fr::from(z));
+            let opening = scheme.open(&a, z).unwrap() + scheme.open(&b, z).unwrap();
+            let commitment = scheme.commit(&a).unwrap() + scheme.commit(&b).unwrap();
+            prop_assert_eq!(&opening, &scheme.open(&(&a + &b), z).unwrap());
+            prop_assert!(scheme.verify(&commitment, &opening, z));
+        }
+
+        #[test]
+        fn combine_openings(
+            polys in vec(vec(any::<u64>(), 1..8), 1..5),
+            scalars in vec(any::<u64>(), 5),
+            z in any::<u64>(),
+        ) {
+            let scheme = KzgScheme::new(Srs::<Bls12_381>::new(8));
+            let z = Fr::from(z);
+            let polys = polys.iter().map(|coeffs| poly(coeffs)).collect::<Vec<_>>();
+            let scalars = scalars[..polys.len()]
+                .iter()
+                .map(|s| Fr::from(*s))
+                .collect::<Vec<_>>();
+            let openings = polys.iter().map(|p| scheme.open(p, z).unwrap()).collect::<Vec<_>>();
+
+            // a single opening scaled by a scalar
+            let scaled = openings[0].clone() * scalars[0];
+            prop_assert!(scheme.verify(&(scheme.commit(&polys[0]).unwrap() * scalars[0]), &scaled, z));
+
+            let opening = KzgOpening::linear_combination(&openings, &scalars).unwrap();
+            let commitment = polys
+                .iter()
+                .zip(&scalars)
+                .map(|(p, s)| scheme.commit(p).unwrap() * *s)
+                .reduce(|acc, c| acc + c)
+                .unwrap();
+            prop_assert!(scheme.verify(&commitment, &opening, z));
+        }
+
+        #[test]
+        fn reject_combination_at_distinct_points(
+            polys in vec(vec(any::<u64>(), 1..8), 2..5),
+            scalars in vec(any::<u64>(), 5),
+            z in any::<u64>(),
+            shift in 1..u64::MAX,
+            index in any::<prop::sample::Index>(),
+        ) {
+            let scheme = KzgScheme::new(Srs::<Bls12_381>::new(8));
+            let z = Fr::from(z);
+            let scalars = scalars[..polys.len()]
+                .iter()
+                .map(|s| Fr::from(*s))
+                .collect::<Vec<_>>();
+            let moved = index.index(polys.len());
+            let openings = polys
+                .iter()
+                .enumerate()
+                .map(|(i, coeffs)| {
+                    let point = if i == moved { z + Fr::from(shift) } else { z };
+                    scheme.open(&poly(coeffs), point).unwrap()
+                })
+                .collect::<Vec<_>>();
+
+            prop_assert_eq!(
+                KzgOpening::linear_combination(&openings, &scalars),
+                Err(KzgError::DistinctPoints)
+            );
+        }
+    }
+
+    #[test]
+    fn reject_invalid_combination() {
+        let scheme = KzgScheme::new(Srs::<Bls12_381>::new(4));
+        let (a, b) = (poly(&[1, 2, 3]), poly(&[4, 5]));
+        let openings = [
+            scheme.open(&a, 7u64).unwrap(),
+            scheme.open(&b, 7u64).unwrap(),
+        ];
+        let scalars = [Fr::from(2u64), Fr::from(3u64)];
+
+        assert_eq!(
+            KzgOpening::linear_combination(&openings, &scalars[..1]),
+            Err(KzgError::LengthMismatch {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            KzgOpening::<Bls12_381>::linear_combination(&[], &[]),
+            Err(KzgError::EmptyCombination)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The openings are at different points")]
+    fn add_openings_at_distinct_points() {
+        let scheme = KzgScheme::new(Srs::<Bls12_381>::new(4));
+        let (a, b) = (poly(&[1, 2, 3]), poly(&[4, 5]));
+        let _ = scheme.open(&a, 7u64).unwrap() + scheme.open(&b, 8u64).unwrap();
     }
 }
@@ -0,0 +1,273 @@
+use std::error::Error;
+use std::fmt::Debug;
+use std::ops::{Add, Mul, Sub};
+
+use ark_ff::PrimeField;
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::DenseUVPolynomial;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+/// Abstracts a univariate polynomial commitment scheme, so that protocols built on top of it
+/// can swap the scheme without code changes.
+///
+/// `KzgScheme` and `IpaScheme` both implement the trait.
+pub trait PolynomialCommitmentScheme: Clone + Debug {
+    /// The field the committed polynomials are defined over.
+    type Field: PrimeField;
+    /// The public parameters of the scheme.
+    type Params: Clone;
+    /// The commitment to a polynomial.
+    type Commitment: Clone + Debug + PartialEq + CanonicalSerialize + CanonicalDeserialize;
+    /// The opening of a polynomial at a point, holding the evaluation.
+    type Opening: Clone + Debug + PartialEq + CanonicalSerialize + CanonicalDeserialize;
+    /// The opening of many polynomials at the same point, holding the evaluations.
+    type BatchOpening: Clone + Debug + PartialEq + CanonicalSerialize + CanonicalDeserialize;
+    /// The error raised on inputs the scheme cannot commit to or open.
+    type Error: Error;
+
+    /// Generates public parameters for polynomials up to the specified degree.
+    ///
+    /// # Parameters
+    ///
+    /// - `max_degree`: The maximum degree of the polynomials to commit to.
+    ///
+    /// # Returns
+    ///
+    /// The public parameters of the scheme.
+    fn setup(max_degree: usize) -> Self::Params;
+
+    /// Creates a new instance of the scheme with the given parameters.
+    ///
+    /// # Parameters
+    ///
+    /// - `params`: The public parameters used in the scheme.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of the scheme.
+    fn new(params: Self::Params) -> Self;
+
+    /// Returns the maximum degree of the polynomials the scheme can commit to.
+    fn max_degree(&self) -> usize;
+
+    /// Commits to a polynomial.
+    ///
+    /// # Parameters
+    ///
+    /// - `polynomial`: The polynomial to be committed to.
+    ///
+    /// # Returns
+    ///
+    /// The commitment to the polynomial, or an error if its degree exceeds the parameters.
+    fn commit(
+        &self,
+        polynomial: &DensePolynomial<Self::Field>,
+    ) -> Result<Self::Commitment, Self::Error>;
+
+    /// Opens a polynomial at a specified point.
+    ///
+    /// # Parameters
+    ///
+    /// - `polynomial`: The polynomial to be opened.
+    /// - `z`: The point at which the polynomial is opened.
+    ///
+    /// # Returns
+    ///
+    /// The opening at the specified point, or an error if the degree of the polynomial
+    /// exceeds the parameters.
+    fn open(
+        &self,
+        polynomial: &DensePolynomial<Self::Field>,
+        z: Self::Field,
+    ) -> Result<Self::Opening, Self::Error>;
+
+    /// Verifies the correctness of an opening.
+    ///
+    /// # Parameters
+    ///
+    /// - `commitment`: The commitment to be verified.
+    /// - `opening`: The opening to be verified.
+    /// - `z`: The point at which the polynomial was opened.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the opening is valid, otherwise `false`.
+    fn verify(
+        &self,
+        commitment: &Self::Commitment,
+        opening: &Self::Opening,
+        z: Self::Field,
+    ) -> bool;
+
+    /// Retrieves the evaluation claimed by an opening.
+    fn evaluation(opening: &Self::Opening) -> Self::Field;
+
+    /// Opens many polynomials at the same point with a single proof.
+    ///
+    /// # Parameters
+    ///
+    /// - `polynomials`: The polynomials to be opened.
+    /// - `commitments`: The commitments to the polynomials, bound to the challenge.
+    /// - `z`: The point at which the polynomials are opened.
+    ///
+    /// # Returns
+    ///
+    /// The opening, holding the evaluation of every polynomial at `z`, or an error
+    /// if the polynomials and commitments differ in number or a degree exceeds the parameters.
+    fn batch_open(
+        &self,
+        polynomials: &[DensePolynomial<Self::Field>],
+        commitments: &[Self::Commitment],
+        z: Self::Field,
+    ) -> Result<Self::BatchOpening, Self::Error>;
+
+    /// Verifies an opening produced by `batch_open`.
+    ///
+    /// # Parameters
+    ///
+    /// - `commitments`: The commitments to the polynomials.
+    /// - `opening`: The opening to be verified, holding the claimed evaluations.
+    /// - `z`: The point at which the polynomials were opened.
+    ///
+    /// # Returns
+    ///
+    /// `true` if every claimed evaluation is valid, otherwise `false`.
+    fn batch_verify(
+        &self,
+        commitments: &[Self::Commitment],
+        opening: &Self::BatchOpening,
+        z: Self::Field,
+    ) -> bool;
+
+    /// Retrieves the evaluations claimed by a batched opening, in order.
+    fn batch_evaluations(opening: &Self::BatchOpening) -> Vec<Self::Field>;
+
+    /// Commits to a coefficient vector.
+    ///
+    /// # Parameters
+    ///
+    /// - `coeffs`: The coefficient vector to be committed to.
+    ///
+    /// # Returns
+    ///
+    /// The commitment to the polynomial, or an error if its degree exceeds the parameters.
+    fn commit_vector(&self, coeffs: &[Self::Field]) -> Result<Self::Commitment, Self::Error> {
+        self.commit(&DensePolynomial::from_coefficients_slice(coeffs))
+    }
+
+    /// Opens a coefficient vector at a specified point.
+    ///
+    /// # Parameters
+    ///
+    /// - `coeffs`: The coefficient vector to be opened.
+    /// - `z`: The point at which the polynomial is opened.
+    ///
+    /// # Returns
+    ///
+    /// The opening at the specified point, or an error if the degree of the polynomial
+    /// exceeds the parameters.
+    fn open_vector(
+        &self,
+        coeffs: &[Self::Field],
+        z: Self::Field,
+    ) -> Result<Self::Opening, Self::Error> {
+        self.open(&DensePolynomial::from_coefficients_slice(coeffs), z)
+    }
+}
+
+/// A polynomial commitment scheme whose commitments are additively homomorphic,
+/// as required by PLONK's linearisation and Nova's folding.
+///
+/// The trait is implemented for every scheme whose commitments support the operations.
+pub trait HomomorphicCommitmentScheme:
+    PolynomialCommitmentScheme<
+    Commitment: Add<Output = <Self as PolynomialCommitmentScheme>::Commitment>
+                    + Sub<Output = <Self as PolynomialCommitmentScheme>::Commitment>
+                    + Mul<
+        <Self as PolynomialCommitmentScheme>::Field,
+        Output = <Self as PolynomialCommitmentScheme>::Commitment,
+    >,
+>
+{
+}
+
+impl<P: PolynomialCommitmentScheme> HomomorphicCommitmentScheme for P where
+    P::Commitment: Add<Output = P::Commitment>
+        + Sub<Output = P::Commitment>
+        + Mul<P::Field, Output = P::Commitment>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::Bls12_381;
+    use ark_bn254::Bn254;
+    use ark_ff::One;
+    use ark_poly::Polynomial;
+
+    use crate::ipa::IpaScheme;
+    use crate::scheme::KzgScheme;
+
+    use super::*;
+
+    /// Runs a scheme through the generic commit, open and verify surface.
+    fn commit_open_verify<P: HomomorphicCommitmentScheme>() {
+        let scheme = P::new(P::setup(9));
+        assert!(scheme.max_degree() >= 9);
+        let rng = &mut rand::thread_rng();
+        let polys = (0..3)
+            .map(|degree| DensePolynomial::<P::Field>::rand(3 * degree + 3, rng))
+            .collect::<Vec<_>>();
+        let commitments = polys
+            .iter()
+            .map(|poly| scheme.commit(poly).unwrap())
+            .collect::<Vec<_>>();
+        let z = P::Field::from(7u64);
+
+        let opening = scheme.open(&polys[0], z).unwrap();
+        assert_eq!(P::evaluation(&opening), polys[0].evaluate(&z));
+        assert!(scheme.verify(&commitments[0], &opening, z));
+        assert!(!scheme.verify(&commitments[1], &opening, z));
+        assert!(!scheme.verify(&commitments[0], &opening, z + P::Field::one()));
+
+        // commitments are additively homomorphic
+        let factor = P::Field::from(3u64);
+        let combined = &polys[0] + &(&polys[1] * factor);
+        let commitment = commitments[0].clone() + commitments[1].clone() * factor;
+        let opening = scheme.open(&combined, z).unwrap();
+        assert_eq!(commitment, scheme.commit(&combined).unwrap());
+        assert!(scheme.verify(&commitment, &opening, z));
+        assert!(scheme.verify(
+            &(commitment - commitments[0].clone()),
+            &scheme.open(&(&combined - &polys[0]), z).unwrap(),
+            z
+        ));
+
+        let opening = scheme.batch_open(&polys, &commitments, z).unwrap();
+        let evaluations = polys
+            .iter()
+            .map(|poly| poly.evaluate(&z))
+            .collect::<Vec<_>>();
+        assert_eq!(P::batch_evaluations(&opening), evaluations);
+        assert!(scheme.batch_verify(&commitments, &opening, z));
+        assert!(!scheme.batch_verify(&commitments[..2], &opening, z));
+        assert!(!scheme.batch_verify(&commitments, &opening, z + P::Field::one()));
+
+        let coeffs = [1u64, 2, 3].map(P::Field::from);
+        let commitment = scheme.commit_vector(&coeffs).unwrap();
+        let opening = scheme.open_vector(&coeffs, z).unwrap();
+        assert!(scheme.verify(&commitment, &opening, z));
+
+        let poly = DensePolynomial::<P::Field>::rand(scheme.max_degree() + 1, rng);
+        assert!(scheme.commit(&poly).is_err());
+        assert!(scheme.open(&poly, z).is_err());
+    }
+
+    #[test]
+    fn polynomial_commitment_scheme() {
+        commit_open_verify::<KzgScheme<Bls12_381>>();
+        commit_open_verify::<KzgScheme<Bn254>>();
+        commit_open_verify::<IpaScheme<Bls12_381>>();
+        commit_open_verify::<IpaScheme<Bn254>>();
+    }
+}
@@ -0,0 +1,365 @@
+use std::io::{self, Read};
+
+use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
+use ark_ff::{BigInteger, Field, PrimeField, Zero};
+
+use crate::error::KzgError;
+
+/// Magic bytes at the start of every `.ptau` file.
+const MAGIC: &[u8; 4] = b"ptau";
+/// Section holding the field size, the prime and the ceremony power.
+const SECTION_HEADER: u32 = 1;
+/// Section holding the powers of tau in G1.
+const SECTION_TAU_G1: u32 = 2;
+/// Section holding the powers of tau in G2.
+const SECTION_TAU_G2: u32 = 3;
+
+/// Powers of tau in G1 and G2 read from a ceremony transcript.
+pub(crate) type PtauPowers<P1, P2> = (Vec<Affine<P1>>, Vec<Affine<P2>>);
+
+/// Reads the first powers of tau from a Powers-of-Tau transcript in the `.ptau` format,
+/// as produced by snarkjs and the perpetual powers of tau ceremony.
+///
+/// Coordinates are stored as little-endian field elements in Montgomery form,
+/// each point is checked to lie on the curve and in the prime-order subgroup.
+///
+/// # Parameters
+///
+/// - `reader`: Source of the `.ptau` transcript.
+/// - `num_g1`: Number of G1 powers to read, starting from tau^0.
+/// - `num_g2`: Number of G2 powers to read, starting from tau^0.
+///
+/// # Returns
+///
+/// The G1 and G2 powers, or an error if the transcript is malformed,
+/// belongs to another curve or holds fewer powers than requested.
+pub(crate) fn read_ptau<R: Read, P1: SWCurveConfig, P2: SWCurveConfig>(
+    mut reader: R,
+    num_g1: usize,
+    num_g2: usize,
+) -> Result<PtauPowers<P1, P2>, KzgError> {
+    let mut magic = [0u8; 4];
+    read_exact(&mut reader, &mut magic)?;
+    if &magic != MAGIC {
+        return Err(KzgError::MalformedFile("not a ptau file"));
+    }
+    let version = read_u32(&mut reader)?;
+    if version != 1 {
+        return Err(KzgError::UnsupportedVersion(version));
+    }
+    let num_sections = read_u32(&mut reader)?;
+
+    let mut n8: Option<usize> = None;
+    let mut g1_powers = None;
+    let mut g2_powers = None;
+    for _ in 0..num_sections {
+        let section_type = read_u32(&mut reader)?;
+        let section_size = read_u64(&mut reader)?;
+        match section_type {
+            SECTION_HEADER => {
+                let field_size = read_u32(&mut reader)? as usize;
+                let mut modulus = vec![0u8; field_size];
+                read_exact(&mut reader, &mut modulus)?;
+                if !is_base_modulus::<P1>(&modulus) {
+                    return Err(KzgError::CurveMismatch);
+                }
+                let power = read_u32(&mut reader)?;
+                if power >= usize::BITS - 1 {
+                    return Err(KzgError::MalformedFile("invalid ceremony power"));
+                }
+                let max_g1 = (1usize << (power + 1)) - 1;
+                let max_g2 = 1usize << power;
+                if num_g1 > max_g1 {
+                    return Err(KzgError::SrsTooSmall {
+                        powers: max_g1,
+                        needed: num_g1,
+                    });
+                }
+                if num_g2 > max_g2 {
+                    return Err(KzgError::SrsTooSmall {
+                        powers: max_g2,
+                        needed: num_g2,
+                    });
+                }
+                let consumed = (4 + field_size + 4) as u64;
+                skip(&mut reader, section_size.saturating_sub(consumed))?;
+                n8 = Some(field_size);
+            }
+            SECTION_TAU_G1 | SECTION_TAU_G2 => {
+                let n8 = n8.ok_or(KzgError::MalformedFile(
+                    "the ptau header must precede the points",
+                ))?;
+                let (count, point_size) = if section_type == SECTION_TAU_G1 {
+                    (num_g1, 2 * n8 * base_degree::<P1>())
+                } else {
+                    (num_g2, 2 * n8 * base_degree::<P2>())
+                };
+                let expected = (count * point_size) as u64;
+                if section_size < expected {
+                    return Err(KzgError::MalformedFile("truncated ptau section"));
+                }
+                if section_type == SECTION_TAU_G1 {
+                    g1_powers = Some(read_points::<_, P1>(&mut reader, count, n8)?);
+                } else {
+                    g2_powers = Some(read_points::<_, P2>(&mut reader, count, n8)?);
+                }
+                skip(&mut reader, section_size - expected)?;
+            }
+            _ => skip(&mut reader, section_size)?,
+        }
+        if g1_powers.is_some() && g2_powers.is_some() {
+            break;
+        }
+    }
+
+    match (g1_powers, g2_powers) {
+        (Some(g1_powers), Some(g2_powers)) => Ok((g1_powers, g2_powers)),
+        _ => Err(KzgError::MalformedFile(
+            "missing powers of tau in the ptau file",
+        )),
+    }
+}
+
+/// Reads `count` affine points whose coordinates are `n8`-byte Montgomery-form limbs.
+fn read_points<R: Read, P: SWCurveConfig>(
+    reader: &mut R,
+    count: usize,
+    n8: usize,
+) -> Result<Vec<Affine<P>>, KzgError> {
+    (0..count)
+        .map(|_| {
+            let x = read_field::<_, P::BaseField>(reader, n8)?;
+            let y = read_field::<_, P::BaseField>(reader, n8)?;
+            // snarkjs encodes the point at infinity as all zeros
+            if x.is_zero() && y.is_zero() {
+                return Ok(Affine::identity());
+            }
+            let point = Affine::new_unchecked(x, y);
+            if !point.is_on_curve() || !point.is_in_correct_subgroup_assuming_on_curve() {
+                return Err(KzgError::MalformedFile("invalid curve point in ptau file"));
+            }
+            Ok(point)
+        })
+        .collect()
+}
+
+/// Reads a field element stored as Montgomery-form limbs of its base prime field.
+fn read_field<R: Read, F: Field>(reader: &mut R, n8: usize) -> Result<F, KzgError> {
+    // Montgomery form holds x * 2^(8 * n8), so multiply by the inverse to recover x
+    let r_inv = F::BasePrimeField::from(2u64)
+        .pow([8 * n8 as u64])
+        .inverse()
+        .ok_or(KzgError::MalformedFile("invalid field size"))?;
+    let mut bytes = vec![0u8; n8];
+    let limbs = (0..F::extension_degree())
+        .map(|_| {
+            read_exact(reader, &mut bytes)?;
+            Ok(F::BasePrimeField::from_le_bytes_mod_order(&bytes) * r_inv)
+        })
+        .collect::<Result<Vec<_>, KzgError>>()?;
+    F::from_base_prime_field_elems(&limbs).ok_or(KzgError::MalformedFile("invalid field element"))
+}
+
+/// Checks that `modulus` is the little-endian modulus of the base prime field of `P`.
+fn is_base_modulus<P: SWCurveConfig>(modulus: &[u8]) -> bool {
+    let expected = <<P::BaseField as Field>::BasePrimeField as PrimeField>::MODULUS.to_bytes_le();
+    let len = expected.len().max(modulus.len());
+    let padded = |bytes: &[u8]| {
+        let mut bytes = bytes.to_vec();
+        bytes.resize(len, 0);
+        bytes
+    };
+    padded(&expected) == padded(modulus)
+}
+
+/// Extension degree of the field the coordinates of `P` live in.
+fn base_degree<P: SWCurveConfig>() -> usize {
+    P::BaseField::extension_degree() as usize
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, KzgError> {
+    let mut bytes = [0u8; 4];
+    read_exact(reader, &mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, KzgError> {
+    let mut bytes = [0u8; 8];
+    read_exact(reader, &mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_exact<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<(), KzgError> {
+    reader
+        .read_exact(buf)
+        .map_err(|e| KzgError::Serialization(e.to_string()))
+}
+
+fn skip<R: Read>(reader: &mut R, len: u64) -> Result<(), KzgError> {
+    let skipped = io::copy(&mut reader.take(len), &mut io::sink())
+        .map_err(|e| KzgError::Serialization(e.to_string()))?;
+    if skipped != len {
+        return Err(KzgError::MalformedFile("truncated ptau section"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Mul;
+
+    use ark_bls12_381::Bls12_381;
+    use ark_bn254::Bn254;
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_ff::One;
+
+    use super::*;
+    use crate::srs::Srs;
+
+    /// Writes a field element as Montgomery-form limbs of its base prime field.
+    fn write_field<F: Field>(out: &mut Vec<u8>, value: &F, n8: usize) {
+        let r = F::BasePrimeField::from(2u64).pow([8 * n8 as u64]);
+        for limb in value.to_base_prime_field_elements() {
+            let mut bytes = (limb * r).into_bigint().to_bytes_le();
+            bytes.resize(n8, 0);
+            out.extend(bytes);
+        }
+    }
+
+    fn write_point<P: SWCurveConfig>(out: &mut Vec<u8>, point: &Affine<P>, n8: usize) {
+        write_field(out, &point.x, n8);
+        write_field(out, &point.y, n8);
+    }
+
+    fn write_section(out: &mut Vec<u8>, section_type: u32, data: &[u8]) {
+        out.extend(section_type.to_le_bytes());
+        out.extend((data.len() as u64).to_le_bytes());
+        out.extend(data);
+    }
+
+    /// Generates a small ceremony transcript in the `.ptau` format from a known secret.
+    fn write_ptau<P1, P2>(secret: P1::ScalarField, power: u32) -> Vec<u8>
+    where
+        P1: SWCurveConfig,
+        P2: SWCurveConfig<ScalarField = P1::ScalarField>,
+    {
+        let modulus = <<P1::BaseField as Field>::BasePrimeField as PrimeField>::MODULUS;
+        let n8 = modulus.to_bytes_le().len();
+
+        let mut header = Vec::new();
+        header.extend((n8 as u32).to_le_bytes());
+        header.extend(modulus.to_bytes_le());
+        header.extend(power.to_le_bytes());
+        header.extend(power.to_le_bytes());
+
+        let powers = |count: usize| {
+            let mut cur = P1::ScalarField::one();
+            (0..count)
+                .map(|_| {
+                    let res = cur;
+                    cur *= secret;
+                    res
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut tau_g1 = Vec::new();
+        for s in powers((1 << (power + 1)) - 1) {
+            write_point(
+                &mut tau_g1,
+                &Affine::<P1>::generator().mul(s).into_affine(),
+                n8,
+            );
+        }
+        let mut tau_g2 = Vec::new();
+        for s in powers(1 << power) {
+            write_point(
+                &mut tau_g2,
+                &Affine::<P2>::generator().mul(s).into_affine(),
+                n8,
+            );
+        }
+
+        let mut out = MAGIC.to_vec();
+        out.extend(1u32.to_le_bytes());
+        out.extend(4u32.to_le_bytes());
+        write_section(&mut out, SECTION_HEADER, &header);
+        // sections may appear in any order, unknown ones are skipped
+        write_section(&mut out, 4, &[0u8; 16]);
+        write_section(&mut out, SECTION_TAU_G1, &tau_g1);
+        write_section(&mut out, SECTION_TAU_G2, &tau_g2);
+        out
+    }
+
+    #[test]
+    fn load_ptau_test() {
+        let secret = ark_bn254::Fr::from(1234567u64);
+        let ptau = write_ptau::<ark_bn254::g1::Config, ark_bn254::g2::Config>(secret, 3);
+
+        let srs = Srs::<Bn254>::from_ptau(&ptau[..], 5).unwrap();
+        assert_eq!(srs, Srs::new_from_secret(secret, 5));
+
+        let secret = ark_bls12_381::Fr::from(7654321u64);
+        let ptau = write_ptau::<ark_bls12_381::g1::Config, ark_bls12_381::g2::Config>(secret, 2);
+        let srs = Srs::<Bls12_381>::from_ptau(&ptau[..], 4).unwrap();
+        assert_eq!(srs, Srs::new_from_secret(secret, 4));
+    }
+
+    #[test]
+    fn truncate_ptau_test() {
+        let secret = ark_bn254::Fr::from(42u64);
+        let ptau = write_ptau::<ark_bn254::g1::Config, ark_bn254::g2::Config>(secret, 3);
+
+        // 2^4 - 1 = 15 G1 powers are available, a circuit of size n needs n + 3
+        let srs = Srs::<Bn254>::from_ptau(&ptau[..], 1).unwrap();
+        assert_eq!(srs.g1_points().len(), 4);
+        assert_eq!(srs, Srs::new_from_secret(secret, 1));
+        assert!(Srs::<Bn254>::from_ptau(&ptau[..], 12).is_ok());
+        assert_eq!(
+            Srs::<Bn254>::from_ptau(&ptau[..], 13),
+            Err(KzgError::SrsTooSmall {
+                powers: 15,
+                needed: 16
+            })
+        );
+    }
+
+    #[test]
+    fn reject_inconsistent_ptau_test() {
+        let secret = ark_bn254::Fr::from(42u64);
+        let mut ptau = write_ptau::<ark_bn254::g1::Config, ark_bn254::g2::Config>(secret, 3);
+
+        // replace the third G1 power with another valid point
+        let n8 = 32;
+        let mut forged = Vec::new();
+        let point = ark_bn254::G1Affine::generator()
+            .mul(ark_bn254::Fr::from(5u64))
+            .into_affine();
+        write_point(&mut forged, &point, n8);
+        let offset = ptau.len() - 8 * 4 * n8 - 12 - 15 * 2 * n8 + 2 * 2 * n8;
+        ptau[offset..offset + 2 * n8].copy_from_slice(&forged);
+
+        assert_eq!(
+            Srs::<Bn254>::from_ptau(&ptau[..], 5),
+            Err(KzgError::MalformedSrs("the powers are inconsistent"))
+        );
+    }
+
+    #[test]
+    fn reject_invalid_ptau_test() {
+        let secret = ark_bls12_381::Fr::from(42u64);
+        let ptau = write_ptau::<ark_bls12_381::g1::Config, ark_bls12_381::g2::Config>(secret, 2);
+
+        // a ceremony over another curve
+        assert_eq!(
+            Srs::<Bn254>::from_ptau(&ptau[..], 1),
+            Err(KzgError::CurveMismatch)
+        );
+        // not a ptau file
+        assert_eq!(
+            Srs::<Bls12_381>::from_ptau(&b"zkey\x01\x00\x00\x00"[..], 1),
+            Err(KzgError::MalformedFile("not a ptau file"))
+        );
+        // truncated transcript
+        assert!(Srs::<Bls12_381>::from_ptau(&ptau[..ptau.len() / 2], 1).is_err());
+    }
+}
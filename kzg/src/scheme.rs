@@ -1,27 +1,30 @@
 use std::fmt::{Debug, Display};
 use std::ops::{Add, Mul, Neg, Sub};
 
-use ark_bls12_381::{Bls12_381, Fr, G1Projective};
 use ark_ec::pairing::Pairing;
-use ark_ec::short_weierstrass::Affine;
-use ark_ec::{AffineRepr, CurveGroup};
-use ark_ff::{One, Zero};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{batch_inversion, Field, One, Zero};
 use ark_poly::univariate::DensePolynomial;
-use ark_poly::{DenseUVPolynomial, Polynomial};
-use rand::{Rng, RngCore};
+use ark_poly::{DenseUVPolynomial, EvaluationDomain, Polynomial};
 
 use crate::commitment::KzgCommitment;
-use crate::opening::KzgOpening;
+use crate::error::KzgError;
+use crate::opening::{
+    KzgBatchOpening, KzgDegreeProof, KzgHidingOpening, KzgMultiPointOpening, KzgOpening,
+};
+use crate::pcs::PolynomialCommitmentScheme;
 use crate::srs::Srs;
+use crate::transcript::Transcript;
 use crate::types::{G1Point, Poly, ScalarField};
 
 /// Implements the KZG polynomial commitment scheme.
 ///
 /// The `KzgScheme` struct provides methods for committing to polynomials, opening commitments,
-/// and verifying openings.
-pub struct KzgScheme(Srs);
+/// and verifying openings over the pairing-friendly curve `E`.
+#[derive(Debug, Clone)]
+pub struct KzgScheme<E: Pairing>(Srs<E>);
 
-impl KzgScheme {
+impl<E: Pairing> KzgScheme<E> {
     /// Creates a new instance of `KzgScheme` with the given structured reference string (SRS).
     ///
     /// # Parameters
@@ -31,12 +34,17 @@ impl KzgScheme {
     /// # Returns
     ///
     /// A new instance of `KzgScheme`.
-    pub fn new(srs: Srs) -> Self {
+    pub fn new(srs: Srs<E>) -> Self {
         Self(srs)
     }
+
+    /// Borrows the structured reference string (SRS) used in the scheme.
+    pub(crate) fn srs(&self) -> &Srs<E> {
+        &self.0
+    }
 }
 
-impl KzgScheme {
+impl<E: Pairing> KzgScheme<E> {
     /// Commits to a polynomial using the KZG scheme.
     ///
     /// # Parameters
@@ -45,10 +53,10 @@ impl KzgScheme {
     ///
     /// # Returns
     ///
-    /// The commitment to the polynomial.
-    pub fn commit(&self, polynomial: &Poly) -> KzgCommitment {
-        let commitment = self.evaluate_in_s(polynomial);
-        KzgCommitment(commitment)
+    /// The commitment to the polynomial, or an error if its degree exceeds the SRS.
+    pub fn commit(&self, polynomial: &Poly<E>) -> Result<KzgCommitment<E>, KzgError> {
+        let commitment = self.evaluate_in_s(polynomial)?;
+        Ok(KzgCommitment(commitment))
     }
 
     /// Commits to a coefficient vector using the KZG scheme.
@@ -59,11 +67,11 @@ impl KzgScheme {
     ///
     /// # Returns
     ///
-    /// The commitment to the polynomial.
-    pub fn commit_vector(&self, coeffs: &[ScalarField]) -> KzgCommitment {
+    /// The commitment to the polynomial, or an error if its degree exceeds the SRS.
+    pub fn commit_vector(&self, coeffs: &[ScalarField<E>]) -> Result<KzgCommitment<E>, KzgError> {
         let new_poly = DensePolynomial::from_coefficients_vec(coeffs.into());
-        let commitment = self.evaluate_in_s(&new_poly);
-        KzgCommitment(commitment)
+        let commitment = self.evaluate_in_s(&new_poly)?;
+        Ok(KzgCommitment(commitment))
     }
 
     /// Commits to a parameter using the KZG scheme.
@@ -74,25 +82,52 @@ impl KzgScheme {
     ///
     /// # Returns
     ///
-    /// The commitment to the parameter.
-    pub fn commit_para(&self, para: Fr) -> KzgCommitment {
-        let g1_0 = *self.0.g1_points().first().unwrap();
+    /// The commitment to the parameter, or an error if the SRS is empty.
+    pub fn commit_para(&self, para: ScalarField<E>) -> Result<KzgCommitment<E>, KzgError> {
+        let g1_0 = *self.0.g1_points_slice().first().ok_or(KzgError::EmptySrs)?;
         let commitment = g1_0.mul(para).into();
-        KzgCommitment(commitment)
+        Ok(KzgCommitment(commitment))
+    }
+
+    /// Commits to a polynomial given by its evaluations over the Lagrange basis domain of the SRS,
+    /// without interpolating it.
+    ///
+    /// # Parameters
+    ///
+    /// - `evaluations`: The evaluations over the domain, missing trailing evaluations are zero.
+    ///
+    /// # Returns
+    ///
+    /// The commitment to the polynomial, or an error if the SRS has no Lagrange basis points
+    /// or there are more evaluations than points in the domain.
+    pub fn commit_evaluations(
+        &self,
+        evaluations: &[ScalarField<E>],
+    ) -> Result<KzgCommitment<E>, KzgError> {
+        let lagrange_points = self.0.lagrange_points_slice();
+        if lagrange_points.is_empty() {
+            return Err(KzgError::MissingLagrangeBasis);
+        }
+        if lagrange_points.len() < evaluations.len() {
+            return Err(KzgError::TooManyEvaluations {
+                evaluations: evaluations.len(),
+                domain_size: lagrange_points.len(),
+            });
+        }
+
+        let commitment =
+            E::G1::msm_unchecked(&lagrange_points[..evaluations.len()], evaluations).into_affine();
+        Ok(KzgCommitment(commitment))
     }
 
-    fn evaluate_in_s(&self, polynomial: &Poly) -> G1Point {
-        let g1_points = self.0.g1_points();
-        assert!(g1_points.len() > polynomial.degree());
+    /// Evaluates the polynomial at the secret in G1 with a multi-scalar multiplication
+    /// of its coefficients over the SRS points.
+    fn evaluate_in_s(&self, polynomial: &Poly<E>) -> Result<G1Point<E>, KzgError> {
+        let g1_points = self.0.g1_points_slice();
+        check_degree(polynomial, g1_points)?;
 
-        let poly = polynomial.coeffs.iter();
-        let g1_points = g1_points.into_iter();
-        let point: G1Point = poly
-            .zip(g1_points)
-            .map(|(cof, s)| s.mul(cof).into_affine())
-            .reduce(|acc, e| acc.add(e).into_affine())
-            .unwrap_or(G1Point::zero());
-        point
+        let coeffs = &polynomial.coeffs;
+        Ok(E::G1::msm_unchecked(&g1_points[..coeffs.len()], coeffs).into_affine())
     }
 
     /// Opens a commitment at a specified point.
@@ -104,19 +139,32 @@ impl KzgScheme {
     ///
     /// # Returns
     ///
-    /// The opening at the specified point.
-    pub fn open(&self, polynomial: &Poly, z: impl Into<Fr>) -> KzgOpening {
+    /// The opening at the specified point, or an error if the degree of the polynomial
+    /// exceeds the SRS.
+    pub fn open(
+        &self,
+        polynomial: &Poly<E>,
+        z: impl Into<ScalarField<E>>,
+    ) -> Result<KzgOpening<E>, KzgError> {
+        check_degree(polynomial, self.0.g1_points_slice())?;
         let z = z.into();
+        let (quotient_poly, evaluation_at_z) = Self::quotient(polynomial, z);
+        let opening = self.evaluate_in_s(&quotient_poly)?;
+
+        Ok(KzgOpening(opening, evaluation_at_z, z))
+    }
+
+    /// Divides the polynomial minus its evaluation at `z` by `X - z`.
+    fn quotient(polynomial: &Poly<E>, z: ScalarField<E>) -> (Poly<E>, ScalarField<E>) {
         let evaluation_at_z = polynomial.evaluate(&z);
         let mut new_poly = polynomial.clone();
-        let first = new_poly.coeffs.first_mut().expect("at least 1");
-        *first -= evaluation_at_z;
-        let root = Poly::from_coefficients_slice(&[-z, 1.into()]);
+        if new_poly.coeffs.is_empty() {
+            new_poly.coeffs.push(ScalarField::<E>::zero());
+        }
+        new_poly.coeffs[0] -= evaluation_at_z;
+        let root = Poly::<E>::from_coefficients_slice(&[-z, ScalarField::<E>::one()]);
         // quotient polynomial
-        let quotient_poly = &new_poly / &root;
-        let opening = self.evaluate_in_s(&quotient_poly);
-
-        KzgOpening(opening, evaluation_at_z)
+        (&new_poly / &root, evaluation_at_z)
     }
 
     /// Opens a commitment at a specified point.
@@ -128,17 +176,79 @@ impl KzgScheme {
     ///
     /// # Returns
     ///
-    /// The opening at the specified point.
-    pub fn open_vector(&self, coeffs: &[ScalarField], z: impl Into<Fr>) -> KzgOpening {
+    /// The opening at the specified point, or an error if the degree of the polynomial
+    /// exceeds the SRS.
+    pub fn open_vector(
+        &self,
+        coeffs: &[ScalarField<E>],
+        z: impl Into<ScalarField<E>>,
+    ) -> Result<KzgOpening<E>, KzgError> {
+        let polynomial = DensePolynomial::from_coefficients_vec(coeffs.into());
+        self.open(&polynomial, z)
+    }
+
+    /// Opens a commitment made by `KzgScheme::commit_evaluations` at a specified point,
+    /// computing the quotient polynomial in evaluation form.
+    ///
+    /// # Parameters
+    ///
+    /// - `evaluations`: The evaluations over the domain, missing trailing evaluations are zero.
+    /// - `z`: The point at which the polynomial is opened, inside or outside the domain.
+    ///
+    /// # Returns
+    ///
+    /// The opening at the specified point, or an error if the SRS has no Lagrange basis points
+    /// or there are more evaluations than points in the domain.
+    pub fn open_evaluations(
+        &self,
+        evaluations: &[ScalarField<E>],
+        z: impl Into<ScalarField<E>>,
+    ) -> Result<KzgOpening<E>, KzgError> {
         let z = z.into();
-        let mut polynomial = DensePolynomial::from_coefficients_vec(coeffs.into());
-        let evaluation_at_z = polynomial.evaluate(&z);
-        let first = polynomial.coeffs.first_mut().expect("at least 1");
-        *first -= evaluation_at_z;
-        let root = Poly::from_coefficients_slice(&[-z, 1.into()]);
-        let quotient_poly = &polynomial / &root;
-        let opening = self.evaluate_in_s(&quotient_poly);
-        KzgOpening(opening, evaluation_at_z)
+        let domain = self
+            .0
+            .lagrange_domain()
+            .ok_or(KzgError::MissingLagrangeBasis)?;
+        if domain.size() < evaluations.len() {
+            return Err(KzgError::TooManyEvaluations {
+                evaluations: evaluations.len(),
+                domain_size: domain.size(),
+            });
+        }
+
+        let mut evaluations = evaluations.to_vec();
+        evaluations.resize(domain.size(), ScalarField::<E>::zero());
+        let elements = domain.elements().collect::<Vec<_>>();
+        let position = elements.iter().position(|element| *element == z);
+        let evaluation_at_z = match position {
+            Some(m) => evaluations[m],
+            None => domain
+                .evaluate_all_lagrange_coefficients(z)
+                .iter()
+                .zip(&evaluations)
+                .map(|(l, f)| *l * f)
+                .sum(),
+        };
+
+        // q_i = (f_i - f(z)) / (w^i - z), the zero denominator at z = w^m is skipped
+        let mut quotient = elements.iter().map(|w| *w - z).collect::<Vec<_>>();
+        batch_inversion(&mut quotient);
+        for (q, f) in quotient.iter_mut().zip(&evaluations) {
+            *q *= *f - evaluation_at_z;
+        }
+        // the quotient has degree below n - 1, so sum_i q_i / A'(w^i) = 0 with A'(w^i) = n * w^(-i),
+        // which gives q_m = -sum_{i != m} q_i * w^(i - m)
+        if let Some(m) = position {
+            let sum = quotient
+                .iter()
+                .zip(&elements)
+                .map(|(q, w)| *q * w)
+                .sum::<ScalarField<E>>();
+            quotient[m] = -sum * elements[(domain.size() - m) % domain.size()];
+        }
+        let opening = E::G1::msm_unchecked(self.0.lagrange_points_slice(), &quotient).into_affine();
+
+        Ok(KzgOpening(opening, evaluation_at_z, z))
     }
 
     /// Verifies the correctness of an opening.
@@ -151,23 +261,32 @@ impl KzgScheme {
     ///
     /// # Returns
     ///
-    /// `true` if the opening is valid, otherwise `false`.
+    /// `true` if the opening is valid and was made at `z`, otherwise `false`.
     pub fn verify(
         &self,
-        commitment: &KzgCommitment,
-        opening: &KzgOpening,
-        z: impl Into<Fr> + Debug + Display,
+        commitment: &KzgCommitment<E>,
+        opening: &KzgOpening<E>,
+        z: impl Into<ScalarField<E>> + Debug + Display,
     ) -> bool {
-        let y = opening.1;
-        let g2s = self.0.g2s();
-        let g2 = self.0.g2();
-        let a = g2s.sub(g2.mul(z.into()).into_affine());
-        let b = commitment.0.sub(G1Point::generator().mul(y).into_affine());
-        // e([Q]_1, [x]_2 - G_2 ⋅ z)
-        let pairing1 = Bls12_381::pairing(opening.0, a);
-        // e([P]_1 - G_1 ⋅ P(x), G_2)
-        let pairing2 = Bls12_381::pairing(b, g2);
-        pairing1 == pairing2
+        let KzgOpening(witness, y, point) = opening;
+        let z = z.into();
+        if *point != z {
+            return false;
+        }
+        let g1 = G1Point::<E>::generator();
+        // [P]_1 - G_1 ⋅ P(z) + [Q]_1 ⋅ z
+        let lhs = E::G1::msm_unchecked(
+            &[commitment.0, g1, *witness],
+            &[ScalarField::<E>::one(), -*y, z],
+        );
+        self.check_pairings(lhs, *witness)
+    }
+
+    /// Checks that `e(lhs, G_2) = e(rhs, [x]_2)` with a single multi-Miller loop
+    /// and one final exponentiation.
+    fn check_pairings(&self, lhs: E::G1, rhs: impl Into<E::G1>) -> bool {
+        let g1_points = E::G1::normalize_batch(&[-lhs, rhs.into()]);
+        E::multi_pairing(g1_points, [self.0.g2(), self.0.g2s()]).is_zero()
     }
 
     /// Aggregates multiple commitments into one commitment using a random challenge
@@ -185,11 +304,11 @@ impl KzgScheme {
     ///
     /// Aggregated commitment from input commitments
     pub fn aggregate_commitments(
-        commitments: &Vec<&KzgCommitment>,
-        challenge: &Fr,
-    ) -> KzgCommitment {
-        let mut pow = Fr::one();
-        let mut result = G1Projective::zero();
+        commitments: &Vec<&KzgCommitment<E>>,
+        challenge: &ScalarField<E>,
+    ) -> KzgCommitment<E> {
+        let mut pow = ScalarField::<E>::one();
+        let mut result = E::G1::zero();
 
         for commitment in commitments {
             result += &commitment.0.mul(pow);
@@ -202,6 +321,7 @@ impl KzgScheme {
     /// Verifies that each proof is a valid proof of evaluation for `commitment_i` at `point_i`.
     ///
     /// This function is implemented according to the protocol on page 13 of the Plonk paper,
+    /// with the powers of a challenge `r` derived from the commitments, points and openings.
     ///
     /// # Arguments
     ///
@@ -214,38 +334,632 @@ impl KzgScheme {
     /// `true` if all proofs is valid, otherwise `false`.
     pub fn batch_verify(
         &self,
-        commitments: &[KzgCommitment],
-        points: &[Fr],
-        openings: &[KzgOpening],
-        rng: &mut impl RngCore,
+        commitments: &[KzgCommitment<E>],
+        points: &[ScalarField<E>],
+        openings: &[KzgOpening<E>],
     ) -> bool {
-        assert_eq!(commitments.len(), points.len());
-        assert_eq!(openings.len(), points.len());
-
-        let g1 = G1Point::generator();
-        let mut e_1 = G1Point::zero();
-        let mut e_2 = G1Point::zero();
-
-        for ((cm, z), KzgOpening(w, s)) in commitments.iter().zip(points).zip(openings) {
-            // cm_i - s_i
-            let cm_minus_s = *cm.inner() - g1.mul(s).into_affine();
-            // z_i * w_i
-            let z_mul_w = *w * z;
-            // r'
-            let r_prime = Fr::from(rng.gen::<u128>());
-            // e_1 += r_prime^i * (cm_i - s_i + z_i * w_i)
-            e_1 = Affine::from(e_1 + (cm_minus_s + z_mul_w) * r_prime);
-
-            // e_2 += r_prime^i * w_i;
-            e_2 = Affine::from(e_2 + (*w * r_prime));
+        if commitments.len() != points.len() || openings.len() != points.len() {
+            return false;
         }
+        let mut transcript = Transcript::<E>::new(b"kzg-batch-verify");
+        transcript.append(commitments);
+        transcript.append(points);
+        transcript.append(openings);
+        let r = transcript.challenge();
+
+        let g1 = G1Point::<E>::generator();
+        let mut bases = Vec::with_capacity(2 * commitments.len() + 1);
+        let mut scalars = Vec::with_capacity(2 * commitments.len() + 1);
+        let mut witnesses = Vec::with_capacity(commitments.len());
+        let mut pows = Vec::with_capacity(commitments.len());
+        let mut evaluation = ScalarField::<E>::zero();
+        let mut pow = ScalarField::<E>::one();
+        for ((cm, z), KzgOpening(w, s, point)) in commitments.iter().zip(points).zip(openings) {
+            if point != z {
+                return false;
+            }
+            // e_1 += r^i * (cm_i - s_i + z_i * w_i)
+            bases.extend([cm.0, *w]);
+            scalars.extend([pow, pow * z]);
+            evaluation += pow * s;
+            // e_2 += r^i * w_i
+            witnesses.push(*w);
+            pows.push(pow);
+            pow *= r;
+        }
+        bases.push(g1);
+        scalars.push(-evaluation);
+        let e_1 = E::G1::msm_unchecked(&bases, &scalars);
+        let e_2 = E::G1::msm_unchecked(&witnesses, &pows);
 
         // check if e(e_1, [1]_2) = e(e_2, [x]_2)
-        Bls12_381::pairing(e_1, self.0.g2()) == Bls12_381::pairing(e_2, self.0.g2s())
+        self.check_pairings(e_1, e_2)
+    }
+}
+
+impl<E: Pairing> KzgScheme<E> {
+    /// Commits to a polynomial with a hiding commitment `[f(x)]_1 + [r(x)]_h`,
+    /// which reveals nothing about the polynomial as long as the blinder `r` is random.
+    ///
+    /// The blinder needs a degree at least equal to the number of points the commitment
+    /// will be opened at, and at most the maximum degree of the SRS.
+    ///
+    /// # Parameters
+    ///
+    /// - `polynomial`: The polynomial to be committed to.
+    /// - `blinder`: The random blinding polynomial.
+    ///
+    /// # Returns
+    ///
+    /// The hiding commitment to the polynomial, or an error if the SRS does not support
+    /// hiding commitments or the degree of the polynomial or the blinder exceeds the SRS.
+    pub fn commit_hiding(
+        &self,
+        polynomial: &Poly<E>,
+        blinder: &Poly<E>,
+    ) -> Result<KzgCommitment<E>, KzgError> {
+        let commitment =
+            self.evaluate_in_s(polynomial)?.into_group() + self.evaluate_in_h(blinder)?;
+        Ok(KzgCommitment(commitment.into_affine()))
+    }
+
+    /// Evaluates the blinder at the secret in G1, over the powers of the second generator.
+    fn evaluate_in_h(&self, blinder: &Poly<E>) -> Result<E::G1, KzgError> {
+        let h_points = self.0.h_points_slice();
+        if h_points.is_empty() {
+            return Err(KzgError::MissingHidingPowers);
+        }
+        check_degree(blinder, h_points)?;
+
+        let coeffs = &blinder.coeffs;
+        Ok(E::G1::msm_unchecked(&h_points[..coeffs.len()], coeffs))
+    }
+
+    /// Opens a hiding commitment at a specified point.
+    ///
+    /// # Parameters
+    ///
+    /// - `polynomial`: The polynomial to be opened.
+    /// - `blinder`: The blinding polynomial used in the commitment.
+    /// - `z`: The point at which the polynomial is opened.
+    ///
+    /// # Returns
+    ///
+    /// The opening at the specified point, with the blinded quotient and the evaluation
+    /// of the blinder, or an error if the SRS does not support hiding commitments
+    /// or the degree of the polynomial or the blinder exceeds the SRS.
+    pub fn open_hiding(
+        &self,
+        polynomial: &Poly<E>,
+        blinder: &Poly<E>,
+        z: impl Into<ScalarField<E>>,
+    ) -> Result<KzgHidingOpening<E>, KzgError> {
+        check_degree(polynomial, self.0.g1_points_slice())?;
+        let z = z.into();
+        let (quotient_poly, evaluation_at_z) = Self::quotient(polynomial, z);
+        let (blinder_quotient, blinder_at_z) = Self::quotient(blinder, z);
+        // fails first if the SRS does not support hiding commitments
+        let blinded_quotient = self.evaluate_in_h(&blinder_quotient)?;
+        check_degree(blinder, self.0.h_points_slice())?;
+        let opening = self.evaluate_in_s(&quotient_poly)?.into_group() + blinded_quotient;
+
+        Ok(KzgHidingOpening(
+            opening.into_affine(),
+            evaluation_at_z,
+            blinder_at_z,
+        ))
+    }
+
+    /// Verifies the correctness of an opening of a hiding commitment.
+    ///
+    /// # Parameters
+    ///
+    /// - `commitment`: The hiding commitment to be verified.
+    /// - `opening`: The opening to be verified.
+    /// - `z`: The point at which the polynomial was opened.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the opening is valid, otherwise `false`.
+    pub fn verify_hiding(
+        &self,
+        commitment: &KzgCommitment<E>,
+        opening: &KzgHidingOpening<E>,
+        z: impl Into<ScalarField<E>> + Debug + Display,
+    ) -> bool {
+        let Some(h) = self.0.h_points_slice().first() else {
+            return false;
+        };
+        let KzgHidingOpening(witness, y, blinder_y) = opening;
+        let g1 = G1Point::<E>::generator();
+        // [P]_1 + [R]_h - G_1 ⋅ P(z) - H ⋅ R(z) + ([Q]_1 + [Q_r]_h) ⋅ z
+        let lhs = E::G1::msm_unchecked(
+            &[commitment.0, g1, *h, *witness],
+            &[ScalarField::<E>::one(), -*y, -*blinder_y, z.into()],
+        );
+        self.check_pairings(lhs, *witness)
+    }
+
+    /// Opens many polynomials at a single point with one witness.
+    ///
+    /// The polynomials are combined as `sum_i v^i * f_i` with a challenge `v` derived from
+    /// the commitments, the point and the evaluations, and the combination is opened at `z`.
+    ///
+    /// # Parameters
+    ///
+    /// - `polynomials`: The polynomials to be opened.
+    /// - `commitments`: The commitments to the polynomials, bound to the challenge.
+    /// - `z`: The point at which the polynomials are opened.
+    ///
+    /// # Returns
+    ///
+    /// The opening, holding the evaluation of every polynomial at `z`, or an error
+    /// if the polynomials and commitments differ in number or a degree exceeds the SRS.
+    pub fn open_batch(
+        &self,
+        polynomials: &[Poly<E>],
+        commitments: &[KzgCommitment<E>],
+        z: impl Into<ScalarField<E>>,
+    ) -> Result<KzgBatchOpening<E>, KzgError> {
+        check_length(commitments.len(), polynomials.len())?;
+        let z = z.into();
+        let evaluations = polynomials
+            .iter()
+            .map(|polynomial| polynomial.evaluate(&z))
+            .collect::<Vec<_>>();
+        let v = Self::batch_transcript(commitments, z, &evaluations).challenge();
+
+        let mut combined = Poly::<E>::zero();
+        let mut pow = ScalarField::<E>::one();
+        for polynomial in polynomials {
+            combined += &(polynomial * pow);
+            pow *= v;
+        }
+        let KzgOpening(witness, _, _) = self.open(&combined, z)?;
+
+        Ok(KzgBatchOpening(witness, evaluations))
+    }
+
+    /// Verifies an opening produced by `KzgScheme::open_batch`.
+    ///
+    /// # Parameters
+    ///
+    /// - `commitments`: The commitments to the polynomials.
+    /// - `opening`: The opening to be verified, holding the claimed evaluations.
+    /// - `z`: The point at which the polynomials were opened.
+    ///
+    /// # Returns
+    ///
+    /// `true` if every claimed evaluation is valid, otherwise `false`.
+    pub fn verify_batch(
+        &self,
+        commitments: &[KzgCommitment<E>],
+        opening: &KzgBatchOpening<E>,
+        z: impl Into<ScalarField<E>> + Debug + Display,
+    ) -> bool {
+        let KzgBatchOpening(witness, evaluations) = opening;
+        if commitments.len() != evaluations.len() {
+            return false;
+        }
+        let z = z.into();
+        let v = Self::batch_transcript(commitments, z, evaluations).challenge();
+
+        let mut pow = ScalarField::<E>::one();
+        let mut scalars = Vec::with_capacity(commitments.len());
+        let mut evaluation = ScalarField::<E>::zero();
+        for y in evaluations {
+            scalars.push(pow);
+            evaluation += pow * y;
+            pow *= v;
+        }
+        let bases = commitments.iter().map(|c| c.0).collect::<Vec<_>>();
+        let commitment = KzgCommitment(E::G1::msm_unchecked(&bases, &scalars).into_affine());
+        self.verify(&commitment, &KzgOpening(*witness, evaluation, z), z)
+    }
+
+    /// Absorbs the commitments, point and evaluations of a batched opening into a new transcript.
+    fn batch_transcript(
+        commitments: &[KzgCommitment<E>],
+        z: ScalarField<E>,
+        evaluations: &[ScalarField<E>],
+    ) -> Transcript<E> {
+        let mut transcript = Transcript::new(b"kzg-batch-opening");
+        transcript.append(commitments);
+        transcript.append(&z);
+        transcript.append(evaluations);
+        transcript
+    }
+
+    /// Opens many polynomials, each at its own set of points, with a constant-size proof
+    /// following the BDFG20 (Shplonk) construction.
+    ///
+    /// With `T` the union of all points, `Z_S` the vanishing polynomial of a set `S`
+    /// and `r_i` the polynomial interpolating `f_i` over its points `S_i`,
+    /// the proof holds the commitment `W` to `h = sum_i gamma^i * (f_i - r_i) / Z_{S_i}`,
+    /// and the witness `W'` of `L = sum_i gamma^i * Z_{T \ S_i}(z) * (f_i - r_i(z)) - Z_T(z) * h`
+    /// vanishing at the challenge `z`.
+    ///
+    /// # Parameters
+    ///
+    /// - `polynomials`: The polynomials to be opened.
+    /// - `commitments`: The commitments to the polynomials, bound to the challenges.
+    /// - `points`: For each polynomial, the distinct points at which it is opened.
+    ///
+    /// # Returns
+    ///
+    /// The opening, and the evaluations of each polynomial at its points, or an error
+    /// if the inputs differ in number, a polynomial is opened twice at a point
+    /// or a degree exceeds the SRS.
+    #[allow(clippy::type_complexity)]
+    pub fn open_multi_point(
+        &self,
+        polynomials: &[Poly<E>],
+        commitments: &[KzgCommitment<E>],
+        points: &[Vec<ScalarField<E>>],
+    ) -> Result<(KzgMultiPointOpening<E>, Vec<Vec<ScalarField<E>>>), KzgError> {
+        check_length(points.len(), polynomials.len())?;
+        check_length(points.len(), commitments.len())?;
+        if points.iter().any(|points| has_duplicates(points)) {
+            return Err(KzgError::DuplicatePoints);
+        }
+        for polynomial in polynomials {
+            check_degree(polynomial, self.0.g1_points_slice())?;
+        }
+
+        let evaluations = polynomials
+            .iter()
+            .zip(points)
+            .map(|(polynomial, points)| points.iter().map(|x| polynomial.evaluate(x)).collect())
+            .collect::<Vec<Vec<_>>>();
+        let mut transcript = Self::multi_point_transcript(commitments, points, &evaluations);
+        let gamma = transcript.challenge();
+
+        // h = sum_i gamma^i * (f_i - r_i) / Z_{S_i}
+        let mut h = Poly::<E>::zero();
+        let mut pow = ScalarField::<E>::one();
+        for ((polynomial, points), evaluations) in polynomials.iter().zip(points).zip(&evaluations)
+        {
+            let numerator = polynomial - &interpolate::<E>(points, evaluations);
+            h += &(&(&numerator / &vanishing::<E>(points)) * pow);
+            pow *= gamma;
+        }
+        let w = self.evaluate_in_s(&h)?;
+        transcript.append(&w);
+        let z = transcript.challenge();
+
+        // L = sum_i gamma^i * Z_{T \ S_i}(z) * (f_i - r_i(z)) - Z_T(z) * h
+        let (factors, z_t) = Self::multi_point_factors(points, gamma, z);
+        let mut l = &h * -z_t;
+        for (((polynomial, points), evaluations), factor) in polynomials
+            .iter()
+            .zip(points)
+            .zip(&evaluations)
+            .zip(factors)
+        {
+            let mut term = polynomial.clone();
+            if term.coeffs.is_empty() {
+                term.coeffs.push(ScalarField::<E>::zero());
+            }
+            term.coeffs[0] -= interpolate_at(points, evaluations, z);
+            l += &(&term * factor);
+        }
+        let root = Poly::<E>::from_coefficients_slice(&[-z, ScalarField::<E>::one()]);
+        let w_prime = self.evaluate_in_s(&(&l / &root))?;
+
+        Ok((KzgMultiPointOpening(w, w_prime), evaluations))
+    }
+
+    /// Verifies a batched opening produced by `KzgScheme::open_multi_point`.
+    ///
+    /// The verifier rebuilds the commitment to `L` as
+    /// `sum_i gamma^i * Z_{T \ S_i}(z) * (C_i - [r_i(z)]_1) - Z_T(z) * W`
+    /// and checks that `e([L]_1 + z * W', G_2) = e(W', [x]_2)`.
+    ///
+    /// # Parameters
+    ///
+    /// - `commitments`: The commitments to the polynomials.
+    /// - `points`: For each polynomial, the distinct points at which it was opened.
+    /// - `evaluations`: For each polynomial, its claimed evaluations at its points.
+    /// - `opening`: The opening to be verified.
+    ///
+    /// # Returns
+    ///
+    /// `true` if every claimed evaluation is valid, otherwise `false`.
+    pub fn verify_multi_point(
+        &self,
+        commitments: &[KzgCommitment<E>],
+        points: &[Vec<ScalarField<E>>],
+        evaluations: &[Vec<ScalarField<E>>],
+        opening: &KzgMultiPointOpening<E>,
+    ) -> bool {
+        if commitments.len() != points.len()
+            || evaluations.len() != points.len()
+            || points
+                .iter()
+                .zip(evaluations)
+                .any(|(x, y)| x.len() != y.len())
+            || points.iter().any(|points| has_duplicates(points))
+        {
+            return false;
+        }
+        let KzgMultiPointOpening(w, w_prime) = opening;
+        let mut transcript = Self::multi_point_transcript(commitments, points, evaluations);
+        let gamma = transcript.challenge();
+        transcript.append(w);
+        let z = transcript.challenge();
+
+        let (factors, z_t) = Self::multi_point_factors(points, gamma, z);
+        let mut bases = commitments.iter().map(|c| c.0).collect::<Vec<_>>();
+        let mut scalars = factors.clone();
+        // the evaluations of all interpolations are gathered on the generator
+        let r = factors
+            .iter()
+            .zip(points.iter().zip(evaluations))
+            .map(|(factor, (points, evaluations))| *factor * interpolate_at(points, evaluations, z))
+            .sum::<ScalarField<E>>();
+        bases.extend([G1Point::<E>::generator(), *w, *w_prime]);
+        scalars.extend([-r, -z_t, z]);
+        let lhs = E::G1::msm_unchecked(&bases, &scalars);
+
+        self.check_pairings(lhs, *w_prime)
+    }
+
+    /// Absorbs the commitments, points and evaluations of a batched opening into a new transcript.
+    fn multi_point_transcript(
+        commitments: &[KzgCommitment<E>],
+        points: &[Vec<ScalarField<E>>],
+        evaluations: &[Vec<ScalarField<E>>],
+    ) -> Transcript<E> {
+        let mut transcript = Transcript::new(b"kzg-multi-point-opening");
+        transcript.append(commitments);
+        transcript.append(points);
+        transcript.append(evaluations);
+        transcript
+    }
+
+    /// Computes `gamma^i * Z_{T \ S_i}(z)` for every set of points `S_i`, and `Z_T(z)`.
+    fn multi_point_factors(
+        points: &[Vec<ScalarField<E>>],
+        gamma: ScalarField<E>,
+        z: ScalarField<E>,
+    ) -> (Vec<ScalarField<E>>, ScalarField<E>) {
+        let mut all_points = points.concat();
+        all_points.sort();
+        all_points.dedup();
+        let z_t = vanishing_at(&all_points, z);
+
+        let mut z_s = points
+            .iter()
+            .map(|points| vanishing_at(points, z))
+            .collect::<Vec<_>>();
+        batch_inversion(&mut z_s);
+        let mut pow = ScalarField::<E>::one();
+        let factors = z_s
+            .into_iter()
+            .map(|z_s_inv| {
+                let factor = pow * z_t * z_s_inv;
+                pow *= gamma;
+                factor
+            })
+            .collect();
+        (factors, z_t)
+    }
+}
+
+impl<E: Pairing> KzgScheme<E> {
+    /// Proves that a polynomial has degree below a bound, by committing to the polynomial
+    /// shifted by `X^(D + 1 - bound)` so that it fits the SRS only if the bound holds.
+    ///
+    /// # Parameters
+    ///
+    /// - `polynomial`: The committed polynomial.
+    /// - `bound`: The bound, strictly greater than the degree of the polynomial.
+    ///
+    /// # Returns
+    ///
+    /// The proof, or an error if the SRS has no powers in G2, or the degree of the polynomial
+    /// is not below the bound or exceeds the SRS.
+    pub fn open_degree_bound(
+        &self,
+        polynomial: &Poly<E>,
+        bound: usize,
+    ) -> Result<KzgDegreeProof<E>, KzgError> {
+        let shift = self.degree_shift(bound).ok_or(KzgError::MissingG2Powers)?;
+        if polynomial.degree() >= bound {
+            return Err(KzgError::DegreeBoundExceeded {
+                degree: polynomial.degree(),
+                bound,
+            });
+        }
+        let mut coeffs = vec![ScalarField::<E>::zero(); shift];
+        coeffs.extend(&polynomial.coeffs);
+        let shifted = self.evaluate_in_s(&Poly::<E>::from_coefficients_vec(coeffs))?;
+        Ok(KzgDegreeProof(shifted))
+    }
+
+    /// Verifies a proof produced by `KzgScheme::open_degree_bound`, checking
+    /// that `e(C, [x^(D + 1 - bound)]_2) = e(W, G_2)`.
+    ///
+    /// # Parameters
+    ///
+    /// - `commitment`: The commitment to the polynomial.
+    /// - `proof`: The proof to be verified.
+    /// - `bound`: The claimed bound on the degree.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the polynomial has degree below the bound, otherwise `false`.
+    pub fn verify_degree_bound(
+        &self,
+        commitment: &KzgCommitment<E>,
+        proof: &KzgDegreeProof<E>,
+        bound: usize,
+    ) -> bool {
+        let Some(g2_shifted) = self
+            .degree_shift(bound)
+            .and_then(|shift| self.0.g2_points_slice().get(shift))
+        else {
+            return false;
+        };
+        let g1_points = [commitment.0, (-proof.0.into_group()).into_affine()];
+        let g2_points = [*g2_shifted, self.0.g2()];
+        E::multi_pairing(g1_points, g2_points).is_zero()
+    }
+
+    /// Computes the power of `X` shifting a polynomial of degree below `bound` up to
+    /// the maximum degree of the SRS, or `None` if the SRS has no powers in G2.
+    fn degree_shift(&self, bound: usize) -> Option<usize> {
+        let g2_points = self.0.g2_points_slice();
+        if g2_points.is_empty() {
+            return None;
+        }
+        Some(g2_points.len().saturating_sub(bound))
+    }
+}
+
+/// Checks that the SRS holds enough points to commit to the polynomial.
+fn check_degree<F: Field, P>(
+    polynomial: &DensePolynomial<F>,
+    points: &[P],
+) -> Result<(), KzgError> {
+    if points.len() <= polynomial.degree() {
+        return Err(KzgError::DegreeTooLarge {
+            degree: polynomial.degree(),
+            max_degree: points.len().saturating_sub(1),
+        });
+    }
+    Ok(())
+}
+
+/// Checks that inputs which go together have the same length.
+fn check_length(expected: usize, found: usize) -> Result<(), KzgError> {
+    if expected != found {
+        return Err(KzgError::LengthMismatch { expected, found });
+    }
+    Ok(())
+}
+
+/// Checks whether some points appear more than once.
+fn has_duplicates<F: Field>(points: &[F]) -> bool {
+    let mut sorted = points.to_vec();
+    sorted.sort();
+    sorted.windows(2).any(|pair| pair[0] == pair[1])
+}
+
+/// Computes the vanishing polynomial of `points`.
+fn vanishing<E: Pairing>(points: &[ScalarField<E>]) -> Poly<E> {
+    points.iter().fold(
+        Poly::<E>::from_coefficients_slice(&[ScalarField::<E>::one()]),
+        |acc, x| {
+            acc.naive_mul(&Poly::<E>::from_coefficients_slice(&[
+                -*x,
+                ScalarField::<E>::one(),
+            ]))
+        },
+    )
+}
+
+/// Evaluates the vanishing polynomial of `points` at `z`.
+fn vanishing_at<F: Field>(points: &[F], z: F) -> F {
+    points.iter().map(|x| z - x).product()
+}
+
+/// Computes the polynomial of lowest degree taking the values `evaluations` at `points`.
+fn interpolate<E: Pairing>(points: &[ScalarField<E>], evaluations: &[ScalarField<E>]) -> Poly<E> {
+    let mut result = Poly::<E>::zero();
+    for (i, (x_i, y_i)) in points.iter().zip(evaluations).enumerate() {
+        let others = [&points[..i], &points[i + 1..]].concat();
+        let denominator = vanishing_at(&others, *x_i);
+        let scale = *y_i * denominator.inverse().expect("points must be distinct");
+        result += &(&vanishing::<E>(&others) * scale);
+    }
+    result
+}
+
+/// Evaluates at `z` the polynomial of lowest degree taking the values `evaluations` at `points`.
+fn interpolate_at<F: Field>(points: &[F], evaluations: &[F], z: F) -> F {
+    points
+        .iter()
+        .zip(evaluations)
+        .enumerate()
+        .map(|(i, (x_i, y_i))| {
+            let (numerator, denominator) = points
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .fold((F::one(), F::one()), |(n, d), (_, x_j)| {
+                    (n * (z - x_j), d * (*x_i - x_j))
+                });
+            *y_i * numerator * denominator.inverse().expect("points must be distinct")
+        })
+        .sum()
+}
+
+impl<E: Pairing> PolynomialCommitmentScheme for KzgScheme<E> {
+    type Field = ScalarField<E>;
+    type Params = Srs<E>;
+    type Commitment = KzgCommitment<E>;
+    type Opening = KzgOpening<E>;
+    type BatchOpening = KzgBatchOpening<E>;
+    type Error = KzgError;
+
+    fn setup(max_degree: usize) -> Srs<E> {
+        Srs::new(max_degree)
+    }
+
+    fn new(params: Srs<E>) -> Self {
+        Self(params)
+    }
+
+    fn max_degree(&self) -> usize {
+        self.0.max_degree()
+    }
+
+    fn commit(&self, polynomial: &Poly<E>) -> Result<KzgCommitment<E>, KzgError> {
+        Self::commit(self, polynomial)
+    }
+
+    fn open(&self, polynomial: &Poly<E>, z: ScalarField<E>) -> Result<KzgOpening<E>, KzgError> {
+        Self::open(self, polynomial, z)
+    }
+
+    fn verify(
+        &self,
+        commitment: &KzgCommitment<E>,
+        opening: &KzgOpening<E>,
+        z: ScalarField<E>,
+    ) -> bool {
+        Self::verify(self, commitment, opening, z)
+    }
+
+    fn evaluation(opening: &KzgOpening<E>) -> ScalarField<E> {
+        opening.1
+    }
+
+    fn batch_open(
+        &self,
+        polynomials: &[Poly<E>],
+        commitments: &[KzgCommitment<E>],
+        z: ScalarField<E>,
+    ) -> Result<KzgBatchOpening<E>, KzgError> {
+        self.open_batch(polynomials, commitments, z)
+    }
+
+    fn batch_verify(
+        &self,
+        commitments: &[KzgCommitment<E>],
+        opening: &KzgBatchOpening<E>,
+        z: ScalarField<E>,
+    ) -> bool {
+        self.verify_batch(commitments, opening, z)
+    }
+
+    fn batch_evaluations(opening: &KzgBatchOpening<E>) -> Vec<ScalarField<E>> {
+        opening.evals().to_vec()
     }
 }
 
-impl Add for KzgCommitment {
+impl<E: Pairing> Add for KzgCommitment<E> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
@@ -254,7 +968,7 @@ impl Add for KzgCommitment {
     }
 }
 
-impl Sub for KzgCommitment {
+impl<E: Pairing> Sub for KzgCommitment<E> {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
@@ -262,29 +976,29 @@ impl Sub for KzgCommitment {
     }
 }
 
-impl Mul<Fr> for KzgCommitment {
+impl<E: Pairing> Mul<ScalarField<E>> for KzgCommitment<E> {
     type Output = Self;
 
-    fn mul(self, rhs: Fr) -> Self::Output {
+    fn mul(self, rhs: ScalarField<E>) -> Self::Output {
         let element = self.0.mul(rhs);
         Self(element.into())
     }
 }
 
-impl Mul<Fr> for &KzgCommitment {
-    type Output = KzgCommitment;
+impl<E: Pairing> Mul<ScalarField<E>> for &KzgCommitment<E> {
+    type Output = KzgCommitment<E>;
 
-    fn mul(self, rhs: Fr) -> Self::Output {
+    fn mul(self, rhs: ScalarField<E>) -> Self::Output {
         let element = self.0.mul(rhs);
         KzgCommitment(element.into())
     }
 }
 
-impl Neg for KzgCommitment {
+impl<E: Pairing> Neg for KzgCommitment<E> {
     type Output = Self;
 
     fn neg(self) -> Self::Output {
-        let point = self.0;
-        Self(-point)
+        let point = self.0.into_group();
+        Self((-point).into_affine())
     }
 }
@@ -1,26 +1,56 @@
+use std::io::{Read, Write};
 use std::ops::Mul;
 
-use ark_bls12_381::Fr;
+use ark_ec::pairing::Pairing;
+use ark_ec::short_weierstrass::{Affine, SWCurveConfig};
 use ark_ec::{AffineRepr, CurveGroup};
-use ark_ff::{One, UniformRand};
+use ark_ff::{BigInteger, Field, One, PrimeField, UniformRand, Zero};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Validate,
+};
+use ark_std::cfg_iter;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
-use crate::types::{G1Point, G2Point};
+use crate::error::KzgError;
+use crate::ptau::read_ptau;
+use crate::types::{BaseField, G1Point, G2Point, ScalarField};
+
+/// Magic bytes at the start of every SRS file.
+const SRS_FILE_MAGIC: &[u8; 4] = b"KZGS";
+/// Version of the SRS file format.
+///
+/// Version 1 holds the powers in G1, `g2` and `g2s`. Version 2 appends the optional hiding powers,
+/// Lagrange basis points and powers in G2, each preceded by a presence flag.
+/// The version must be bumped whenever the layout of the file changes.
+const SRS_FILE_VERSION: u32 = 2;
 
 /// Structured Reference String (SRS) used in the KZG scheme.
 ///
 /// The `Srs` struct represents the structured reference string used in the KZG scheme,
 /// containing precomputed values necessary for commitment and verification.
-#[derive(Debug, Clone, PartialEq)]
-pub struct Srs {
+/// It is generic over the pairing-friendly curve `E` the points live on.
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Srs<E: Pairing> {
     /// Points in G1, each equals to generator point multiplied by the secret's powers.
-    g1_points: Vec<G1Point>,
+    g1_points: Vec<G1Point<E>>,
     /// Generator point in G2.
-    g2: G2Point,
+    g2: G2Point<E>,
     /// Generator point in G2 multiplied by the secret.
-    g2s_point: G2Point,
+    g2s_point: G2Point<E>,
+    /// Points in G1, each equals to a second generator point `h`, of unknown discrete logarithm,
+    /// multiplied by the secret's powers. Empty unless the SRS supports hiding commitments.
+    h_points: Vec<G1Point<E>>,
+    /// Points in G1, each equals to generator point multiplied by a Lagrange basis polynomial
+    /// of a radix-2 domain evaluated at the secret. Empty unless precomputed.
+    lagrange_points: Vec<G1Point<E>>,
+    /// Points in G2, each equals to generator point multiplied by the secret's powers.
+    /// Empty unless the SRS supports degree-bound proofs.
+    g2_points: Vec<G2Point<E>>,
 }
 
-impl Srs {
+impl<E: Pairing> Srs<E> {
     /// Generates a new SRS with a random secret and the specified circuit size.
     ///
     /// # Parameters
@@ -31,7 +61,7 @@ impl Srs {
     ///
     /// A new `Srs` instance.
     pub fn new(circuit_size: usize) -> Self {
-        let s = Fr::rand(&mut rand::thread_rng());
+        let s = ScalarField::<E>::rand(&mut rand::thread_rng());
         Self::new_from_secret(s, circuit_size)
     }
 
@@ -45,46 +75,375 @@ impl Srs {
     /// # Returns
     ///
     /// A new `Srs` instance.
-    pub fn new_from_secret(secret: Fr, circuit_size: usize) -> Self {
-        let g1 = G1Point::generator();
+    pub fn new_from_secret(secret: ScalarField<E>, circuit_size: usize) -> Self {
+        let g1 = G1Point::<E>::generator();
 
-        let g1_points = vec![Fr::one(); circuit_size + 3];
-        let mut cur = Fr::one();
-        let g1_points = g1_points
-            .into_iter()
+        let mut cur = ScalarField::<E>::one();
+        let powers = (0..circuit_size + 3)
             .map(|_| {
-                let res = g1.mul(cur).into_affine();
+                let res = cur;
                 cur *= secret;
                 res
             })
             .collect::<Vec<_>>();
+        let g1_points = cfg_iter!(powers)
+            .map(|power| g1.mul(power))
+            .collect::<Vec<_>>();
+        let g1_points = E::G1::normalize_batch(&g1_points);
 
-        let g2 = G2Point::generator();
+        let g2 = G2Point::<E>::generator();
         let g2s_point = g2.mul(secret).into();
+        Self::from_points(g1_points, g2, g2s_point)
+    }
+
+    /// Generates a new SRS supporting hiding commitments, with random secrets
+    /// and the specified circuit size.
+    ///
+    /// # Parameters
+    ///
+    /// - `circuit_size`: The size of the circuit.
+    ///
+    /// # Returns
+    ///
+    /// A new `Srs` instance.
+    pub fn new_hiding(circuit_size: usize) -> Self {
+        let rng = &mut rand::thread_rng();
+        let s = ScalarField::<E>::rand(rng);
+        let gamma = ScalarField::<E>::rand(rng);
+        Self::new_hiding_from_secrets(s, gamma, circuit_size)
+    }
+
+    /// Generates a new SRS supporting hiding commitments, with the provided secrets
+    /// and the specified circuit size.
+    ///
+    /// The second generator is `h = gamma * G_1`, so `gamma` must be discarded
+    /// along with the secret for the commitments to be hiding and binding.
+    ///
+    /// # Parameters
+    ///
+    /// - `secret`: The secret used for generating the SRS.
+    /// - `gamma`: The discrete logarithm of the second generator.
+    /// - `circuit_size`: The size of the circuit.
+    ///
+    /// # Returns
+    ///
+    /// A new `Srs` instance.
+    pub fn new_hiding_from_secrets(
+        secret: ScalarField<E>,
+        gamma: ScalarField<E>,
+        circuit_size: usize,
+    ) -> Self {
+        let mut srs = Self::new_from_secret(secret, circuit_size);
+        let h_points = cfg_iter!(srs.g1_points)
+            .map(|point| point.mul(gamma))
+            .collect::<Vec<_>>();
+        srs.h_points = E::G1::normalize_batch(&h_points);
+        srs
+    }
+
+    /// Generates a new SRS supporting degree-bound proofs, with a random secret
+    /// and the specified circuit size.
+    ///
+    /// # Parameters
+    ///
+    /// - `circuit_size`: The size of the circuit.
+    ///
+    /// # Returns
+    ///
+    /// A new `Srs` instance.
+    pub fn new_with_g2_powers(circuit_size: usize) -> Self {
+        let s = ScalarField::<E>::rand(&mut rand::thread_rng());
+        Self::new_with_g2_powers_from_secret(s, circuit_size)
+    }
+
+    /// Generates a new SRS supporting degree-bound proofs, with the provided secret
+    /// and the specified circuit size.
+    ///
+    /// Besides the powers in G1, the SRS holds as many powers of the secret in G2.
+    ///
+    /// # Parameters
+    ///
+    /// - `secret`: The secret used for generating the SRS.
+    /// - `circuit_size`: The size of the circuit.
+    ///
+    /// # Returns
+    ///
+    /// A new `Srs` instance.
+    pub fn new_with_g2_powers_from_secret(secret: ScalarField<E>, circuit_size: usize) -> Self {
+        let mut srs = Self::new_from_secret(secret, circuit_size);
+        let g2 = srs.g2;
+        let mut cur = ScalarField::<E>::one();
+        let powers = (0..srs.g1_points.len())
+            .map(|_| {
+                let res = cur;
+                cur *= secret;
+                res
+            })
+            .collect::<Vec<_>>();
+        let g2_points = cfg_iter!(powers)
+            .map(|power| g2.mul(power))
+            .collect::<Vec<_>>();
+        srs.g2_points = E::G2::normalize_batch(&g2_points);
+        srs
+    }
+
+    /// Loads an SRS for the specified circuit size from a Powers-of-Tau ceremony transcript
+    /// in the `.ptau` format, keeping only the powers the circuit needs.
+    ///
+    /// The transcript must start from the standard generators, and its G1 and G2 powers
+    /// are checked for consistency with pairings.
+    ///
+    /// # Parameters
+    ///
+    /// - `reader`: Source of the `.ptau` transcript.
+    /// - `circuit_size`: The size of the circuit.
+    ///
+    /// # Returns
+    ///
+    /// A new `Srs` instance, or an error if the transcript is malformed, belongs to another
+    /// curve, is too small for the circuit or holds inconsistent powers.
+    pub fn from_ptau<R, P1, P2>(reader: R, circuit_size: usize) -> Result<Self, KzgError>
+    where
+        R: Read,
+        P1: SWCurveConfig,
+        P2: SWCurveConfig,
+        E: Pairing<G1Affine = Affine<P1>, G2Affine = Affine<P2>>,
+    {
+        let (g1_points, g2_points) = read_ptau::<R, P1, P2>(reader, circuit_size + 3, 2)?;
+        let (g2, g2s_point) = (g2_points[0], g2_points[1]);
+        if g1_points[0] != G1Point::<E>::generator() || g2 != G2Point::<E>::generator() {
+            return Err(KzgError::MalformedSrs(
+                "the first points are not the standard generators",
+            ));
+        }
+        if !Self::powers_are_consistent(&g1_points, g2, g2s_point) {
+            return Err(KzgError::MalformedSrs("the powers are inconsistent"));
+        }
+        Ok(Self::from_points(g1_points, g2, g2s_point))
+    }
+
+    /// Builds an SRS from already computed points, without any check.
+    pub(crate) fn from_points(
+        g1_points: Vec<G1Point<E>>,
+        g2: G2Point<E>,
+        g2s_point: G2Point<E>,
+    ) -> Self {
         Self {
             g1_points,
             g2,
             g2s_point,
+            h_points: vec![],
+            lagrange_points: vec![],
+            g2_points: vec![],
+        }
+    }
+
+    /// Precomputes the Lagrange basis points of the smallest radix-2 domain holding `domain_size`
+    /// elements, with an inverse FFT over the G1 points.
+    ///
+    /// # Parameters
+    ///
+    /// - `domain_size`: The minimum number of evaluations to commit to.
+    ///
+    /// # Returns
+    ///
+    /// The SRS with the Lagrange basis points, `KzgError::DomainTooLarge` if the field has no
+    /// such domain or `KzgError::SrsTooSmall` if the SRS holds fewer powers than the domain size.
+    pub fn with_lagrange_basis(mut self, domain_size: usize) -> Result<Self, KzgError> {
+        let domain = Radix2EvaluationDomain::<ScalarField<E>>::new(domain_size)
+            .ok_or(KzgError::DomainTooLarge { size: domain_size })?;
+        if domain.size() > self.g1_points.len() {
+            return Err(KzgError::SrsTooSmall {
+                powers: self.g1_points.len(),
+                needed: domain.size(),
+            });
+        }
+        let powers = self.g1_points[..domain.size()]
+            .iter()
+            .map(|point| point.into_group())
+            .collect::<Vec<_>>();
+        // [L_i(s)]_1 = 1/n * sum_j w^(-ij) * [s^j]_1
+        self.lagrange_points = E::G1::normalize_batch(&domain.ifft(&powers));
+        Ok(self)
+    }
+
+    /// Derives a smaller SRS for the specified circuit size, keeping the leading powers.
+    ///
+    /// Precomputed Lagrange basis points are discarded, as their domain depends on the size;
+    /// call `Srs::with_lagrange_basis` on the result to precompute them again.
+    ///
+    /// # Parameters
+    ///
+    /// - `circuit_size`: The size of the circuit.
+    ///
+    /// # Returns
+    ///
+    /// A new `Srs` instance sharing the secret, or `KzgError::SrsTooSmall` if the SRS is too small
+    /// for the circuit.
+    pub fn sub_srs(&self, circuit_size: usize) -> Result<Self, KzgError> {
+        let size = circuit_size + 3;
+        if size > self.g1_points.len() {
+            return Err(KzgError::SrsTooSmall {
+                powers: self.g1_points.len(),
+                needed: size,
+            });
+        }
+        let mut srs = Self::from_points(self.g1_points[..size].to_vec(), self.g2, self.g2s_point);
+        if !self.h_points.is_empty() {
+            srs.h_points = self.h_points[..size].to_vec();
+        }
+        if !self.g2_points.is_empty() {
+            srs.g2_points = self.g2_points[..size].to_vec();
+        }
+        Ok(srs)
+    }
+
+    /// Checks that the SRS is well-formed: it starts from the standard generators, consecutive
+    /// powers in G1 satisfy the pairing relation with `g2s`, and the optional powers of the second
+    /// generator and Lagrange basis points are consistent with them.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the SRS is well-formed, otherwise `KzgError::MalformedSrs` describing the failure.
+    pub fn verify(&self) -> Result<(), KzgError> {
+        if self.g1_points.is_empty() {
+            return Err(KzgError::EmptySrs);
+        }
+        if self.g1_points[0] != G1Point::<E>::generator() || self.g2 != G2Point::<E>::generator() {
+            return Err(KzgError::MalformedSrs(
+                "the first points are not the standard generators",
+            ));
+        }
+        if self.g2s_point.is_zero() {
+            return Err(KzgError::MalformedSrs("the secret is zero"));
+        }
+        if !Self::powers_are_consistent(&self.g1_points, self.g2, self.g2s_point) {
+            return Err(KzgError::MalformedSrs("the powers are inconsistent"));
+        }
+        if !self.h_points.is_empty() {
+            if self.h_points.len() != self.g1_points.len() || self.h_points[0].is_zero() {
+                return Err(KzgError::MalformedSrs(
+                    "the hiding powers have the wrong length",
+                ));
+            }
+            if !Self::powers_are_consistent(&self.h_points, self.g2, self.g2s_point) {
+                return Err(KzgError::MalformedSrs("the hiding powers are inconsistent"));
+            }
+        }
+        if !self.g2_points.is_empty() {
+            if self.g2_points.len() != self.g1_points.len()
+                || self.g2_points[0] != self.g2
+                || self
+                    .g2_points
+                    .get(1)
+                    .is_some_and(|point| *point != self.g2s_point)
+            {
+                return Err(KzgError::MalformedSrs(
+                    "the powers in G2 do not match the generators",
+                ));
+            }
+            if self.g1_points.len() > 1
+                && !Self::g2_powers_are_consistent(
+                    &self.g2_points,
+                    self.g1_points[0],
+                    self.g1_points[1],
+                )
+            {
+                return Err(KzgError::MalformedSrs("the powers in G2 are inconsistent"));
+            }
+        }
+        if !self.lagrange_points.is_empty() {
+            let lagrange_points = self.lagrange_points.clone();
+            let expected = Self::from_points(self.g1_points.clone(), self.g2, self.g2s_point)
+                .with_lagrange_basis(lagrange_points.len())
+                .map_err(|_| {
+                    KzgError::MalformedSrs("the Lagrange basis points have no matching domain")
+                })?;
+            if expected.lagrange_points != lagrange_points {
+                return Err(KzgError::MalformedSrs(
+                    "the Lagrange basis points are inconsistent",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Rerandomizes the SRS in place, so that its secret becomes the old secret multiplied by `secret`.
+    ///
+    /// Precomputed Lagrange basis points are discarded, as they no longer match the secret.
+    pub(crate) fn rerandomize(&mut self, secret: ScalarField<E>) {
+        self.lagrange_points.clear();
+        for points in [&mut self.g1_points, &mut self.h_points] {
+            let mut cur = ScalarField::<E>::one();
+            for point in points.iter_mut() {
+                *point = point.mul(cur).into_affine();
+                cur *= secret;
+            }
+        }
+        self.g2s_point = self.g2s_point.mul(secret).into_affine();
+        let mut cur = ScalarField::<E>::one();
+        for point in self.g2_points.iter_mut() {
+            *point = point.mul(cur).into_affine();
+            cur *= secret;
+        }
+    }
+
+    /// Checks that every G1 point is the previous one multiplied by the secret behind `g2s`,
+    /// i.e. e(g1_points[i + 1], g2) = e(g1_points[i], g2s), batched with a random linear combination.
+    pub(crate) fn powers_are_consistent(
+        g1_points: &[G1Point<E>],
+        g2: G2Point<E>,
+        g2s: G2Point<E>,
+    ) -> bool {
+        let r = ScalarField::<E>::rand(&mut rand::thread_rng());
+        let mut cur = ScalarField::<E>::one();
+        let mut lhs = E::G1::zero();
+        let mut rhs = E::G1::zero();
+        for pair in g1_points.windows(2) {
+            lhs += pair[1].mul(cur);
+            rhs += pair[0].mul(cur);
+            cur *= r;
+        }
+        E::pairing(lhs, g2) == E::pairing(rhs, g2s)
+    }
+
+    /// Checks that every G2 point is the previous one multiplied by the secret behind `g1s`,
+    /// i.e. e(g1, g2_points[i + 1]) = e(g1s, g2_points[i]), batched with a random linear combination.
+    fn g2_powers_are_consistent(g2_points: &[G2Point<E>], g1: G1Point<E>, g1s: G1Point<E>) -> bool {
+        let r = ScalarField::<E>::rand(&mut rand::thread_rng());
+        let mut cur = ScalarField::<E>::one();
+        let mut lhs = E::G2::zero();
+        let mut rhs = E::G2::zero();
+        for pair in g2_points.windows(2) {
+            lhs += pair[1].mul(cur);
+            rhs += pair[0].mul(cur);
+            cur *= r;
         }
+        E::pairing(g1, lhs) == E::pairing(g1s, rhs)
     }
 }
 
-impl Srs {
+impl<E: Pairing> Srs<E> {
     /// Returns the precomputed points in G1.
     ///
     /// # Returns
     ///
     /// A vector containing points in G1.
-    pub fn g1_points(&self) -> Vec<G1Point> {
+    pub fn g1_points(&self) -> Vec<G1Point<E>> {
         self.g1_points.clone()
     }
 
+    /// Borrows the precomputed points in G1, without cloning them.
+    pub(crate) fn g1_points_slice(&self) -> &[G1Point<E>] {
+        &self.g1_points
+    }
+
     /// Returns the generator point in G2.
     ///
     /// # Returns
     ///
     /// The generator point in G2.
-    pub fn g2(&self) -> G2Point {
+    pub fn g2(&self) -> G2Point<E> {
         self.g2
     }
 
@@ -93,7 +452,485 @@ impl Srs {
     /// # Returns
     ///
     /// The generator point in G2 multiplied by the secret.
-    pub fn g2s(&self) -> G2Point {
+    pub fn g2s(&self) -> G2Point<E> {
         self.g2s_point
     }
+
+    /// Returns the powers of the secret in G2, used by degree-bound proofs.
+    ///
+    /// # Returns
+    ///
+    /// A vector containing points in G2, empty unless the SRS supports degree-bound proofs.
+    pub fn g2_points(&self) -> Vec<G2Point<E>> {
+        self.g2_points.clone()
+    }
+
+    /// Borrows the powers of the secret in G2, without cloning them.
+    pub(crate) fn g2_points_slice(&self) -> &[G2Point<E>] {
+        &self.g2_points
+    }
+
+    /// Returns the powers of the second generator in G1, used by hiding commitments.
+    ///
+    /// # Returns
+    ///
+    /// A vector containing points in G1, empty unless the SRS supports hiding commitments.
+    pub fn h_points(&self) -> Vec<G1Point<E>> {
+        self.h_points.clone()
+    }
+
+    /// Borrows the powers of the second generator in G1, without cloning them.
+    pub(crate) fn h_points_slice(&self) -> &[G1Point<E>] {
+        &self.h_points
+    }
+
+    /// Returns the precomputed Lagrange basis points in G1.
+    ///
+    /// # Returns
+    ///
+    /// A vector containing points in G1, empty unless `Srs::with_lagrange_basis` was called.
+    pub fn lagrange_points(&self) -> Vec<G1Point<E>> {
+        self.lagrange_points.clone()
+    }
+
+    /// Borrows the precomputed Lagrange basis points in G1, without cloning them.
+    pub(crate) fn lagrange_points_slice(&self) -> &[G1Point<E>] {
+        &self.lagrange_points
+    }
+
+    /// Returns the radix-2 domain of the precomputed Lagrange basis points.
+    ///
+    /// # Returns
+    ///
+    /// The evaluation domain, or `None` if no Lagrange basis points were precomputed.
+    pub fn lagrange_domain(&self) -> Option<Radix2EvaluationDomain<ScalarField<E>>> {
+        match self.lagrange_points.is_empty() {
+            true => None,
+            false => Radix2EvaluationDomain::new(self.lagrange_points.len()),
+        }
+    }
+
+    /// Returns the maximum degree of the polynomials the SRS can commit to.
+    ///
+    /// # Returns
+    ///
+    /// The maximum degree, one less than the number of points in G1.
+    pub fn max_degree(&self) -> usize {
+        self.g1_points.len().saturating_sub(1)
+    }
+}
+
+impl<E: Pairing> Srs<E> {
+    /// Writes the SRS in the versioned SRS file format.
+    ///
+    /// The file starts with a magic, the format version, an identifier of the curve
+    /// made of its base and scalar field moduli, and the maximum degree,
+    /// followed by the points in the requested encoding: the powers in G1, `g2`, `g2s`,
+    /// then the hiding powers, the Lagrange basis points and the powers in G2,
+    /// each preceded by a flag telling whether the SRS holds them.
+    ///
+    /// # Parameters
+    ///
+    /// - `writer`: Destination of the SRS file.
+    /// - `compress`: Whether the points are written compressed.
+    pub fn write<W: Write>(&self, mut writer: W, compress: Compress) -> Result<(), KzgError> {
+        let header = (
+            SRS_FILE_VERSION,
+            Self::curve_id(),
+            self.max_degree() as u64,
+            compress == Compress::Yes,
+        );
+        writer
+            .write_all(SRS_FILE_MAGIC)
+            .map_err(|e| e.into())
+            .and_then(|_| header.serialize_uncompressed(&mut writer))
+            .and_then(|_| self.g1_points.serialize_with_mode(&mut writer, compress))
+            .and_then(|_| self.g2.serialize_with_mode(&mut writer, compress))
+            .and_then(|_| self.g2s_point.serialize_with_mode(&mut writer, compress))
+            .and_then(|_| write_section(&mut writer, &self.h_points, compress))
+            .and_then(|_| write_section(&mut writer, &self.lagrange_points, compress))
+            .and_then(|_| write_section(&mut writer, &self.g2_points, compress))
+            .map_err(|e| KzgError::Serialization(e.to_string()))
+    }
+
+    /// Reads an SRS written by `Srs::write`, in the current or an earlier version of the format,
+    /// checking that every point is on the curve and in the prime-order subgroup,
+    /// and that the SRS is well-formed with `Srs::verify`.
+    ///
+    /// # Parameters
+    ///
+    /// - `reader`: Source of the SRS file.
+    ///
+    /// # Returns
+    ///
+    /// The SRS, or an error if the file is malformed, has an unsupported version,
+    /// was generated for another curve or holds an ill-formed SRS.
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, KzgError> {
+        let read_error = |e: SerializationError| KzgError::Serialization(e.to_string());
+        let mut magic = [0u8; 4];
+        reader
+            .read_exact(&mut magic)
+            .map_err(|e| read_error(e.into()))?;
+        if &magic != SRS_FILE_MAGIC {
+            return Err(KzgError::MalformedFile("not an SRS file"));
+        }
+        let version = u32::deserialize_uncompressed(&mut reader).map_err(read_error)?;
+        if version == 0 || version > SRS_FILE_VERSION {
+            return Err(KzgError::UnsupportedVersion(version));
+        }
+        let curve_id = Vec::<u8>::deserialize_uncompressed(&mut reader).map_err(read_error)?;
+        if curve_id != Self::curve_id() {
+            return Err(KzgError::CurveMismatch);
+        }
+        let max_degree = u64::deserialize_uncompressed(&mut reader).map_err(read_error)?;
+        let compress = match bool::deserialize_uncompressed(&mut reader).map_err(read_error)? {
+            true => Compress::Yes,
+            false => Compress::No,
+        };
+        let g1_points =
+            Vec::<G1Point<E>>::deserialize_with_mode(&mut reader, compress, Validate::Yes)
+                .map_err(read_error)?;
+        let g2 = G2Point::<E>::deserialize_with_mode(&mut reader, compress, Validate::Yes)
+            .map_err(read_error)?;
+        let g2s_point = G2Point::<E>::deserialize_with_mode(&mut reader, compress, Validate::Yes)
+            .map_err(read_error)?;
+        let mut srs = Self::from_points(g1_points, g2, g2s_point);
+        // the optional sections were introduced in version 2
+        if version >= 2 {
+            srs.h_points = read_section(&mut reader, compress).map_err(read_error)?;
+            srs.lagrange_points = read_section(&mut reader, compress).map_err(read_error)?;
+            srs.g2_points = read_section(&mut reader, compress).map_err(read_error)?;
+        }
+        if srs.g1_points.is_empty() || srs.max_degree() as u64 != max_degree {
+            return Err(KzgError::MalformedFile(
+                "the SRS does not match its maximum degree",
+            ));
+        }
+        srs.verify()?;
+        Ok(srs)
+    }
+
+    /// Identifies the curve by the moduli of its base and scalar fields.
+    fn curve_id() -> Vec<u8> {
+        let mut id = <BaseField<E> as Field>::BasePrimeField::MODULUS.to_bytes_le();
+        id.extend(ScalarField::<E>::MODULUS.to_bytes_le());
+        id
+    }
+}
+
+/// Writes an optional section of the SRS file, a presence flag followed by the points if any.
+fn write_section<W: Write, P: CanonicalSerialize>(
+    mut writer: W,
+    points: &Vec<P>,
+    compress: Compress,
+) -> Result<(), SerializationError> {
+    (!points.is_empty()).serialize_uncompressed(&mut writer)?;
+    match points.is_empty() {
+        true => Ok(()),
+        false => points.serialize_with_mode(&mut writer, compress),
+    }
+}
+
+/// Reads an optional section of the SRS file written by `write_section`.
+fn read_section<R: Read, P: CanonicalDeserialize>(
+    mut reader: R,
+    compress: Compress,
+) -> Result<Vec<P>, SerializationError> {
+    if !bool::deserialize_uncompressed(&mut reader)? {
+        return Ok(vec![]);
+    }
+    let points = Vec::<P>::deserialize_with_mode(&mut reader, compress, Validate::Yes)?;
+    match points.is_empty() {
+        true => Err(SerializationError::InvalidData),
+        false => Ok(points),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use ark_bls12_381::{Bls12_381, Fq, Fr, G1Affine};
+    use ark_bn254::Bn254;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress};
+
+    use super::*;
+
+    /// Finds a point on the BLS12-381 G1 curve outside of the prime-order subgroup.
+    pub(crate) fn non_subgroup_point() -> G1Affine {
+        (1u64..)
+            .filter_map(|x| G1Affine::get_point_from_x_unchecked(Fq::from(x), false))
+            .find(|point| !point.is_in_correct_subgroup_assuming_on_curve())
+            .unwrap()
+    }
+
+    #[test]
+    fn serialize_srs() {
+        let srs = Srs::<Bls12_381>::new_from_secret(Fr::from(17u64), 6);
+        for compress in [Compress::Yes, Compress::No] {
+            let mut bytes = Vec::new();
+            srs.serialize_with_mode(&mut bytes, compress).unwrap();
+            assert_eq!(bytes.len(), srs.serialized_size(compress));
+            let loaded =
+                Srs::<Bls12_381>::deserialize_with_mode(&bytes[..], compress, Validate::Yes)
+                    .unwrap();
+            assert_eq!(loaded, srs);
+
+            let mut file = Vec::new();
+            srs.write(&mut file, compress).unwrap();
+            assert_eq!(Srs::<Bls12_381>::read(&file[..]).unwrap(), srs);
+        }
+        assert_eq!(srs.max_degree(), 8);
+    }
+
+    #[test]
+    fn reject_invalid_srs_file() {
+        let srs = Srs::<Bls12_381>::new(4);
+        let mut file = Vec::new();
+        srs.write(&mut file, Compress::Yes).unwrap();
+
+        // another curve
+        assert_eq!(Srs::<Bn254>::read(&file[..]), Err(KzgError::CurveMismatch));
+        // truncated file
+        assert!(matches!(
+            Srs::<Bls12_381>::read(&file[..file.len() - 1]),
+            Err(KzgError::Serialization(_))
+        ));
+        // wrong magic
+        let mut forged = file.clone();
+        forged[0] = b'X';
+        assert_eq!(
+            Srs::<Bls12_381>::read(&forged[..]),
+            Err(KzgError::MalformedFile("not an SRS file"))
+        );
+        // unsupported version
+        for version in [0, 3] {
+            let mut forged = file.clone();
+            forged[4] = version;
+            assert_eq!(
+                Srs::<Bls12_381>::read(&forged[..]),
+                Err(KzgError::UnsupportedVersion(version as u32))
+            );
+        }
+
+        // maximum degree that does not match the points
+        let srs = Srs::<Bn254>::new(4);
+        let mut file = Vec::new();
+        srs.write(&mut file, Compress::No).unwrap();
+        let degree_offset = 4 + 4 + 8 + Srs::<Bn254>::curve_id().len();
+        file[degree_offset] += 1;
+        assert_eq!(
+            Srs::<Bn254>::read(&file[..]),
+            Err(KzgError::MalformedFile(
+                "the SRS does not match its maximum degree"
+            ))
+        );
+    }
+
+    #[test]
+    fn read_srs_file_version_1() {
+        // written by the first version of `Srs::write`, for a secret of 17 and a circuit size of 2
+        let file = include_bytes!("../testdata/srs_v1_bls12_381.bin");
+        assert_eq!(&file[4..8], 1u32.to_le_bytes());
+        let srs = Srs::<Bls12_381>::read(&file[..]).unwrap();
+        assert_eq!(srs, Srs::new_from_secret(Fr::from(17u64), 2));
+
+        // written again, it is upgraded to the current version
+        let mut upgraded = Vec::new();
+        srs.write(&mut upgraded, Compress::Yes).unwrap();
+        assert_eq!(&upgraded[4..8], SRS_FILE_VERSION.to_le_bytes());
+        assert_eq!(Srs::<Bls12_381>::read(&upgraded[..]).unwrap(), srs);
+    }
+
+    #[test]
+    fn read_hiding_srs_file() {
+        let srs = Srs::<Bls12_381>::new_hiding_from_secrets(Fr::from(5u64), Fr::from(23u64), 4);
+        for compress in [Compress::Yes, Compress::No] {
+            let mut file = Vec::new();
+            srs.write(&mut file, compress).unwrap();
+            assert_eq!(Srs::<Bls12_381>::read(&file[..]).unwrap(), srs);
+        }
+
+        // hiding powers of another secret are rejected
+        let mut forged = srs.clone();
+        forged.h_points =
+            Srs::<Bls12_381>::new_hiding_from_secrets(Fr::from(6u64), Fr::from(23u64), 4).h_points;
+        let mut file = Vec::new();
+        forged.write(&mut file, Compress::Yes).unwrap();
+        assert!(Srs::<Bls12_381>::read(&file[..]).is_err());
+
+        // a hiding section flagged as present but empty is rejected
+        let mut file = Vec::new();
+        Srs::<Bls12_381>::new(4)
+            .write(&mut file, Compress::Yes)
+            .unwrap();
+        let flags = file.len() - 3;
+        file[flags] = 1;
+        file.splice(flags + 1..flags + 1, 0u64.to_le_bytes());
+        assert!(Srs::<Bls12_381>::read(&file[..]).is_err());
+    }
+
+    #[test]
+    fn reject_non_subgroup_srs() {
+        let g1 = G1Point::<Bls12_381>::generator();
+        let g2 = G2Point::<Bls12_381>::generator();
+        let srs = Srs::<Bls12_381>::from_points(vec![g1, non_subgroup_point()], g2, g2);
+        for compress in [Compress::Yes, Compress::No] {
+            let mut file = Vec::new();
+            srs.write(&mut file, compress).unwrap();
+            assert!(Srs::<Bls12_381>::read(&file[..]).is_err());
+        }
+    }
+
+    #[test]
+    fn verify_srs() {
+        let srs = Srs::<Bls12_381>::new(6);
+        assert!(srs.verify().is_ok());
+        assert!(Srs::<Bn254>::new_hiding(6).verify().is_ok());
+        let srs = srs.with_lagrange_basis(8).unwrap();
+        assert!(srs.verify().is_ok());
+
+        // a power that does not follow from the previous one
+        let mut forged = srs.clone();
+        forged.g1_points.swap(2, 3);
+        assert_eq!(
+            forged.verify(),
+            Err(KzgError::MalformedSrs("the powers are inconsistent"))
+        );
+        // a secret in G2 that does not match the powers
+        let mut forged = srs.clone();
+        forged.g2s_point = forged.g2s_point.mul(Fr::from(2u64)).into_affine();
+        assert!(forged.verify().is_err());
+        // a Lagrange basis point that does not match the powers
+        let mut forged = srs.clone();
+        forged.lagrange_points.swap(0, 1);
+        assert!(forged.verify().is_err());
+        // hiding powers of another secret
+        let mut forged = Srs::<Bls12_381>::new_hiding(6);
+        forged.h_points = Srs::<Bls12_381>::new_hiding(6).h_points;
+        assert!(forged.verify().is_err());
+        // non-standard generator
+        let g1 = G1Point::<Bls12_381>::generator();
+        let g2 = G2Point::<Bls12_381>::generator();
+        let double = g1.mul(Fr::from(2u64)).into_affine();
+        assert!(Srs::<Bls12_381>::from_points(vec![double, double], g2, g2)
+            .verify()
+            .is_err());
+        assert_eq!(
+            Srs::<Bls12_381>::from_points(vec![], g2, g2).verify(),
+            Err(KzgError::EmptySrs)
+        );
+    }
+
+    #[test]
+    fn g2_powers() {
+        let secret = Fr::from(19u64);
+        let srs = Srs::<Bls12_381>::new_with_g2_powers_from_secret(secret, 4);
+        let g2 = G2Point::<Bls12_381>::generator();
+        assert_eq!(srs.g2_points().len(), srs.g1_points().len());
+        assert_eq!(srs.g2_points()[0], g2);
+        assert_eq!(srs.g2_points()[3], g2.mul(secret.pow([3])).into_affine());
+        assert!(srs.verify().is_ok());
+        assert!(Srs::<Bn254>::new_with_g2_powers(4).verify().is_ok());
+
+        let sub = srs.sub_srs(2).unwrap();
+        assert_eq!(sub, Srs::new_with_g2_powers_from_secret(secret, 2));
+
+        let mut forged = srs.clone();
+        forged.g2_points.swap(3, 4);
+        assert!(forged.verify().is_err());
+        let mut forged = srs.clone();
+        forged.g2_points.pop();
+        assert!(forged.verify().is_err());
+
+        // with and without the powers in G2, and alongside the other optional sections
+        let hiding = Srs::<Bls12_381>::new_hiding_from_secrets(secret, Fr::from(3u64), 4);
+        let mut full = Srs::new_with_g2_powers_from_secret(secret, 4)
+            .with_lagrange_basis(4)
+            .unwrap();
+        full.h_points = hiding.h_points;
+        for srs in [srs.clone(), Srs::new_from_secret(secret, 4), full] {
+            for compress in [Compress::Yes, Compress::No] {
+                let mut file = Vec::new();
+                srs.write(&mut file, compress).unwrap();
+                assert_eq!(Srs::<Bls12_381>::read(&file[..]).unwrap(), srs);
+            }
+        }
+
+        // powers in G2 of another secret are rejected on read
+        let mut forged = srs.clone();
+        forged.g2_points =
+            Srs::<Bls12_381>::new_with_g2_powers_from_secret(Fr::from(20u64), 4).g2_points;
+        forged.g2_points[1] = srs.g2s_point;
+        let mut file = Vec::new();
+        forged.write(&mut file, Compress::Yes).unwrap();
+        assert!(Srs::<Bls12_381>::read(&file[..]).is_err());
+    }
+
+    #[test]
+    fn sub_srs() {
+        let secret = Fr::from(13u64);
+        let srs = Srs::<Bls12_381>::new_from_secret(secret, 16)
+            .with_lagrange_basis(16)
+            .unwrap();
+        for circuit_size in [0, 4, 16] {
+            let sub = srs.sub_srs(circuit_size).unwrap();
+            assert_eq!(sub, Srs::new_from_secret(secret, circuit_size));
+            assert!(sub.verify().is_ok());
+        }
+        assert_eq!(
+            srs.sub_srs(17),
+            Err(KzgError::SrsTooSmall {
+                powers: 19,
+                needed: 20
+            })
+        );
+
+        let srs = Srs::<Bls12_381>::new_hiding(16);
+        let sub = srs.sub_srs(6).unwrap();
+        assert_eq!(sub.max_degree(), 8);
+        assert_eq!(sub.h_points(), srs.h_points()[..9]);
+        assert!(sub.verify().is_ok());
+    }
+
+    #[test]
+    fn lagrange_basis() {
+        let secret = Fr::from(11u64);
+        let srs = Srs::<Bls12_381>::new_from_secret(secret, 6)
+            .with_lagrange_basis(6)
+            .unwrap();
+        let domain = srs.lagrange_domain().unwrap();
+        assert_eq!(domain.size(), 8);
+
+        let g1 = G1Point::<Bls12_381>::generator();
+        let expected = domain
+            .evaluate_all_lagrange_coefficients(secret)
+            .into_iter()
+            .map(|l| g1.mul(l).into_affine())
+            .collect::<Vec<_>>();
+        assert_eq!(srs.lagrange_points(), expected);
+
+        let mut file = Vec::new();
+        srs.write(&mut file, Compress::Yes).unwrap();
+        assert_eq!(Srs::<Bls12_381>::read(&file[..]).unwrap(), srs);
+
+        // Lagrange basis points that do not match the powers are rejected when reading
+        let mut forged = srs.clone();
+        forged.lagrange_points.swap(0, 1);
+        let mut file = Vec::new();
+        forged.write(&mut file, Compress::Yes).unwrap();
+        assert!(Srs::<Bls12_381>::read(&file[..]).is_err());
+
+        // the domain needs more powers than the SRS holds
+        assert_eq!(
+            Srs::<Bls12_381>::new(6).with_lagrange_basis(9),
+            Err(KzgError::SrsTooSmall {
+                powers: 9,
+                needed: 16
+            })
+        );
+        assert_eq!(
+            Srs::<Bls12_381>::new(6).with_lagrange_basis(1 << 33),
+            Err(KzgError::DomainTooLarge { size: 1 << 33 })
+        );
+        assert!(Srs::<Bls12_381>::new(6).lagrange_domain().is_none());
+    }
 }
@@ -0,0 +1,87 @@
+use std::marker::PhantomData;
+
+use ark_ec::pairing::Pairing;
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use sha2::{Digest, Sha256};
+
+use crate::types::ScalarField;
+
+/// Fiat-Shamir transcript of the KZG scheme.
+///
+/// The `Transcript` struct absorbs the serialized messages of a protocol and derives
+/// challenges from their SHA-256 hash, so that the prover and the verifier
+/// obtain the same challenges from the same messages.
+#[derive(Debug, Clone)]
+pub struct Transcript<E: Pairing> {
+    /// Messages absorbed since the last challenge, prefixed by the previous state.
+    data: Vec<u8>,
+
+    /// Phantom data for annotation purposes.
+    _phantom_data_e: PhantomData<E>,
+}
+
+impl<E: Pairing> Transcript<E> {
+    /// Creates a new `Transcript` bound to the protocol `label`.
+    ///
+    /// # Parameters
+    ///
+    /// - `label`: Domain separator of the protocol.
+    ///
+    /// # Returns
+    ///
+    /// A new `Transcript` instance.
+    pub fn new(label: &[u8]) -> Self {
+        Self {
+            data: label.to_vec(),
+            _phantom_data_e: PhantomData,
+        }
+    }
+
+    /// Absorbs a message, such as a commitment, a point or a list of evaluations.
+    ///
+    /// # Parameters
+    ///
+    /// - `message`: The message to absorb.
+    pub fn append<T: CanonicalSerialize + ?Sized>(&mut self, message: &T) {
+        message
+            .serialize_compressed(&mut self.data)
+            .expect("serializing into a Vec should be infallible");
+    }
+
+    /// Derives a challenge from everything absorbed so far.
+    ///
+    /// # Returns
+    ///
+    /// The challenge, a scalar field element.
+    pub fn challenge(&mut self) -> ScalarField<E> {
+        let digest = Sha256::digest(&self.data);
+        self.data = digest.to_vec();
+        ScalarField::<E>::from_le_bytes_mod_order(&digest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::{Bls12_381, Fr};
+
+    use super::*;
+
+    #[test]
+    fn transcript() {
+        let mut transcript = Transcript::<Bls12_381>::new(b"test");
+        transcript.append(&Fr::from(3u64));
+        let mut other = transcript.clone();
+        let challenge = transcript.challenge();
+        assert_eq!(challenge, other.challenge());
+
+        // the challenges depend on the label, the messages and the previous challenges
+        assert_ne!(transcript.challenge(), challenge);
+        let mut other = Transcript::<Bls12_381>::new(b"other");
+        other.append(&Fr::from(3u64));
+        assert_ne!(other.challenge(), challenge);
+        let mut other = Transcript::<Bls12_381>::new(b"test");
+        other.append(&Fr::from(4u64));
+        assert_ne!(other.challenge(), challenge);
+    }
+}
@@ -1,10 +1,10 @@
-use ark_bls12_381::Fr;
-use ark_ec::bls12::Bls12;
 use ark_ec::pairing::Pairing;
 use ark_poly::univariate::DensePolynomial;
+use ark_poly::DenseMultilinearExtension;
 
-pub type G1Point = <Bls12<ark_bls12_381::Config> as Pairing>::G1Affine;
-pub type G2Point = <Bls12<ark_bls12_381::Config> as Pairing>::G2Affine;
-pub type ScalarField = <Bls12<ark_bls12_381::Config> as Pairing>::ScalarField;
-pub type BaseField = <Bls12<ark_bls12_381::Config> as Pairing>::BaseField;
-pub type Poly = DensePolynomial<Fr>;
+pub type G1Point<E> = <E as Pairing>::G1Affine;
+pub type G2Point<E> = <E as Pairing>::G2Affine;
+pub type ScalarField<E> = <E as Pairing>::ScalarField;
+pub type BaseField<E> = <E as Pairing>::BaseField;
+pub type Poly<E> = DensePolynomial<<E as Pairing>::ScalarField>;
+pub type MultilinearPoly<E> = DenseMultilinearExtension<<E as Pairing>::ScalarField>;
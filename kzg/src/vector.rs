@@ -0,0 +1,352 @@
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::Zero;
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+
+use crate::commitment::KzgCommitment;
+use crate::error::KzgError;
+use crate::opening::{KzgMultiPointOpening, KzgOpening};
+use crate::scheme::KzgScheme;
+use crate::srs::Srs;
+use crate::types::{Poly, ScalarField};
+
+/// Implements a KZG vector commitment over a radix-2 roots-of-unity domain.
+///
+/// The `KzgVectorScheme` struct commits to a vector as the polynomial taking its `i`-th
+/// element at the `i`-th root of unity, so that opening a position is opening the polynomial
+/// at the matching root of unity.
+#[derive(Debug, Clone)]
+pub struct KzgVectorScheme<E: Pairing> {
+    scheme: KzgScheme<E>,
+    domain: Radix2EvaluationDomain<ScalarField<E>>,
+}
+
+impl<E: Pairing> KzgVectorScheme<E> {
+    /// Creates a new instance of `KzgVectorScheme` over the Lagrange basis domain of the SRS.
+    ///
+    /// # Parameters
+    ///
+    /// - `srs`: The structured reference string (SRS), with Lagrange basis points.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `KzgVectorScheme`, or an error if the SRS has no Lagrange basis points.
+    pub fn new(srs: Srs<E>) -> Result<Self, KzgError> {
+        let domain = srs
+            .lagrange_domain()
+            .ok_or(KzgError::MissingLagrangeBasis)?;
+        Ok(Self {
+            scheme: KzgScheme::new(srs),
+            domain,
+        })
+    }
+
+    /// Returns the maximum number of elements of the committed vectors.
+    pub fn size(&self) -> usize {
+        self.domain.size()
+    }
+
+    /// Returns the domain whose `i`-th element is the point at which position `i` is opened.
+    pub fn domain(&self) -> Radix2EvaluationDomain<ScalarField<E>> {
+        self.domain
+    }
+
+    /// Commits to a vector.
+    ///
+    /// # Parameters
+    ///
+    /// - `vector`: The vector to be committed to, missing trailing elements are zero.
+    ///
+    /// # Returns
+    ///
+    /// The commitment to the vector, or an error if it is larger than the domain.
+    pub fn commit(&self, vector: &[ScalarField<E>]) -> Result<KzgCommitment<E>, KzgError> {
+        self.scheme.commit_evaluations(vector)
+    }
+
+    /// Opens a single position of a committed vector.
+    ///
+    /// # Parameters
+    ///
+    /// - `vector`: The committed vector.
+    /// - `position`: The position to be opened.
+    ///
+    /// # Returns
+    ///
+    /// The opening holding the element at the position, or an error if the vector is larger
+    /// than the domain or the position lies outside of it.
+    pub fn open(
+        &self,
+        vector: &[ScalarField<E>],
+        position: usize,
+    ) -> Result<KzgOpening<E>, KzgError> {
+        let point = self.point(position)?;
+        self.scheme.open_evaluations(vector, point)
+    }
+
+    /// Verifies the opening of a single position.
+    ///
+    /// # Parameters
+    ///
+    /// - `commitment`: The commitment to the vector.
+    /// - `opening`: The opening to be verified, holding the claimed element.
+    /// - `position`: The position that was opened.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the opening is valid, otherwise `false`.
+    pub fn verify(
+        &self,
+        commitment: &KzgCommitment<E>,
+        opening: &KzgOpening<E>,
+        position: usize,
+    ) -> bool {
+        match self.point(position) {
+            Ok(point) => self.scheme.verify(commitment, opening, point),
+            Err(_) => false,
+        }
+    }
+
+    /// Opens several positions of a committed vector with a single aggregated proof.
+    ///
+    /// # Parameters
+    ///
+    /// - `vector`: The committed vector.
+    /// - `commitment`: The commitment to the vector, bound to the challenges.
+    /// - `positions`: The distinct positions to be opened.
+    ///
+    /// # Returns
+    ///
+    /// The opening, or an error if the vector is larger than the domain, a position lies
+    /// outside of it or is opened twice.
+    pub fn open_positions(
+        &self,
+        vector: &[ScalarField<E>],
+        commitment: &KzgCommitment<E>,
+        positions: &[usize],
+    ) -> Result<KzgMultiPointOpening<E>, KzgError> {
+        let points = self.points(positions)?;
+        let polynomial = self.interpolate(vector)?;
+        let (opening, _) = self.scheme.open_multi_point(
+            &[polynomial],
+            std::slice::from_ref(commitment),
+            &[points],
+        )?;
+        Ok(opening)
+    }
+
+    /// Verifies an opening produced by `KzgVectorScheme::open_positions`.
+    ///
+    /// # Parameters
+    ///
+    /// - `commitment`: The commitment to the vector.
+    /// - `positions`: The positions that were opened.
+    /// - `elements`: The claimed element at each position.
+    /// - `opening`: The opening to be verified.
+    ///
+    /// # Returns
+    ///
+    /// `true` if every claimed element is valid, otherwise `false`.
+    pub fn verify_positions(
+        &self,
+        commitment: &KzgCommitment<E>,
+        positions: &[usize],
+        elements: &[ScalarField<E>],
+        opening: &KzgMultiPointOpening<E>,
+    ) -> bool {
+        match self.points(positions) {
+            Ok(points) => self.scheme.verify_multi_point(
+                std::slice::from_ref(commitment),
+                &[points],
+                &[elements.to_vec()],
+                opening,
+            ),
+            Err(_) => false,
+        }
+    }
+
+    /// Computes the openings of every position of a vector at once, in `O(n log n)`
+    /// group operations with the Feist–Khovratovich technique.
+    ///
+    /// With `f = sum_k c_k X^k`, the witness of position `i` is `h(w^i)` for the polynomial
+    /// `h = sum_j h_j X^j` with `h_j = sum_{k > j} c_k * [s^(k - j - 1)]_1`. The coefficients
+    /// `h_j` are a Toeplitz matrix-vector product, computed as a convolution with FFTs over
+    /// a domain of twice the size, and all `h(w^i)` with one more FFT over G1.
+    ///
+    /// # Parameters
+    ///
+    /// - `vector`: The committed vector.
+    ///
+    /// # Returns
+    ///
+    /// The opening of each position of the domain, or an error if the vector is larger
+    /// than the domain or the field has no domain of twice its size.
+    pub fn open_all(&self, vector: &[ScalarField<E>]) -> Result<Vec<KzgOpening<E>>, KzgError> {
+        let n = self.size();
+        let coeffs = self.interpolate(vector)?.coeffs;
+        let mut evaluations = vector.to_vec();
+        evaluations.resize(n, ScalarField::<E>::zero());
+
+        let double = Radix2EvaluationDomain::<ScalarField<E>>::new(2 * n)
+            .ok_or(KzgError::DomainTooLarge { size: 2 * n })?;
+        // the powers [s^0]_1, ..., [s^(n - 2)]_1 and the reversed coefficients, zero padded
+        let mut powers = self.scheme.srs().g1_points_slice()[..n - 1]
+            .iter()
+            .map(|point| point.into_group())
+            .collect::<Vec<_>>();
+        powers.resize(2 * n, E::G1::zero());
+        let mut reversed = coeffs;
+        reversed.resize(n, ScalarField::<E>::zero());
+        reversed.reverse();
+
+        let powers = double.fft(&powers);
+        let reversed = double.fft(&reversed);
+        let product = powers
+            .iter()
+            .zip(&reversed)
+            .map(|(point, c)| *point * c)
+            .collect::<Vec<_>>();
+        let convolution = double.ifft(&product);
+
+        // h_j is the convolution at n - 2 - j, and h_(n - 1) = 0
+        let mut h = (0..n - 1)
+            .map(|j| convolution[n - 2 - j])
+            .collect::<Vec<_>>();
+        h.push(E::G1::zero());
+        let witnesses = E::G1::normalize_batch(&self.domain.fft(&h));
+
+        Ok(witnesses
+            .into_iter()
+            .zip(evaluations)
+            .zip(self.domain.elements())
+            .map(|((witness, evaluation), point)| KzgOpening(witness, evaluation, point))
+            .collect())
+    }
+
+    /// Computes the coefficients of the polynomial taking the elements of the vector over the domain.
+    fn interpolate(&self, vector: &[ScalarField<E>]) -> Result<Poly<E>, KzgError> {
+        if vector.len() > self.size() {
+            return Err(KzgError::TooManyEvaluations {
+                evaluations: vector.len(),
+                domain_size: self.size(),
+            });
+        }
+        Ok(Poly::<E> {
+            coeffs: self.domain.ifft(vector),
+        })
+    }
+
+    /// Returns the point at which a position is opened.
+    fn point(&self, position: usize) -> Result<ScalarField<E>, KzgError> {
+        if position >= self.size() {
+            return Err(KzgError::PositionOutOfRange {
+                position,
+                size: self.size(),
+            });
+        }
+        Ok(self.domain.element(position))
+    }
+
+    /// Returns the points at which the positions are opened.
+    fn points(&self, positions: &[usize]) -> Result<Vec<ScalarField<E>>, KzgError> {
+        positions
+            .iter()
+            .map(|position| self.point(*position))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_bn254::Bn254;
+    use ark_ff::{One, UniformRand};
+
+    use super::*;
+
+    fn vector_commitment<E: Pairing>() {
+        let rng = &mut rand::thread_rng();
+        let srs = Srs::<E>::new(16).with_lagrange_basis(16).unwrap();
+        let scheme = KzgVectorScheme::new(srs).unwrap();
+        assert_eq!(scheme.size(), 16);
+        let vector = (0..13)
+            .map(|_| ScalarField::<E>::rand(rng))
+            .collect::<Vec<_>>();
+        let commitment = scheme.commit(&vector).unwrap();
+
+        let opening = scheme.open(&vector, 4).unwrap();
+        assert_eq!(opening.1, vector[4]);
+        assert!(scheme.verify(&commitment, &opening, 4));
+        assert!(!scheme.verify(&commitment, &opening, 5));
+        assert!(!scheme.verify(&commitment, &opening, 16));
+        // the padding is zero
+        let opening = scheme.open(&vector, 14).unwrap();
+        assert_eq!(opening.1, ScalarField::<E>::zero());
+        assert!(scheme.verify(&commitment, &opening, 14));
+
+        let positions = [0, 3, 7, 15];
+        let elements = [vector[0], vector[3], vector[7], ScalarField::<E>::zero()];
+        let opening = scheme
+            .open_positions(&vector, &commitment, &positions)
+            .unwrap();
+        assert!(scheme.verify_positions(&commitment, &positions, &elements, &opening));
+        let mut forged = elements;
+        forged[1] += ScalarField::<E>::one();
+        assert!(!scheme.verify_positions(&commitment, &positions, &forged, &opening));
+        assert!(!scheme.verify_positions(&commitment, &positions[..3], &elements[..3], &opening));
+        assert!(!scheme.verify_positions(&commitment, &[0, 3, 7, 16], &elements, &opening));
+    }
+
+    #[test]
+    fn kzg_vector_commitment() {
+        vector_commitment::<Bls12_381>();
+        vector_commitment::<Bn254>();
+    }
+
+    #[test]
+    fn open_all_positions() {
+        let rng = &mut rand::thread_rng();
+        for size in [1, 2, 8, 32] {
+            let srs = Srs::<Bls12_381>::new(size)
+                .with_lagrange_basis(size)
+                .unwrap();
+            let scheme = KzgVectorScheme::new(srs).unwrap();
+            let vector = (0..size).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+            let commitment = scheme.commit(&vector).unwrap();
+
+            let openings = scheme.open_all(&vector).unwrap();
+            assert_eq!(openings.len(), size);
+            for (position, opening) in openings.iter().enumerate() {
+                assert_eq!(*opening, scheme.open(&vector, position).unwrap());
+                assert!(scheme.verify(&commitment, opening, position));
+            }
+        }
+    }
+
+    #[test]
+    fn reject_invalid_vector_inputs() {
+        assert_eq!(
+            KzgVectorScheme::new(Srs::<Bls12_381>::new(8)).unwrap_err(),
+            KzgError::MissingLagrangeBasis
+        );
+        let srs = Srs::<Bls12_381>::new(8).with_lagrange_basis(8).unwrap();
+        let scheme = KzgVectorScheme::new(srs).unwrap();
+        let vector = vec![Fr::one(); 9];
+        assert!(scheme.commit(&vector).is_err());
+        assert!(scheme.open_all(&vector).is_err());
+
+        let vector = vec![Fr::one(); 8];
+        let commitment = scheme.commit(&vector).unwrap();
+        assert_eq!(
+            scheme.open(&vector, 8),
+            Err(KzgError::PositionOutOfRange {
+                position: 8,
+                size: 8
+            })
+        );
+        assert_eq!(
+            scheme.open_positions(&vector, &commitment, &[1, 1]),
+            Err(KzgError::DuplicatePoints)
+        );
+    }
+}
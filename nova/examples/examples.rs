@@ -1,7 +1,7 @@
+use ark_bls12_381::{Bls12_381, Fq as BaseField, Fr as ScalarField};
 use ark_ff::{BigInteger, One, PrimeField, Zero};
 use kzg::scheme::KzgScheme;
 use kzg::srs::Srs;
-use kzg::types::{BaseField, ScalarField};
 use nova::circuit::{AugmentedCircuit, FCircuit, State};
 use nova::ivc::{IVCProof, ZkIVCProof, IVC};
 use nova::r1cs::{create_trivial_pair, FInstance, FWitness, R1CS};
@@ -10,8 +10,14 @@ use nova::utils::{to_f_matrix, to_f_vec};
 use sha2::Sha256;
 
 struct TestCircuit {}
-impl FCircuit for TestCircuit {
-    fn run(&self, z_i: &State, w_i: &FWitness) -> State {
+impl FCircuit<KzgScheme<Bls12_381>> for TestCircuit {
+    type BaseField = BaseField;
+
+    fn run(
+        &self,
+        z_i: &State<BaseField>,
+        w_i: &FWitness<KzgScheme<Bls12_381>>,
+    ) -> State<BaseField> {
         let x = w_i.w[0];
         let res = x * x * x + x + ScalarField::from(5);
         let base_res = BaseField::from_le_bytes_mod_order(&res.into_bigint().to_bytes_le());
@@ -35,19 +41,19 @@ fn main() {
 
     // Trusted setup
     let domain_size = witnesses[0].len() + x[0].len() + 1;
-    let srs = Srs::new(domain_size);
+    let srs = Srs::<Bls12_381>::new(domain_size);
     let scheme = KzgScheme::new(srs);
     let x_len = x[0].len();
 
     // Generate witnesses and instances
-    let w: Vec<FWitness> = witnesses
+    let w: Vec<FWitness<KzgScheme<Bls12_381>>> = witnesses
         .iter()
         .map(|witness| FWitness::new(witness, matrix_a.len()))
         .collect();
-    let mut u: Vec<FInstance> = w
+    let mut u: Vec<FInstance<KzgScheme<Bls12_381>>> = w
         .iter()
         .zip(x)
-        .map(|(w, x)| w.commit(&scheme, &x))
+        .map(|(w, x)| w.commit(&scheme, &x).unwrap())
         .collect();
 
     // step i
@@ -55,7 +61,7 @@ fn main() {
 
     // generate trivial instance-witness pair
     let (trivial_witness, trivial_instance) =
-        create_trivial_pair(x_len, witnesses[0].len(), &scheme);
+        create_trivial_pair(x_len, witnesses[0].len(), &scheme).unwrap();
 
     // generate f_circuit instance
     let f_circuit = TestCircuit {};
@@ -75,11 +81,14 @@ fn main() {
     let mut verifier_transcript = Transcript::<Sha256>::default();
 
     // create F'
-    let augmented_circuit =
-        AugmentedCircuit::<Sha256, TestCircuit>::new(f_circuit, &trivial_instance, &z[0]);
+    let augmented_circuit = AugmentedCircuit::<KzgScheme<Bls12_381>, Sha256, TestCircuit>::new(
+        f_circuit,
+        &trivial_instance,
+        &z[0],
+    );
 
     // generate IVC
-    let mut ivc = IVC::<Sha256, TestCircuit> {
+    let mut ivc = IVC::<KzgScheme<Bls12_381>, Sha256, TestCircuit> {
         scheme,
         augmented_circuit,
     };
@@ -126,7 +135,7 @@ fn main() {
             prover_transcript = Transcript::<Sha256>::default();
             verifier_transcript = Transcript::<Sha256>::default();
 
-            let hash_x = AugmentedCircuit::<Sha256, TestCircuit>::hash_io(
+            let hash_x = AugmentedCircuit::<KzgScheme<Bls12_381>, Sha256, TestCircuit>::hash_io(
                 i,
                 &z[0],
                 &z[step + 1],
@@ -144,8 +153,9 @@ fn main() {
                 &folded_instance,
                 &folded_witness,
             );
-            (folded_witness, folded_instance, zk_ivc_proof) =
-                ivc.prove(&r1cs, &ivc_proof, &mut prover_transcript);
+            (folded_witness, folded_instance, zk_ivc_proof) = ivc
+                .prove(&r1cs, &ivc_proof, &mut prover_transcript)
+                .unwrap();
         }
     }
 }
@@ -3,52 +3,62 @@ use crate::r1cs::{FInstance, FWitness};
 use crate::transcript::Transcript;
 use ark_ff::{BigInteger, One, PrimeField, Zero};
 use ark_serialize::CanonicalSerialize;
-use kzg::commitment::KzgCommitment;
-use kzg::types::{BaseField, ScalarField};
+use kzg::pcs::HomomorphicCommitmentScheme;
 use sha2::Digest;
 use std::marker::PhantomData;
 use std::ops::Add;
 
-/// State structure of IVC, which is presented in BaseField of Bsn12_381 curve
-/// Todo: Implement a general version.
+/// State structure of IVC, which is presented in the BaseField of the commitment curve
 #[derive(Clone, Debug)]
-pub struct State {
-    pub state: BaseField,
+pub struct State<F: PrimeField> {
+    pub state: F,
 }
 
 /// trait for F circuit
-pub trait FCircuit {
+pub trait FCircuit<P: HomomorphicCommitmentScheme> {
+    /// The field of the state and the public IO hash, the BaseField of the commitment curve
+    type BaseField: PrimeField;
+
     // return state z_{i+1} = F(z_i, w_i)
-    fn run(&self, z_i: &State, w_i: &FWitness) -> State;
+    fn run(&self, z_i: &State<Self::BaseField>, w_i: &FWitness<P>) -> State<Self::BaseField>;
 }
 
 /// F' circuit
-pub struct AugmentedCircuit<T: Digest + Default + ark_serialize::Write, FC: FCircuit> {
+pub struct AugmentedCircuit<
+    P: HomomorphicCommitmentScheme,
+    T: Digest + Default + ark_serialize::Write,
+    FC: FCircuit<P>,
+> {
     // F function
     pub f_circuit: FC,
     // i is the step of IVC
-    pub i: BaseField,
+    pub i: FC::BaseField,
     // trivial instance u⊥
-    pub trivial_instance: FInstance,
+    pub trivial_instance: FInstance<P>,
     // The initial state z_0
-    pub z_0: State,
+    pub z_0: State<FC::BaseField>,
     // The current state
-    pub z_i: State,
+    pub z_i: State<FC::BaseField>,
     // The next state z_{i+1} = F(z_i, w_i).
-    pub z_i1: Option<State>,
+    pub z_i1: Option<State<FC::BaseField>>,
     // h_i = hash(i, z0, zi, Ui)
-    pub h_i: Option<BaseField>,
+    pub h_i: Option<FC::BaseField>,
     // store the next hash IO: h_{i+1} = hash(i + 1, z0, z{i+1}, U{i+1})
-    pub h_i1: Option<BaseField>,
+    pub h_i1: Option<FC::BaseField>,
     pub phantom_data_t: PhantomData<T>,
 }
 
 #[allow(dead_code)]
-impl<T: Digest + Default + ark_serialize::Write, FC: FCircuit> AugmentedCircuit<T, FC> {
-    pub fn new(f_circuit: FC, trivial_instance: &FInstance, z_0: &State) -> Self {
+impl<
+        P: HomomorphicCommitmentScheme,
+        T: Digest + Default + ark_serialize::Write,
+        FC: FCircuit<P>,
+    > AugmentedCircuit<P, T, FC>
+{
+    pub fn new(f_circuit: FC, trivial_instance: &FInstance<P>, z_0: &State<FC::BaseField>) -> Self {
         Self {
             f_circuit,
-            i: BaseField::zero(),
+            i: FC::BaseField::zero(),
             trivial_instance: trivial_instance.clone(),
             z_0: z_0.clone(),
             z_i: z_0.clone(),
@@ -60,12 +70,12 @@ impl<T: Digest + Default + ark_serialize::Write, FC: FCircuit> AugmentedCircuit<
     }
     pub fn run(
         &mut self,
-        u_i: &FInstance,
-        big_u_i: Option<&FInstance>,
-        w_i: &FWitness,
-        com_t: Option<&KzgCommitment>,
-    ) -> Result<BaseField, String> {
-        if self.i != BaseField::from(0) {
+        u_i: &FInstance<P>,
+        big_u_i: Option<&FInstance<P>>,
+        w_i: &FWitness<P>,
+        com_t: Option<&P::Commitment>,
+    ) -> Result<FC::BaseField, String> {
+        if self.i != FC::BaseField::zero() {
             // check that if i > 0 then U_i and com_t must exist
             if big_u_i.is_none() || com_t.is_none() {
                 return Err(String::from("Wrong parameters."));
@@ -85,7 +95,7 @@ impl<T: Digest + Default + ark_serialize::Write, FC: FCircuit> AugmentedCircuit<
             // Todo: Non-native field transform
 
             let u_dot_x = u_i.x[0];
-            let hash_fr = ScalarField::from_le_bytes_mod_order(&hash_x.into_bigint().to_bytes_le());
+            let hash_fr = P::Field::from_le_bytes_mod_order(&hash_x.into_bigint().to_bytes_le());
             if u_dot_x != hash_fr {
                 return Err(String::from("Public IO is wrong "));
             }
@@ -95,7 +105,7 @@ impl<T: Digest + Default + ark_serialize::Write, FC: FCircuit> AugmentedCircuit<
                 return Err(String::from("Commitment of E is wrong"));
             }
 
-            if u_i.u != ScalarField::one() {
+            if u_i.u != P::Field::one() {
                 return Err(String::from("Scalar u is wrong"));
             }
 
@@ -107,13 +117,18 @@ impl<T: Digest + Default + ark_serialize::Write, FC: FCircuit> AugmentedCircuit<
             let [r] = transcript.generate_challenges();
 
             // 3.compute U_{i+1}
-            let big_u_i1 = NIFS::<T>::verifier(r, u_i, big_u_i.unwrap(), com_t.unwrap());
+            let big_u_i1 = NIFS::<P, T>::verifier(r, u_i, big_u_i.unwrap(), com_t.unwrap());
 
             // compute z_{i+1} = F(z_i, w_i)
             let z_i1 = self.f_circuit.run(&self.z_i, w_i);
 
             // compute hash
-            let new_hash = Self::hash_io(self.i.add(BaseField::one()), &self.z_0, &z_i1, &big_u_i1);
+            let new_hash = Self::hash_io(
+                self.i.add(FC::BaseField::one()),
+                &self.z_0,
+                &z_i1,
+                &big_u_i1,
+            );
 
             // store the next hash
             self.h_i1 = Some(new_hash);
@@ -126,8 +141,12 @@ impl<T: Digest + Default + ark_serialize::Write, FC: FCircuit> AugmentedCircuit<
             let z_i1 = self.f_circuit.run(&self.z_i, w_i);
 
             // compute hash
-            let new_hash =
-                Self::hash_io(BaseField::one(), &self.z_0, &z_i1, &self.trivial_instance);
+            let new_hash = Self::hash_io(
+                FC::BaseField::one(),
+                &self.z_0,
+                &z_i1,
+                &self.trivial_instance,
+            );
 
             // store the next hash
             self.h_i1 = Some(new_hash);
@@ -143,28 +162,33 @@ impl<T: Digest + Default + ark_serialize::Write, FC: FCircuit> AugmentedCircuit<
     pub fn next_step(&mut self) {
         self.z_i = self.z_i1.clone().unwrap();
         self.z_i1 = None;
-        self.i += BaseField::one();
+        self.i += FC::BaseField::one();
         self.h_i = self.h_i1;
         self.h_i1 = None;
     }
 
     /// A function computes public IO of an instance: u.x = hash(i, z0, zi, Ui).
-    pub fn hash_io(i: BaseField, z_0: &State, z_i: &State, big_u_i: &FInstance) -> BaseField {
+    pub fn hash_io(
+        i: FC::BaseField,
+        z_0: &State<FC::BaseField>,
+        z_i: &State<FC::BaseField>,
+        big_u_i: &FInstance<P>,
+    ) -> FC::BaseField {
         let mut hasher = T::default();
         i.serialize_uncompressed(&mut hasher).unwrap();
         z_0.state.serialize_uncompressed(&mut hasher).unwrap();
         z_i.state.serialize_uncompressed(&mut hasher).unwrap();
 
-        big_u_i.com_e.0.serialize_uncompressed(&mut hasher).unwrap();
+        big_u_i.com_e.serialize_uncompressed(&mut hasher).unwrap();
         big_u_i.u.serialize_uncompressed(&mut hasher).unwrap();
-        big_u_i.com_w.0.serialize_uncompressed(&mut hasher).unwrap();
+        big_u_i.com_w.serialize_uncompressed(&mut hasher).unwrap();
 
         for x in &big_u_i.x {
             x.serialize_uncompressed(&mut hasher).unwrap();
         }
 
         let data = hasher.finalize().to_vec();
-        BaseField::from_le_bytes_mod_order(&data)
+        FC::BaseField::from_le_bytes_mod_order(&data)
     }
 }
 
@@ -174,13 +198,20 @@ mod test {
     use super::*;
     use crate::nifs::nifs_verifier::gen_test_values;
     use crate::r1cs::create_trivial_pair;
+    use ark_bls12_381::{Bls12_381, Fq as BaseField, Fr as ScalarField};
     use kzg::scheme::KzgScheme;
     use kzg::srs::Srs;
     use sha2::Sha256;
 
     struct TestCircuit {}
-    impl FCircuit for TestCircuit {
-        fn run(&self, z_i: &State, w_i: &FWitness) -> State {
+    impl FCircuit<KzgScheme<Bls12_381>> for TestCircuit {
+        type BaseField = BaseField;
+
+        fn run(
+            &self,
+            z_i: &State<BaseField>,
+            w_i: &FWitness<KzgScheme<Bls12_381>>,
+        ) -> State<BaseField> {
             let x = w_i.w[0];
             let res = x * x * x + x + ScalarField::from(5);
             // because res is in scalar field, we need to convert it into base_field
@@ -204,14 +235,15 @@ mod test {
 
         // Trusted setup
         let domain_size = witnesses[0].len() + x[0].len() + 1;
-        let srs = Srs::new(domain_size);
+        let srs = Srs::<Bls12_381>::new(domain_size);
         let scheme = KzgScheme::new(srs);
 
         let w_0 = FWitness::new(&witnesses[0], matrix_a.len());
-        let u_0 = w_0.commit(&scheme, &x[0]);
+        let u_0 = w_0.commit(&scheme, &x[0]).unwrap();
 
         // generate trivial_instance
-        let (_, trivial_instance) = create_trivial_pair(x[0].len(), witnesses[0].len(), &scheme);
+        let (_, trivial_instance) =
+            create_trivial_pair(x[0].len(), witnesses[0].len(), &scheme).unwrap();
 
         // generate f_circuit instance
         let f_circuit = TestCircuit {};
@@ -226,7 +258,7 @@ mod test {
         // let prover_transcript = Transcript::<Sha256>::default();
 
         // create F'
-        let mut augmented_circuit = AugmentedCircuit::<Sha256, TestCircuit> {
+        let mut augmented_circuit = AugmentedCircuit::<KzgScheme<Bls12_381>, Sha256, TestCircuit> {
             f_circuit,
             i: BaseField::zero(),
             trivial_instance: trivial_instance.clone(),
@@ -250,7 +282,7 @@ mod test {
         // check if the hash output is correct
         assert_eq!(
             hash,
-            AugmentedCircuit::<Sha256, TestCircuit>::hash_io(
+            AugmentedCircuit::<KzgScheme<Bls12_381>, Sha256, TestCircuit>::hash_io(
                 BaseField::one(),
                 &z_0,
                 &z_1,
@@ -274,15 +306,15 @@ mod test {
 
         // Trusted setup
         let domain_size = witnesses[0].len() + x[0].len() + 1;
-        let srs = Srs::new(domain_size);
+        let srs = Srs::<Bls12_381>::new(domain_size);
         let scheme = KzgScheme::new(srs);
 
         let w_1 = FWitness::new(&witnesses[1], matrix_a.len());
-        let mut u_1 = w_1.commit(&scheme, &x[1]);
+        let mut u_1 = w_1.commit(&scheme, &x[1]).unwrap();
 
         // generate trivial_instance
         let (trivial_witness, trivial_instance) =
-            create_trivial_pair(x[0].len(), witnesses[0].len(), &scheme);
+            create_trivial_pair(x[0].len(), witnesses[0].len(), &scheme).unwrap();
 
         // generate f_circuit instance
         let f_circuit = TestCircuit {};
@@ -299,7 +331,7 @@ mod test {
         };
         let mut prover_transcript = Transcript::<Sha256>::default();
 
-        let u_1_x = AugmentedCircuit::<Sha256, TestCircuit>::hash_io(
+        let u_1_x = AugmentedCircuit::<KzgScheme<Bls12_381>, Sha256, TestCircuit>::hash_io(
             BaseField::from(1),
             &z_0,
             &z_1,
@@ -310,7 +342,7 @@ mod test {
             &u_1_x.into_bigint().to_bytes_le(),
         )];
 
-        let (_, folded_instance, com_t, _) = NIFS::<Sha256>::prover(
+        let (_, folded_instance, com_t, _) = NIFS::<KzgScheme<Bls12_381>, Sha256>::prover(
             &r1cs,
             &w_1,
             &trivial_witness,
@@ -318,10 +350,11 @@ mod test {
             &trivial_instance,
             &scheme,
             &mut prover_transcript,
-        );
+        )
+        .unwrap();
 
         // create F'
-        let mut augmented_circuit = AugmentedCircuit::<Sha256, TestCircuit> {
+        let mut augmented_circuit = AugmentedCircuit::<KzgScheme<Bls12_381>, Sha256, TestCircuit> {
             f_circuit,
             i: BaseField::from(1),
             trivial_instance: trivial_instance.clone(),
@@ -345,7 +378,7 @@ mod test {
         // check if the hash output is correct
         assert_eq!(
             hash,
-            AugmentedCircuit::<Sha256, TestCircuit>::hash_io(
+            AugmentedCircuit::<KzgScheme<Bls12_381>, Sha256, TestCircuit>::hash_io(
                 BaseField::from(2),
                 &z_0,
                 &z_2,
@@ -370,15 +403,15 @@ mod test {
 
         // Trusted setup
         let domain_size = witnesses[0].len() + x[0].len() + 1;
-        let srs = Srs::new(domain_size);
+        let srs = Srs::<Bls12_381>::new(domain_size);
         let scheme = KzgScheme::new(srs);
 
         let w_1 = FWitness::new(&witnesses[1], matrix_a.len());
-        let mut u_1 = w_1.commit(&scheme, &x[1]);
+        let mut u_1 = w_1.commit(&scheme, &x[1]).unwrap();
 
         // generate trivial_instance
         let (trivial_witness, trivial_instance) =
-            create_trivial_pair(x[0].len(), witnesses[0].len(), &scheme);
+            create_trivial_pair(x[0].len(), witnesses[0].len(), &scheme).unwrap();
 
         // generate f_circuit instance
         let f_circuit = TestCircuit {};
@@ -395,7 +428,7 @@ mod test {
         };
         let mut prover_transcript = Transcript::<Sha256>::default();
 
-        let u_1_x = AugmentedCircuit::<Sha256, TestCircuit>::hash_io(
+        let u_1_x = AugmentedCircuit::<KzgScheme<Bls12_381>, Sha256, TestCircuit>::hash_io(
             BaseField::from(1),
             &z_0,
             &z_1,
@@ -406,7 +439,7 @@ mod test {
             &u_1_x.into_bigint().to_bytes_le(),
         )];
 
-        let (_, folded_instance, com_t, _) = NIFS::<Sha256>::prover(
+        let (_, folded_instance, com_t, _) = NIFS::<KzgScheme<Bls12_381>, Sha256>::prover(
             &r1cs,
             &w_1,
             &trivial_witness,
@@ -414,10 +447,11 @@ mod test {
             &trivial_instance,
             &scheme,
             &mut prover_transcript,
-        );
+        )
+        .unwrap();
 
         // create F'
-        let mut augmented_circuit = AugmentedCircuit::<Sha256, TestCircuit> {
+        let mut augmented_circuit = AugmentedCircuit::<KzgScheme<Bls12_381>, Sha256, TestCircuit> {
             f_circuit,
             i: BaseField::from(1),
             trivial_instance: trivial_instance.clone(),
@@ -441,7 +475,7 @@ mod test {
         // check if the hash output is correct
         assert_eq!(
             hash,
-            AugmentedCircuit::<Sha256, TestCircuit>::hash_io(
+            AugmentedCircuit::<KzgScheme<Bls12_381>, Sha256, TestCircuit>::hash_io(
                 BaseField::from(2),
                 &z_0,
                 &z_2,
@@ -4,23 +4,29 @@ use crate::nifs::NIFS;
 use crate::r1cs::{FInstance, FWitness, R1CS};
 use crate::transcript::Transcript;
 use ark_ff::Zero;
-use kzg::types::ScalarField;
+use kzg::pcs::HomomorphicCommitmentScheme;
 use sha2::Digest;
 
 #[allow(dead_code)]
-impl<T: Digest + Default + ark_serialize::Write, FC: FCircuit> IVC<T, FC> {
+impl<
+        P: HomomorphicCommitmentScheme,
+        T: Digest + Default + ark_serialize::Write,
+        FC: FCircuit<P>,
+    > IVC<P, T, FC>
+{
     /// IVC prover will fold 2 instance-witness pairs into one via NIFS
     /// and generate zkSNARK proof for it.
+    #[allow(clippy::type_complexity)]
     pub fn prove(
         &self,
-        r1cs: &R1CS<ScalarField>,
-        ivc_proof: &IVCProof,
+        r1cs: &R1CS<P::Field>,
+        ivc_proof: &IVCProof<P>,
         prover_transcript: &mut Transcript<T>,
-    ) -> (FWitness, FInstance, ZkIVCProof) {
+    ) -> Result<(FWitness<P>, FInstance<P>, ZkIVCProof<P>), String> {
         let i = self.augmented_circuit.i;
         if !i.is_zero() {
             // 1 + 2. Parse Π and compute U', W' and com_T
-            let (big_w_out, big_u_out, com_t, r) = NIFS::<T>::prover(
+            let (big_w_out, big_u_out, com_t, r) = NIFS::<P, T>::prover(
                 r1cs,
                 &ivc_proof.w_i,
                 &ivc_proof.big_w_i,
@@ -28,13 +34,13 @@ impl<T: Digest + Default + ark_serialize::Write, FC: FCircuit> IVC<T, FC> {
                 &ivc_proof.big_u_i,
                 &self.scheme,
                 prover_transcript,
-            );
+            )?;
 
             // 3. Generate zkSNARK proof
             let nifs_proof =
-                NIFS::<T>::prove(r, &big_w_out, &big_u_out, &self.scheme, prover_transcript);
+                NIFS::<P, T>::prove(r, &big_w_out, &big_u_out, &self.scheme, prover_transcript)?;
 
-            (
+            Ok((
                 big_w_out,
                 big_u_out,
                 ZkIVCProof {
@@ -43,9 +49,9 @@ impl<T: Digest + Default + ark_serialize::Write, FC: FCircuit> IVC<T, FC> {
                     com_t: Some(com_t),
                     folded_u_proof: Some(nifs_proof),
                 },
-            )
+            ))
         } else {
-            (
+            Ok((
                 ivc_proof.big_w_i.clone(),
                 ivc_proof.big_u_i.clone(),
                 ZkIVCProof {
@@ -54,7 +60,7 @@ impl<T: Digest + Default + ark_serialize::Write, FC: FCircuit> IVC<T, FC> {
                     com_t: None,
                     folded_u_proof: None,
                 },
-            )
+            ))
         }
     }
 }
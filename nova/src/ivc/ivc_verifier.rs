@@ -3,23 +3,28 @@ use crate::ivc::{ZkIVCProof, IVC};
 use crate::nifs::NIFS;
 use crate::transcript::Transcript;
 use ark_ff::{BigInteger, One, PrimeField, Zero};
-use kzg::types::{BaseField, ScalarField};
+use kzg::pcs::HomomorphicCommitmentScheme;
 use sha2::Digest;
 
 #[allow(dead_code)]
-impl<T: Digest + Default + ark_serialize::Write, FC: FCircuit> IVC<T, FC> {
+impl<
+        P: HomomorphicCommitmentScheme,
+        T: Digest + Default + ark_serialize::Write,
+        FC: FCircuit<P>,
+    > IVC<P, T, FC>
+{
     /// IVC verifier will do 5 steps as mentioned in constructor 4
     /// of Nova paper.
     pub fn verify(
         &mut self,
-        zk_ivc_proof: &ZkIVCProof,
+        zk_ivc_proof: &ZkIVCProof<P>,
         verifier_transcript: &mut Transcript<T>,
     ) -> Result<(), String> {
         let i = self.augmented_circuit.i;
         let z_0 = &self.augmented_circuit.z_0;
         let z_i = &self.augmented_circuit.z_i;
 
-        if i == BaseField::zero() {
+        if i == FC::BaseField::zero() {
             if z_0.state == z_i.state {
                 Ok(())
             } else {
@@ -43,9 +48,8 @@ impl<T: Digest + Default + ark_serialize::Write, FC: FCircuit> IVC<T, FC> {
             let folded_u_proof = zk_ivc_proof.folded_u_proof.clone().unwrap();
 
             // 2. check that u.x = hash(i, z_0, z_i, U)
-            let hash_io = AugmentedCircuit::<T, FC>::hash_io(i, z_0, z_i, &big_u_i);
-            let hash_fr =
-                ScalarField::from_le_bytes_mod_order(&hash_io.into_bigint().to_bytes_le());
+            let hash_io = AugmentedCircuit::<P, T, FC>::hash_io(i, z_0, z_i, &big_u_i);
+            let hash_fr = P::Field::from_le_bytes_mod_order(&hash_io.into_bigint().to_bytes_le());
             if u_i.x[0] != hash_fr {
                 return Err(String::from("Verify failed: Public IO is wrong"));
             }
@@ -59,11 +63,11 @@ impl<T: Digest + Default + ark_serialize::Write, FC: FCircuit> IVC<T, FC> {
             }
 
             // 4. compute U' = NIFS.V(U, u, comT)
-            let big_u_out = NIFS::<T>::verifier(folded_u_proof.r, &u_i, &big_u_i, &com_t);
+            let big_u_out = NIFS::<P, T>::verifier(folded_u_proof.r, &u_i, &big_u_i, &com_t);
 
             // 5. verify that zkSNARK.V(U', pi_U') = 1
 
-            NIFS::<T>::verify(
+            NIFS::<P, T>::verify(
                 &folded_u_proof,
                 &u_i,
                 &big_u_i,
@@ -85,13 +89,20 @@ mod tests {
     use crate::nifs::nifs_verifier::gen_test_values;
     use crate::r1cs::{create_trivial_pair, FInstance, FWitness};
     use crate::transcript::Transcript;
+    use ark_bls12_381::{Bls12_381, Fq as BaseField, Fr as ScalarField};
     use kzg::scheme::KzgScheme;
     use kzg::srs::Srs;
     use sha2::Sha256;
     use std::marker::PhantomData;
     struct TestCircuit {}
-    impl FCircuit for TestCircuit {
-        fn run(&self, z_i: &State, w_i: &FWitness) -> State {
+    impl FCircuit<KzgScheme<Bls12_381>> for TestCircuit {
+        type BaseField = BaseField;
+
+        fn run(
+            &self,
+            z_i: &State<BaseField>,
+            w_i: &FWitness<KzgScheme<Bls12_381>>,
+        ) -> State<BaseField> {
             let x = w_i.w[0];
             let res = x * x * x + x + ScalarField::from(5);
             // because res is in scalar field, we need to convert it into base_field
@@ -118,19 +129,19 @@ mod tests {
 
         // Trusted setup
         let domain_size = witnesses[0].len() + x[0].len() + 1;
-        let srs = Srs::new(domain_size);
+        let srs = Srs::<Bls12_381>::new(domain_size);
         let scheme = KzgScheme::new(srs);
         let x_len = x[0].len();
 
         // Generate witnesses and instances
-        let w: Vec<FWitness> = witnesses
+        let w: Vec<FWitness<KzgScheme<Bls12_381>>> = witnesses
             .iter()
             .map(|witness| FWitness::new(witness, matrix_a.len()))
             .collect();
-        let mut u: Vec<FInstance> = w
+        let mut u: Vec<FInstance<KzgScheme<Bls12_381>>> = w
             .iter()
             .zip(x)
-            .map(|(w, x)| w.commit(&scheme, &x))
+            .map(|(w, x)| w.commit(&scheme, &x).unwrap())
             .collect();
 
         // step i
@@ -138,7 +149,7 @@ mod tests {
 
         // generate trivial instance-witness pair
         let (trivial_witness, trivial_instance) =
-            create_trivial_pair(x_len, witnesses[0].len(), &scheme);
+            create_trivial_pair(x_len, witnesses[0].len(), &scheme).unwrap();
 
         // generate f_circuit instance
         let f_circuit = TestCircuit {};
@@ -158,11 +169,14 @@ mod tests {
         let mut verifier_transcript = Transcript::<Sha256>::default();
 
         // create F'
-        let augmented_circuit =
-            AugmentedCircuit::<Sha256, TestCircuit>::new(f_circuit, &trivial_instance, &z[0]);
+        let augmented_circuit = AugmentedCircuit::<KzgScheme<Bls12_381>, Sha256, TestCircuit>::new(
+            f_circuit,
+            &trivial_instance,
+            &z[0],
+        );
 
         // generate IVC
-        let mut ivc = IVC::<Sha256, TestCircuit> {
+        let mut ivc = IVC::<KzgScheme<Bls12_381>, Sha256, TestCircuit> {
             scheme,
             augmented_circuit,
         };
@@ -210,7 +224,7 @@ mod tests {
                 prover_transcript = Transcript::<Sha256>::default();
                 verifier_transcript = Transcript::<Sha256>::default();
 
-                let hash_x = AugmentedCircuit::<Sha256, TestCircuit>::hash_io(
+                let hash_x = AugmentedCircuit::<KzgScheme<Bls12_381>, Sha256, TestCircuit>::hash_io(
                     i,
                     &z[0],
                     &z[step + 1],
@@ -228,8 +242,9 @@ mod tests {
                     &folded_instance,
                     &folded_witness,
                 );
-                (folded_witness, folded_instance, zk_ivc_proof) =
-                    ivc.prove(&r1cs, &ivc_proof, &mut prover_transcript);
+                (folded_witness, folded_instance, zk_ivc_proof) = ivc
+                    .prove(&r1cs, &ivc_proof, &mut prover_transcript)
+                    .unwrap();
             }
         }
     }
@@ -250,16 +265,16 @@ mod tests {
 
         // Trusted setup
         let domain_size = witnesses[0].len() + x[0].len() + 1;
-        let srs = Srs::new(domain_size);
+        let srs = Srs::<Bls12_381>::new(domain_size);
         let scheme = KzgScheme::new(srs);
 
         let w_0 = FWitness::new(&witnesses[0], matrix_a.len());
         let w_1 = FWitness::new(&witnesses[1], matrix_a.len());
         let w_2 = FWitness::new(&witnesses[2], matrix_a.len());
 
-        let u_0 = w_0.commit(&scheme, &x[0]);
-        let mut u_1 = w_1.commit(&scheme, &x[1]);
-        let mut u_2 = w_2.commit(&scheme, &x[2]);
+        let u_0 = w_0.commit(&scheme, &x[0]).unwrap();
+        let mut u_1 = w_1.commit(&scheme, &x[1]).unwrap();
+        let mut u_2 = w_2.commit(&scheme, &x[2]).unwrap();
 
         // step i
         let mut i = BaseField::zero();
@@ -267,7 +282,7 @@ mod tests {
         // generate trivial_instance
         let trivial_x = vec![ScalarField::from(0); x[0].len()];
         let trivial_witness = FWitness::new_trivial_witness(witnesses[0].len());
-        let trivial_instance = trivial_witness.commit(&scheme, &trivial_x);
+        let trivial_instance = trivial_witness.commit(&scheme, &trivial_x).unwrap();
 
         // generate f_circuit instance
         let f_circuit = TestCircuit {};
@@ -283,7 +298,7 @@ mod tests {
         let mut verifier_transcript = Transcript::<Sha256>::default();
 
         // create F'
-        let augmented_circuit = AugmentedCircuit::<Sha256, TestCircuit> {
+        let augmented_circuit = AugmentedCircuit::<KzgScheme<Bls12_381>, Sha256, TestCircuit> {
             f_circuit,
             i: BaseField::zero(),
             trivial_instance: trivial_instance.clone(),
@@ -296,7 +311,7 @@ mod tests {
         };
 
         // generate IVC
-        let mut ivc = IVC::<Sha256, TestCircuit> {
+        let mut ivc = IVC::<KzgScheme<Bls12_381>, Sha256, TestCircuit> {
             scheme,
             augmented_circuit,
         };
@@ -344,8 +359,12 @@ mod tests {
         verifier_transcript = Transcript::<Sha256>::default();
 
         // because all instances above are from F, not F', so we need to do this trick.
-        let u_1_x =
-            AugmentedCircuit::<Sha256, TestCircuit>::hash_io(i, &z_0, &z_1, &trivial_instance);
+        let u_1_x = AugmentedCircuit::<KzgScheme<Bls12_381>, Sha256, TestCircuit>::hash_io(
+            i,
+            &z_0,
+            &z_1,
+            &trivial_instance,
+        );
         // convert u_1_x from BaseField into ScalarField
         u_1.x = vec![ScalarField::from_le_bytes_mod_order(
             &u_1_x.into_bigint().to_bytes_le(),
@@ -363,8 +382,9 @@ mod tests {
         };
 
         // generate W_2, U_2 and zkIVCProof via IVC proof
-        (folded_witness, folded_instance, zk_ivc_proof) =
-            ivc.prove(&r1cs, &ivc_proof, &mut prover_transcript);
+        (folded_witness, folded_instance, zk_ivc_proof) = ivc
+            .prove(&r1cs, &ivc_proof, &mut prover_transcript)
+            .unwrap();
 
         println!("Step 2");
         // run F' for the second time
@@ -399,8 +419,12 @@ mod tests {
             "Wrong state"
         );
 
-        let u_2_x =
-            AugmentedCircuit::<Sha256, TestCircuit>::hash_io(i, &z_0, &z_2, &folded_instance);
+        let u_2_x = AugmentedCircuit::<KzgScheme<Bls12_381>, Sha256, TestCircuit>::hash_io(
+            i,
+            &z_0,
+            &z_2,
+            &folded_instance,
+        );
         u_2.x = vec![ScalarField::from_le_bytes_mod_order(
             &u_2_x.into_bigint().to_bytes_le(),
         )];
@@ -412,7 +436,9 @@ mod tests {
         };
 
         // Compute W_3, U_3, and zkSNARK proof
-        (_, _, zk_ivc_proof) = ivc.prove(&r1cs, &ivc_proof, &mut prover_transcript);
+        (_, _, zk_ivc_proof) = ivc
+            .prove(&r1cs, &ivc_proof, &mut prover_transcript)
+            .unwrap();
 
         println!("Step 3");
         // run F' for the last time
@@ -4,31 +4,35 @@ mod ivc_verifier;
 use crate::circuit::{AugmentedCircuit, FCircuit};
 use crate::nifs::NIFSProof;
 use crate::r1cs::{FInstance, FWitness};
-use kzg::commitment::KzgCommitment;
-use kzg::scheme::KzgScheme;
+use kzg::pcs::HomomorphicCommitmentScheme;
 use sha2::Digest;
 
 /// This struct is the zero knowledge proof for IVC
 /// π = (U, u, com_T , π_U') where U' is the folded instance
 /// of u and U.
-pub struct ZkIVCProof {
-    pub u_i: FInstance,
-    pub big_u_i: FInstance,
-    pub com_t: Option<KzgCommitment>,
-    pub folded_u_proof: Option<NIFSProof>,
+pub struct ZkIVCProof<P: HomomorphicCommitmentScheme> {
+    pub u_i: FInstance<P>,
+    pub big_u_i: FInstance<P>,
+    pub com_t: Option<P::Commitment>,
+    pub folded_u_proof: Option<NIFSProof<P>>,
 }
 
 /// This struct is the proof for IVC: Π = (u, w) (U, W)
-pub struct IVCProof {
-    pub u_i: FInstance,
-    pub w_i: FWitness,
-    pub big_u_i: FInstance,
-    pub big_w_i: FWitness,
+pub struct IVCProof<P: HomomorphicCommitmentScheme> {
+    pub u_i: FInstance<P>,
+    pub w_i: FWitness<P>,
+    pub big_u_i: FInstance<P>,
+    pub big_w_i: FWitness<P>,
 }
 
 #[allow(dead_code)]
-impl IVCProof {
-    pub fn new(u_i: &FInstance, w_i: &FWitness, big_u_i: &FInstance, big_w_i: &FWitness) -> Self {
+impl<P: HomomorphicCommitmentScheme> IVCProof<P> {
+    pub fn new(
+        u_i: &FInstance<P>,
+        w_i: &FWitness<P>,
+        big_u_i: &FInstance<P>,
+        big_w_i: &FWitness<P>,
+    ) -> Self {
         Self {
             u_i: u_i.clone(),
             w_i: w_i.clone(),
@@ -38,7 +42,10 @@ impl IVCProof {
     }
 
     // Generate a trivial IVC proof.
-    pub fn trivial_ivc_proof(trivial_instance: &FInstance, trivial_witness: &FWitness) -> Self {
+    pub fn trivial_ivc_proof(
+        trivial_instance: &FInstance<P>,
+        trivial_witness: &FWitness<P>,
+    ) -> Self {
         Self {
             u_i: trivial_instance.clone(),
             w_i: trivial_witness.clone(),
@@ -49,8 +56,8 @@ impl IVCProof {
 }
 
 #[allow(dead_code)]
-impl ZkIVCProof {
-    pub fn trivial_zk_ivc_proof(trivial_instance: &FInstance) -> Self {
+impl<P: HomomorphicCommitmentScheme> ZkIVCProof<P> {
+    pub fn trivial_zk_ivc_proof(trivial_instance: &FInstance<P>) -> Self {
         Self {
             u_i: trivial_instance.clone(),
             big_u_i: trivial_instance.clone(),
@@ -61,7 +68,11 @@ impl ZkIVCProof {
 }
 
 /// IVC structure includes a scheme for commitment and an augmented F' function
-pub struct IVC<T: Digest + Default + ark_serialize::Write, FC: FCircuit> {
-    pub scheme: KzgScheme,
-    pub augmented_circuit: AugmentedCircuit<T, FC>,
+pub struct IVC<
+    P: HomomorphicCommitmentScheme,
+    T: Digest + Default + ark_serialize::Write,
+    FC: FCircuit<P>,
+> {
+    pub scheme: P,
+    pub augmented_circuit: AugmentedCircuit<P, T, FC>,
 }
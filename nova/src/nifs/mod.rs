@@ -1,14 +1,10 @@
-use ark_ec::CurveGroup;
 use std::marker::PhantomData;
-use std::ops::Mul;
 
 use crate::r1cs::{FInstance, FWitness, R1CS};
 use crate::utils::{
     hadamard_product, matrix_vector_product, vec_add, vec_sub, vector_elem_product,
 };
-use kzg::commitment::KzgCommitment;
-use kzg::opening::KzgOpening;
-use kzg::types::ScalarField;
+use kzg::pcs::HomomorphicCommitmentScheme;
 use sha2::Digest;
 
 mod nifs_prover;
@@ -17,27 +13,28 @@ pub(crate) mod nifs_verifier;
 /// NIFS Proof is a zk proof. To convince the verifier, prover creates an opening
 /// for each E and W.
 #[derive(Clone)]
-pub struct NIFSProof {
-    pub r: ScalarField,
-    pub opening_point: ScalarField,
-    pub opening_e: KzgOpening,
-    pub opening_w: KzgOpening,
+pub struct NIFSProof<P: HomomorphicCommitmentScheme> {
+    pub r: P::Field,
+    pub opening_point: P::Field,
+    pub opening_e: P::Opening,
+    pub opening_w: P::Opening,
 }
 
-pub struct NIFS<T: Digest + Default> {
+pub struct NIFS<P: HomomorphicCommitmentScheme, T: Digest + Default> {
     _phantom_data_t: PhantomData<T>,
+    _phantom_data_e: PhantomData<P>,
 }
 
-impl<T: Digest + Default> NIFS<T> {
+impl<P: HomomorphicCommitmentScheme, T: Digest + Default> NIFS<P, T> {
     /// Compute the cross-term T
     /// T = AZ1 ◦ BZ2 + AZ2 ◦ BZ1 − u1 · CZ2 − u2 · CZ1.
     pub fn compute_t(
-        r1cs: &R1CS<ScalarField>,
-        u1: ScalarField,
-        u2: ScalarField,
-        z1: &[ScalarField],
-        z2: &[ScalarField],
-    ) -> Vec<ScalarField> {
+        r1cs: &R1CS<P::Field>,
+        u1: P::Field,
+        u2: P::Field,
+        z1: &[P::Field],
+        z2: &[P::Field],
+    ) -> Vec<P::Field> {
         let az1 = matrix_vector_product(&r1cs.matrix_a, z1);
         let bz1 = matrix_vector_product(&r1cs.matrix_b, z1);
         let cz1 = matrix_vector_product(&r1cs.matrix_c, z1);
@@ -61,12 +58,12 @@ impl<T: Digest + Default> NIFS<T> {
     /// E ← E1 + r · T + r^2 · E2
     /// W ← W1 + r · W2
     pub fn fold_witness(
-        r: ScalarField,
-        fw1: &FWitness,
-        fw2: &FWitness,
-        t: &[ScalarField],
+        r: P::Field,
+        fw1: &FWitness<P>,
+        fw2: &FWitness<P>,
+        t: &[P::Field],
         // rT: ScalarField,
-    ) -> FWitness {
+    ) -> FWitness<P> {
         let new_e = fw1
             .e
             .iter()
@@ -86,14 +83,13 @@ impl<T: Digest + Default> NIFS<T> {
     /// com_W ← com_W1 + r · com_W2
     /// x ← x1 + r · x2
     pub fn fold_instance(
-        r: ScalarField,
-        fi1: &FInstance,
-        fi2: &FInstance,
-        com_t: &KzgCommitment,
-    ) -> FInstance {
-        let new_com_e =
-            KzgCommitment((fi1.com_e.0 + com_t.0.mul(r) + fi2.com_e.0.mul(r * r)).into_affine());
-        let new_com_w = KzgCommitment((fi1.com_w.0 + fi2.com_w.0.mul(r)).into_affine());
+        r: P::Field,
+        fi1: &FInstance<P>,
+        fi2: &FInstance<P>,
+        com_t: &P::Commitment,
+    ) -> FInstance<P> {
+        let new_com_e = fi1.com_e.clone() + com_t.clone() * r + fi2.com_e.clone() * (r * r);
+        let new_com_w = fi1.com_w.clone() + fi2.com_w.clone() * r;
 
         let new_u = fi1.u + fi2.u * r;
         let new_x = fi1.x.iter().zip(&fi2.x).map(|(a, b)| *a + *b * r).collect();
@@ -2,31 +2,29 @@ use crate::nifs::{FInstance, NIFSProof, NIFS, R1CS};
 use crate::transcript::Transcript;
 use crate::utils::{to_f_matrix, to_f_vec};
 use ark_ff::PrimeField;
-use kzg::commitment::KzgCommitment;
-use kzg::scheme::KzgScheme;
-use kzg::types::ScalarField;
+use kzg::pcs::HomomorphicCommitmentScheme;
 use sha2::Digest;
 
-impl<T: Digest + Default> NIFS<T> {
+impl<P: HomomorphicCommitmentScheme, T: Digest + Default> NIFS<P, T> {
     /// NIFS.V generate the folded instance.
     pub fn verifier(
-        r: ScalarField,
-        fi1: &FInstance,
-        fi2: &FInstance,
-        com_t: &KzgCommitment,
-    ) -> FInstance {
-        NIFS::<T>::fold_instance(r, fi1, fi2, com_t)
+        r: P::Field,
+        fi1: &FInstance<P>,
+        fi2: &FInstance<P>,
+        com_t: &P::Commitment,
+    ) -> FInstance<P> {
+        NIFS::<P, T>::fold_instance(r, fi1, fi2, com_t)
     }
 
     /// NIFS.V can verify whether the Prover folding process was done
     /// correctly or not via the NIFS proof.
     pub fn verify(
-        proof: &NIFSProof,
-        fi1: &FInstance,
-        fi2: &FInstance,
-        fi3: &FInstance, // folded instance.
-        com_t: &KzgCommitment,
-        scheme: &KzgScheme,
+        proof: &NIFSProof<P>,
+        fi1: &FInstance<P>,
+        fi2: &FInstance<P>,
+        fi3: &FInstance<P>, // folded instance.
+        com_t: &P::Commitment,
+        scheme: &P,
         transcript: &mut Transcript<T>,
     ) -> Result<(), String> {
         // verify challenge.
@@ -42,10 +40,10 @@ impl<T: Digest + Default> NIFS<T> {
 
     /// Verify challenge r via Fiat-Shamir
     pub fn verify_challenge(
-        r: ScalarField,
-        fi1_u: ScalarField,
-        fi2_u: ScalarField,
-        com_t: &KzgCommitment,
+        r: P::Field,
+        fi1_u: P::Field,
+        fi2_u: P::Field,
+        com_t: &P::Commitment,
         transcript: &mut Transcript<T>,
     ) -> Result<(), String> {
         // Recreate challenge r
@@ -53,7 +51,7 @@ impl<T: Digest + Default> NIFS<T> {
         transcript.feed_scalar_num(fi2_u);
         transcript.feed(com_t);
 
-        let [new_r] = transcript.generate_challenges();
+        let [new_r]: [P::Field; 1] = transcript.generate_challenges();
 
         // Verify that proof.r = Transcript(fi1.u, fi2.u, cmT)
         if new_r != r {
@@ -63,17 +61,17 @@ impl<T: Digest + Default> NIFS<T> {
         Ok(())
     }
 
-    /// Verify KZG opening
+    /// Verify the openings of the folded instance
     pub fn verify_opening(
-        proof: &NIFSProof,
-        fi3: &FInstance, // folded instance.
-        scheme: &KzgScheme,
+        proof: &NIFSProof<P>,
+        fi3: &FInstance<P>, // folded instance.
+        scheme: &P,
         transcript: &mut Transcript<T>,
     ) -> Result<(), String> {
         transcript.feed(&fi3.com_e);
         transcript.feed(&fi3.com_w);
         // Verify Opening_point = Transcript(fi1.cmE, fi1.cmW)
-        let [opening_point] = transcript.generate_challenges();
+        let [opening_point]: [P::Field; 1] = transcript.generate_challenges();
         if opening_point != proof.opening_point {
             return Err(String::from(
                 "Verify: Error in computing random opening point",
@@ -148,11 +146,13 @@ pub fn gen_test_values<F: PrimeField>(inputs: Vec<usize>) -> (R1CS<F>, Vec<Vec<F
 mod tests {
     use super::*;
     use crate::nifs::{FWitness, NIFS};
-    use kzg::srs::Srs;
+    use ark_bls12_381::Bls12_381;
+    use kzg::ipa::IpaScheme;
+    use kzg::scheme::KzgScheme;
     use sha2::Sha256;
 
-    #[test]
-    fn test_one_fold() {
+    /// Folds two instances with the commitment scheme `P` and verifies the folding.
+    fn one_fold<P: HomomorphicCommitmentScheme>() {
         // generate R1CS, witnesses and public input, output.
         let (r1cs, witnesses, x) = gen_test_values(vec![3, 4]);
         let (matrix_a, _, _) = (
@@ -161,50 +161,61 @@ mod tests {
             r1cs.matrix_c.clone(),
         );
 
-        // Trusted setup
+        // Setup
         let domain_size = witnesses[0].len() + x[0].len() + 1;
-        let srs = Srs::new(domain_size);
-        let scheme = KzgScheme::new(srs);
+        let scheme = &P::new(P::setup(domain_size));
 
         let mut prover_transcript = Transcript::<Sha256>::default();
         let mut verifier_transcript = Transcript::<Sha256>::default();
 
         // generate witnesses and instances
-        let fw1 = FWitness::new(&witnesses[0], matrix_a.len());
-        let fw2 = FWitness::new(&witnesses[1], matrix_a.len());
+        let fw1 = FWitness::<P>::new(&witnesses[0], matrix_a.len());
+        let fw2 = FWitness::<P>::new(&witnesses[1], matrix_a.len());
 
-        let fi1 = fw1.commit(&scheme, &x[0]);
-        let fi2 = fw2.commit(&scheme, &x[1]);
+        let fi1 = fw1.commit(scheme, &x[0]).unwrap();
+        let fi2 = fw2.commit(scheme, &x[1]).unwrap();
 
-        let (p_folded_witness, p_folded_instance, com_t, r) = NIFS::prover(
+        let (p_folded_witness, p_folded_instance, com_t, r) = NIFS::<P, Sha256>::prover(
             &r1cs,
             &fw1,
             &fw2,
             &fi1,
             &fi2,
-            &scheme,
+            scheme,
             &mut prover_transcript,
-        );
+        )
+        .unwrap();
 
-        let proof = NIFS::<Sha256>::prove(
+        let proof = NIFS::<P, Sha256>::prove(
             r,
             &p_folded_witness,
             &p_folded_instance,
-            &scheme,
+            scheme,
             &mut prover_transcript,
-        );
-        let v_folded_instance = NIFS::<Sha256>::verifier(r, &fi1, &fi2, &com_t);
+        )
+        .unwrap();
+        let v_folded_instance = NIFS::<P, Sha256>::verifier(r, &fi1, &fi2, &com_t);
 
-        let result = NIFS::<Sha256>::verify(
+        let result = NIFS::<P, Sha256>::verify(
             &proof,
             &fi1,
             &fi2,
             &v_folded_instance,
             &com_t,
-            &scheme,
+            scheme,
             &mut verifier_transcript,
         );
         println!("{:?}", result);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_one_fold() {
+        one_fold::<KzgScheme<Bls12_381>>();
+    }
+
+    #[test]
+    fn test_one_fold_ipa() {
+        one_fold::<IpaScheme<Bls12_381>>();
+    }
 }
@@ -2,9 +2,7 @@ use crate::utils::{
     hadamard_product, matrix_vector_product, vec_add, vec_equal, vector_elem_product,
 };
 use ark_ff::{One, PrimeField, Zero};
-use kzg::commitment::KzgCommitment;
-use kzg::scheme::KzgScheme;
-use kzg::types::ScalarField;
+use kzg::pcs::HomomorphicCommitmentScheme;
 
 /// Create R1CS structure
 #[derive(Clone)]
@@ -16,30 +14,28 @@ pub struct R1CS<F: PrimeField> {
     pub num_vars: usize,
 }
 
-/// Create Committed Relaxed R1CS Instance structure with KZG commitment
-/// Todo: Need to impl a general-curve commitment.
+/// Create Committed Relaxed R1CS Instance structure with a homomorphic polynomial commitment
 #[derive(Debug, Clone)]
-pub struct FInstance {
-    pub com_e: KzgCommitment,
-    pub u: ScalarField,
-    pub com_w: KzgCommitment,
-    pub x: Vec<ScalarField>,
+pub struct FInstance<P: HomomorphicCommitmentScheme> {
+    pub com_e: P::Commitment,
+    pub u: P::Field,
+    pub com_w: P::Commitment,
+    pub x: Vec<P::Field>,
 }
 
-/// Create Committed Relaxed FWitness with KZG commitment
-/// Todo: Need to implement a general-curve commitment
+/// Create Committed Relaxed FWitness with a homomorphic polynomial commitment
 #[derive(Debug, Clone)]
-pub struct FWitness {
-    pub e: Vec<ScalarField>,
+pub struct FWitness<P: HomomorphicCommitmentScheme> {
+    pub e: Vec<P::Field>,
     // pub rE: ScalarField,
-    pub w: Vec<ScalarField>,
+    pub w: Vec<P::Field>,
     // pub rW: ScalarField,
 }
 #[allow(dead_code)]
-impl FWitness {
-    pub fn new(w: &[ScalarField], len: usize) -> Self {
+impl<P: HomomorphicCommitmentScheme> FWitness<P> {
+    pub fn new(w: &[P::Field], len: usize) -> Self {
         FWitness {
-            e: vec![ScalarField::zero(); len],
+            e: vec![P::Field::zero(); len],
             // rE: ScalarField::rand(&mut rand::thread_rng()),
             w: w.into(),
             // rW: ScalarField::rand(&mut rand::thread_rng()),
@@ -49,48 +45,52 @@ impl FWitness {
     /// Create a trivial witness, where E, W, and x are appropriately-sized zero vectors.
     pub fn new_trivial_witness(len: usize) -> Self {
         FWitness {
-            e: vec![ScalarField::zero(); len],
-            w: vec![ScalarField::zero(); len],
+            e: vec![P::Field::zero(); len],
+            w: vec![P::Field::zero(); len],
         }
     }
 
     /// Commit a witness into its corresponding instance.
-    pub fn commit(&self, scheme: &KzgScheme, x: &[ScalarField]) -> FInstance {
-        let com_e = scheme.commit_vector(&self.e);
+    pub fn commit(&self, scheme: &P, x: &[P::Field]) -> Result<FInstance<P>, String> {
+        let com_e = scheme
+            .commit_vector(&self.e)
+            .map_err(|error| error.to_string())?;
         // cE.0 = cE.0.mul(self.rE).into_affine();
-        let com_w = scheme.commit_vector(&self.w);
+        let com_w = scheme
+            .commit_vector(&self.w)
+            .map_err(|error| error.to_string())?;
         // cW.0 = cW.0.mul(self.rW).into_affine();
 
-        FInstance {
+        Ok(FInstance {
             com_e,
-            u: ScalarField::one(),
+            u: P::Field::one(),
             com_w,
             x: x.into(),
-        }
+        })
     }
 }
 
 /// This function creates a trivial instance-witness pair
 #[allow(dead_code)]
-pub fn create_trivial_pair(
+pub fn create_trivial_pair<P: HomomorphicCommitmentScheme>(
     x_len: usize,
     w_len: usize,
-    scheme: &KzgScheme,
-) -> (FWitness, FInstance) {
-    let trivial_x = vec![ScalarField::from(0); x_len];
+    scheme: &P,
+) -> Result<(FWitness<P>, FInstance<P>), String> {
+    let trivial_x = vec![P::Field::zero(); x_len];
     let trivial_witness = FWitness::new_trivial_witness(w_len);
-    let trivial_instance = trivial_witness.commit(scheme, &trivial_x);
-    (trivial_witness, trivial_instance)
+    let trivial_instance = trivial_witness.commit(scheme, &trivial_x)?;
+    Ok((trivial_witness, trivial_instance))
 }
 
 /// Check that whether the witness and instance are satisfied R1CS.
 /// (A ·Z) ◦ (B ·Z) = u ·(C ·Z) + E
 #[allow(dead_code)]
-pub fn is_r1cs_satisfied(
-    r1cs: &R1CS<ScalarField>,
-    f_instance: &FInstance,
-    f_witness: &FWitness,
-    scheme: &KzgScheme,
+pub fn is_r1cs_satisfied<P: HomomorphicCommitmentScheme>(
+    r1cs: &R1CS<P::Field>,
+    f_instance: &FInstance<P>,
+    f_witness: &FWitness<P>,
+    scheme: &P,
 ) -> Result<(), String> {
     if r1cs.num_vars != f_witness.w.len() {
         return Err(String::from("Witness does not match with matrices"));
@@ -115,8 +115,13 @@ pub fn is_r1cs_satisfied(
     let res_eq = vec_equal(&left_side, &right_side);
 
     // check whether Instance satisfies Witness
-    let res_com = (f_instance.com_w == scheme.commit_vector(&f_witness.w))
-        && (f_instance.com_e == scheme.commit_vector(&f_witness.e));
+    let com_w = scheme
+        .commit_vector(&f_witness.w)
+        .map_err(|error| error.to_string())?;
+    let com_e = scheme
+        .commit_vector(&f_witness.e)
+        .map_err(|error| error.to_string())?;
+    let res_com = (f_instance.com_w == com_w) && (f_instance.com_e == com_e);
 
     if res_com && res_eq {
         Ok(())
@@ -129,9 +134,9 @@ pub fn is_r1cs_satisfied(
 mod tests {
     use crate::nifs::nifs_verifier::gen_test_values;
     use crate::r1cs::{is_r1cs_satisfied, FInstance, FWitness};
+    use ark_bls12_381::{Bls12_381, Fr as ScalarField};
     use kzg::scheme::KzgScheme;
     use kzg::srs::Srs;
-    use kzg::types::ScalarField;
 
     #[test]
     pub fn test_r1cs_satisfaction_condition() {
@@ -145,18 +150,18 @@ mod tests {
 
         // Trusted setup
         let domain_size = witnesses[0].len() + x[0].len() + 1;
-        let srs = Srs::new(domain_size);
+        let srs = Srs::<Bls12_381>::new(domain_size);
         let scheme = KzgScheme::new(srs);
 
         // Generate witnesses and instances
-        let w: Vec<FWitness> = witnesses
+        let w: Vec<FWitness<KzgScheme<Bls12_381>>> = witnesses
             .iter()
             .map(|witness| FWitness::new(witness, matrix_a.len()))
             .collect();
-        let u: Vec<FInstance> = w
+        let u: Vec<FInstance<KzgScheme<Bls12_381>>> = w
             .iter()
             .zip(x)
-            .map(|(w, x)| w.commit(&scheme, &x))
+            .map(|(w, x)| w.commit(&scheme, &x).unwrap())
             .collect();
 
         let ok = is_r1cs_satisfied(&r1cs, &u[0], &w[0], &scheme);
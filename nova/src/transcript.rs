@@ -1,16 +1,12 @@
 use std::marker::PhantomData;
 
-use ark_bls12_381::Fr;
-use ark_ff::UniformRand;
+use ark_ff::PrimeField;
 use ark_serialize::{CanonicalSerialize, Write};
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 use sha2::Digest;
 
-use kzg::commitment::KzgCommitment;
-use kzg::types::ScalarField;
-
-/// Generates Fiat-Shamir challenges for the KZG scheme.
+/// Generates Fiat-Shamir challenges for the polynomial commitment scheme.
 ///
 /// The `Transcript` struct is responsible for generating challenges.
 #[derive(Clone, Default)]
@@ -29,14 +25,14 @@ impl<T: Digest + Default> Transcript<T> {
     ///
     /// # Parameters
     ///
-    /// - `kzg_commitment`: A slice containing the commitments.
+    /// - `commitments`: A slice containing the commitments.
     ///
     /// # Returns
     ///
     /// A new `Transcript` instance.
-    pub fn from_commitment(kzg_commitment: &[KzgCommitment]) -> Self {
+    pub fn from_commitment<C: CanonicalSerialize>(commitments: &[C]) -> Self {
         let mut challenge_parse = Self::default();
-        for commitment in kzg_commitment {
+        for commitment in commitments {
             challenge_parse.feed(commitment);
         }
         challenge_parse
@@ -51,7 +47,7 @@ impl<T: Digest + Default> Transcript<T> {
     /// # Returns
     ///
     /// A new `Transcript` instance.
-    pub fn from_scalar_number(numbers: &[ScalarField]) -> Self {
+    pub fn from_scalar_number<F: PrimeField>(numbers: &[F]) -> Self {
         let mut challenge_parse = Self::default();
         for number in numbers {
             challenge_parse.feed_scalar_num(*number);
@@ -65,12 +61,11 @@ impl<T: Digest + Default> Transcript<T> {
     ///
     /// # Parameters
     ///
-    /// - `kzg_commitment`: The commitment to feed to the generator.
-    pub fn feed(&mut self, kzg_commitment: &KzgCommitment) {
+    /// - `commitment`: The commitment to feed to the generator.
+    pub fn feed<C: CanonicalSerialize>(&mut self, commitment: &C) {
         let mut hasher = T::default();
         hasher.update(self.data.take().unwrap_or_default());
-        kzg_commitment
-            .inner()
+        commitment
             .serialize_uncompressed(HashMarshaller(&mut hasher))
             .expect("HashMarshaller::flush should be infallible!");
         self.data = Some(hasher.finalize().to_vec());
@@ -78,7 +73,7 @@ impl<T: Digest + Default> Transcript<T> {
     }
 
     /// Feeds a number to the transcript
-    pub fn feed_scalar_num(&mut self, num: ScalarField) {
+    pub fn feed_scalar_num<F: PrimeField>(&mut self, num: F) {
         let mut hasher = T::default();
         hasher.update(self.data.take().unwrap_or_default());
         num.serialize_uncompressed(HashMarshaller(&mut hasher))
@@ -107,10 +102,10 @@ impl<T: Digest + Default> Transcript<T> {
     /// # Returns
     ///
     /// An array of generated challenges.
-    pub fn generate_challenges<const N: usize>(&mut self) -> [Fr; N] {
+    pub fn generate_challenges<F: PrimeField, const N: usize>(&mut self) -> [F; N] {
         let mut rng = self.generate_rng_with_seed();
         let points = [0; N];
-        points.map(|_| Fr::rand(&mut rng))
+        points.map(|_| F::rand(&mut rng))
     }
 }
 
@@ -135,8 +130,9 @@ impl<'a, H: Digest> Write for HashMarshaller<'a, H> {
 mod tests {
     use std::ops::Mul;
 
-    use ark_bls12_381::Fr;
+    use ark_bls12_381::{Bls12_381, Fr};
     use ark_ec::{AffineRepr, CurveGroup};
+    use kzg::commitment::KzgCommitment;
     use kzg::types::G1Point;
     use sha2::Sha256;
 
@@ -144,18 +140,29 @@ mod tests {
 
     #[test]
     fn aggregation_digest_test() {
-        let commitment1 = KzgCommitment(G1Point::generator().mul(Fr::from(1)).into_affine());
-        let commitment2 = KzgCommitment(G1Point::generator().mul(Fr::from(2)).into_affine());
-        let commitments1: [KzgCommitment; 2] = [commitment1.clone(), commitment2.clone()];
-        let [a, aa, aaa] =
+        let commitment1: KzgCommitment<Bls12_381> = KzgCommitment(
+            G1Point::<Bls12_381>::generator()
+                .mul(Fr::from(1))
+                .into_affine(),
+        );
+        let commitment2: KzgCommitment<Bls12_381> = KzgCommitment(
+            G1Point::<Bls12_381>::generator()
+                .mul(Fr::from(2))
+                .into_affine(),
+        );
+        let commitments1: [KzgCommitment<Bls12_381>; 2] =
+            [commitment1.clone(), commitment2.clone()];
+        let [a, aa, aaa]: [Fr; 3] =
             Transcript::<Sha256>::from_commitment(&commitments1).generate_challenges();
 
-        let commitments2: [KzgCommitment; 1] = [commitment2.clone()];
-        let [b] = Transcript::<Sha256>::from_commitment(&commitments2).generate_challenges();
+        let commitments2: [KzgCommitment<Bls12_381>; 1] = [commitment2.clone()];
+        let [b]: [Fr; 1] =
+            Transcript::<Sha256>::from_commitment(&commitments2).generate_challenges();
         assert_ne!(a, b, "should be different");
 
-        let commitments3: [KzgCommitment; 2] = [commitment1.clone(), commitment2.clone()];
-        let [c, cc, ccc] =
+        let commitments3: [KzgCommitment<Bls12_381>; 2] =
+            [commitment1.clone(), commitment2.clone()];
+        let [c, cc, ccc]: [Fr; 3] =
             Transcript::<Sha256>::from_commitment(&commitments3).generate_challenges();
         assert_eq!(a, c, "should be equal");
         assert_eq!(aa, cc, "should be equal");
@@ -164,11 +171,13 @@ mod tests {
 
     #[test]
     fn transcript_test_01() {
-        let a = ScalarField::from(15);
-        let b = ScalarField::from(20);
+        let a = Fr::from(15);
+        let b = Fr::from(20);
 
-        let [x, y, z] = Transcript::<Sha256>::from_scalar_number(&[a, b]).generate_challenges();
-        let [x1, y1, z1] = Transcript::<Sha256>::from_scalar_number(&[a, b]).generate_challenges();
+        let [x, y, z]: [Fr; 3] =
+            Transcript::<Sha256>::from_scalar_number(&[a, b]).generate_challenges();
+        let [x1, y1, z1]: [Fr; 3] =
+            Transcript::<Sha256>::from_scalar_number(&[a, b]).generate_challenges();
 
         assert_eq!(x, x1, "should be equal");
         assert_eq!(y, y1, "should be equal");
@@ -177,12 +186,18 @@ mod tests {
 
     #[test]
     fn transcript_test_02() {
-        let a = ScalarField::from(15);
-        let b = ScalarField::from(20);
-        let commitment1 =
-            KzgCommitment(G1Point::generator().mul(ScalarField::from(1)).into_affine());
-        let commitment2 =
-            KzgCommitment(G1Point::generator().mul(ScalarField::from(2)).into_affine());
+        let a = Fr::from(15);
+        let b = Fr::from(20);
+        let commitment1: KzgCommitment<Bls12_381> = KzgCommitment(
+            G1Point::<Bls12_381>::generator()
+                .mul(Fr::from(1))
+                .into_affine(),
+        );
+        let commitment2: KzgCommitment<Bls12_381> = KzgCommitment(
+            G1Point::<Bls12_381>::generator()
+                .mul(Fr::from(2))
+                .into_affine(),
+        );
 
         let mut ts1 = Transcript::<Sha256>::default();
         let mut ts2 = Transcript::<Sha256>::default();
@@ -195,8 +210,8 @@ mod tests {
         ts1.feed(&commitment2);
         ts2.feed(&commitment1);
         ts2.feed(&commitment2);
-        let [x, y, z] = ts1.generate_challenges();
-        let [x1, y1, z1] = ts2.generate_challenges();
+        let [x, y, z]: [Fr; 3] = ts1.generate_challenges();
+        let [x1, y1, z1]: [Fr; 3] = ts2.generate_challenges();
         assert_eq!(x, x1, "should be equal");
         assert_eq!(y, y1, "should be equal");
         assert_eq!(z, z1, "should be equal");
@@ -205,10 +220,14 @@ mod tests {
     #[test]
     #[should_panic]
     fn safe_guard() {
-        let commitment1 = KzgCommitment(G1Point::generator().mul(Fr::from(1)).into_affine());
-        let commitments1: [KzgCommitment; 1] = [commitment1.clone()];
+        let commitment1: KzgCommitment<Bls12_381> = KzgCommitment(
+            G1Point::<Bls12_381>::generator()
+                .mul(Fr::from(1))
+                .into_affine(),
+        );
+        let commitments1: [KzgCommitment<Bls12_381>; 1] = [commitment1.clone()];
         let mut generator = Transcript::<Sha256>::from_commitment(&commitments1);
-        let [_a, _aa, _aaa] = generator.generate_challenges();
-        let [_a, _aa, _aaa] = generator.generate_challenges();
+        let [_a, _aa, _aaa]: [Fr; 3] = generator.generate_challenges();
+        let [_a, _aa, _aaa]: [Fr; 3] = generator.generate_challenges();
     }
 }
@@ -1,6 +1,7 @@
-use ark_bls12_381::Fr;
+use ark_bls12_381::{Bls12_381, Fr};
 use sha2::Sha256;
 
+use kzg::scheme::KzgScheme;
 use kzg::srs::Srs;
 use plonk::circuit::Circuit;
 use plonk::prover;
@@ -57,9 +58,9 @@ fn main() {
     let compiled_circuit = circuit.compile().unwrap();
 
     // generate proof
-    let srs = Srs::new(compiled_circuit.size);
-    let proof = prover::generate_proof::<Sha256>(&compiled_circuit, srs.clone());
+    let scheme = KzgScheme::new(Srs::<Bls12_381>::new(compiled_circuit.size));
+    let proof = prover::generate_proof::<_, Sha256>(&compiled_circuit, &scheme).unwrap();
 
     // verify proof
-    assert!(verifier::verify::<Sha256>(&compiled_circuit, srs, proof).is_ok());
+    assert!(verifier::verify::<_, Sha256>(&compiled_circuit, &scheme, proof).is_ok());
 }
@@ -1,17 +1,14 @@
 use std::marker::PhantomData;
 
-use ark_bls12_381::Fr;
-use ark_ff::{UniformRand, Zero};
+use ark_ff::PrimeField;
 use ark_serialize::{CanonicalSerialize, Write};
 use ark_std::rand::rngs::StdRng;
 use ark_std::rand::SeedableRng;
 use sha2::Digest;
 
-use kzg::commitment::KzgCommitment;
-
-/// Generates Fiat-Shamir challenges for the KZG scheme.
+/// Generates Fiat-Shamir challenges for the polynomial commitment scheme.
 ///
-/// The `ChallengeGenerator` struct is responsible for generating challenges used in the KZG scheme.
+/// The `ChallengeGenerator` struct is responsible for generating challenges used in the proof system.
 #[derive(Clone, Default)]
 pub struct ChallengeGenerator<T: Digest + Default> {
     data: Option<Vec<u8>>,
@@ -22,18 +19,18 @@ pub struct ChallengeGenerator<T: Digest + Default> {
 }
 
 impl<T: Digest + Default> ChallengeGenerator<T> {
-    /// Creates a new `ChallengeGenerator` from a slice of KZG commitments.
+    /// Creates a new `ChallengeGenerator` from a slice of commitments.
     ///
     /// # Arguments
     ///
-    /// * `kzg_commitments` - A slice of KZG commitments used to initialize the generator.
+    /// * `commitments` - A slice of commitments used to initialize the generator.
     ///
     /// # Returns
     ///
     /// A `ChallengeGenerator` initialized with the provided commitments.
-    pub fn from_commitments(kzg_commitments: &[KzgCommitment]) -> Self {
+    pub fn from_commitments<C: CanonicalSerialize>(commitments: &[C]) -> Self {
         let mut challenge_generator = Self::default();
-        for commitment in kzg_commitments {
+        for commitment in commitments {
             challenge_generator.feed(commitment);
         }
         challenge_generator
@@ -41,17 +38,15 @@ impl<T: Digest + Default> ChallengeGenerator<T> {
 }
 
 impl<T: Digest + Default> ChallengeGenerator<T> {
-    /// Feeds a commitment to the challenge generator.
+    /// Feeds a commitment, opening or evaluation to the challenge generator.
     ///
     /// # Parameters
     ///
-    /// - `kzg_commitment`: The commitment to feed to the generator.
-    pub fn feed(&mut self, kzg_commitment: &KzgCommitment) {
+    /// - `item`: The item to feed to the generator.
+    pub fn feed<C: CanonicalSerialize>(&mut self, item: &C) {
         let mut hasher = T::default();
         hasher.update(self.data.take().unwrap_or_default());
-        kzg_commitment
-            .inner()
-            .serialize_uncompressed(HashMarshaller(&mut hasher))
+        item.serialize_uncompressed(HashMarshaller(&mut hasher))
             .expect("HashMarshaller::serialize_uncompressed should be infallible!");
         self.data = Some(hasher.finalize().to_vec());
         self.generated = false;
@@ -79,11 +74,11 @@ impl<T: Digest + Default> ChallengeGenerator<T> {
     /// # Returns
     ///
     /// An array of generated challenges.
-    pub fn generate_challenges<const N: usize>(&mut self) -> [Fr; N] {
+    pub fn generate_challenges<F: PrimeField, const N: usize>(&mut self) -> [F; N] {
         let mut rng = self.generate_rng_with_seed();
-        let mut points = [Fr::zero(); N];
+        let mut points = [F::zero(); N];
         for point in &mut points {
-            *point = Fr::rand(&mut rng);
+            *point = F::rand(&mut rng);
         }
         points
     }
@@ -108,29 +103,42 @@ impl<'a, H: Digest> Write for HashMarshaller<'a, H> {
 
 #[cfg(test)]
 mod tests {
+    use ark_bls12_381::{Bls12_381, Fr};
     use ark_ec::{AffineRepr, CurveGroup};
     use sha2::Sha256;
     use std::ops::Mul;
 
+    use kzg::commitment::KzgCommitment;
+
     use crate::types::G1Point;
 
     use super::*;
 
     #[test]
     fn aggregation_digest_test() {
-        let commitment1 = KzgCommitment(G1Point::generator().mul(Fr::from(1)).into_affine());
-        let commitment2 = KzgCommitment(G1Point::generator().mul(Fr::from(2)).into_affine());
-        let commitments1: [KzgCommitment; 2] = [commitment1.clone(), commitment2.clone()];
-        let [a, aa, aaa] =
+        let commitment1 = KzgCommitment(
+            G1Point::<Bls12_381>::generator()
+                .mul(Fr::from(1))
+                .into_affine(),
+        );
+        let commitment2 = KzgCommitment(
+            G1Point::<Bls12_381>::generator()
+                .mul(Fr::from(2))
+                .into_affine(),
+        );
+        let commitments1: [KzgCommitment<Bls12_381>; 2] =
+            [commitment1.clone(), commitment2.clone()];
+        let [a, aa, aaa]: [Fr; 3] =
             ChallengeGenerator::<Sha256>::from_commitments(&commitments1).generate_challenges();
 
-        let commitments2: [KzgCommitment; 1] = [commitment2.clone()];
-        let [b] =
+        let commitments2: [KzgCommitment<Bls12_381>; 1] = [commitment2.clone()];
+        let [b]: [Fr; 1] =
             ChallengeGenerator::<Sha256>::from_commitments(&commitments2).generate_challenges();
         assert_ne!(a, b, "should be different");
 
-        let commitments3: [KzgCommitment; 2] = [commitment1.clone(), commitment2.clone()];
-        let [c, cc, ccc] =
+        let commitments3: [KzgCommitment<Bls12_381>; 2] =
+            [commitment1.clone(), commitment2.clone()];
+        let [c, cc, ccc]: [Fr; 3] =
             ChallengeGenerator::<Sha256>::from_commitments(&commitments3).generate_challenges();
         assert_eq!(a, c, "should be equal");
         assert_eq!(aa, cc, "should be equal");
@@ -140,10 +148,14 @@ mod tests {
     #[test]
     #[should_panic]
     fn safe_guard() {
-        let commitment1 = KzgCommitment(G1Point::generator().mul(Fr::from(1)).into_affine());
-        let commitments1: [KzgCommitment; 1] = [commitment1.clone()];
+        let commitment1 = KzgCommitment(
+            G1Point::<Bls12_381>::generator()
+                .mul(Fr::from(1))
+                .into_affine(),
+        );
+        let commitments1: [KzgCommitment<Bls12_381>; 1] = [commitment1.clone()];
         let mut generator = ChallengeGenerator::<Sha256>::from_commitments(&commitments1);
-        let [_a, _aa, _aaa] = generator.generate_challenges();
-        let [_a, _aa, _aaa] = generator.generate_challenges();
+        let [_a, _aa, _aaa]: [Fr; 3] = generator.generate_challenges();
+        let [_a, _aa, _aaa]: [Fr; 3] = generator.generate_challenges();
     }
 }
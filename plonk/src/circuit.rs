@@ -1,8 +1,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use ark_bls12_381::Fr;
-use ark_ff::One;
+use ark_ff::PrimeField;
 use ark_poly::{EvaluationDomain, Evaluations, GeneralEvaluationDomain};
 
 use crate::compiled_circuit::CompiledCircuit;
@@ -11,12 +10,12 @@ use crate::gate::{Gate, Position};
 
 /// Represents a circuit consisting of gates and values.
 #[derive(PartialEq, Debug)]
-pub struct Circuit {
-    gates: Vec<Gate>,
-    vals: Vec<Arc<Vec<Fr>>>,
+pub struct Circuit<F: PrimeField> {
+    gates: Vec<Gate<F>>,
+    vals: Vec<Arc<Vec<F>>>,
 }
 
-impl Default for Circuit {
+impl<F: PrimeField> Default for Circuit<F> {
     fn default() -> Self {
         Self {
             gates: Vec::default(),
@@ -29,7 +28,7 @@ impl Default for Circuit {
     }
 }
 
-impl Circuit {
+impl<F: PrimeField> Circuit<F> {
     pub const VEC_A: &'static str = "vec_a";
     pub const VEC_B: &'static str = "vec_b";
     pub const VEC_C: &'static str = "vec_c";
@@ -41,15 +40,15 @@ impl Circuit {
     pub const VEC_PI: &'static str = "vec_pi";
 }
 
-impl Circuit {
+impl<F: PrimeField> Circuit<F> {
     /// Adds a gate to the circuit.
     fn add_gate(
         &mut self,
-        a: (usize, usize, Fr),
-        b: (usize, usize, Fr),
-        c: (usize, usize, Fr),
+        a: (usize, usize, F),
+        b: (usize, usize, F),
+        c: (usize, usize, F),
         gate_type: GateType,
-        pi: Fr,
+        pi: F,
     ) {
         // Push the values to the corresponding vectors
         Arc::get_mut(&mut self.vals[0]).unwrap().push(a.2);
@@ -84,10 +83,10 @@ impl Circuit {
     /// Adds an addition gate to the circuit.
     pub fn add_addition_gate(
         &mut self,
-        a: (usize, usize, Fr),
-        b: (usize, usize, Fr),
-        c: (usize, usize, Fr),
-        pi: Fr,
+        a: (usize, usize, F),
+        b: (usize, usize, F),
+        c: (usize, usize, F),
+        pi: F,
     ) {
         self.add_gate(a, b, c, GateType::Addition, pi);
     }
@@ -95,10 +94,10 @@ impl Circuit {
     /// Adds a multiplication gate to the circuit.
     pub fn add_multiplication_gate(
         &mut self,
-        a: (usize, usize, Fr),
-        b: (usize, usize, Fr),
-        c: (usize, usize, Fr),
-        pi: Fr,
+        a: (usize, usize, F),
+        b: (usize, usize, F),
+        c: (usize, usize, F),
+        pi: F,
     ) {
         self.add_gate(a, b, c, GateType::Multiplication, pi);
     }
@@ -106,26 +105,26 @@ impl Circuit {
     /// Adds a constant gate to the circuit.
     pub fn add_constant_gate(
         &mut self,
-        a: (usize, usize, Fr),
-        b: (usize, usize, Fr),
-        c: (usize, usize, Fr),
-        pi: Fr,
+        a: (usize, usize, F),
+        b: (usize, usize, F),
+        c: (usize, usize, F),
+        pi: F,
     ) {
         self.add_gate(a, b, c, GateType::Constant, pi);
     }
 
     /// Gets the assignment of the circuit.
-    pub(crate) fn get_assignment(&self) -> HashMap<&'static str, Vec<Fr>> {
+    pub(crate) fn get_assignment(&self) -> HashMap<&'static str, Vec<F>> {
         let mut result = HashMap::default();
-        result.insert(Circuit::VEC_A, vec![]);
-        result.insert(Circuit::VEC_B, vec![]);
-        result.insert(Circuit::VEC_C, vec![]);
-        result.insert(Circuit::VEC_QL, vec![]);
-        result.insert(Circuit::VEC_QR, vec![]);
-        result.insert(Circuit::VEC_QM, vec![]);
-        result.insert(Circuit::VEC_QO, vec![]);
-        result.insert(Circuit::VEC_QC, vec![]);
-        result.insert(Circuit::VEC_PI, vec![]);
+        result.insert(Self::VEC_A, vec![]);
+        result.insert(Self::VEC_B, vec![]);
+        result.insert(Self::VEC_C, vec![]);
+        result.insert(Self::VEC_QL, vec![]);
+        result.insert(Self::VEC_QR, vec![]);
+        result.insert(Self::VEC_QM, vec![]);
+        result.insert(Self::VEC_QO, vec![]);
+        result.insert(Self::VEC_QC, vec![]);
+        result.insert(Self::VEC_PI, vec![]);
 
         for (i, gate) in self.gates.iter().enumerate() {
             if gate.is_dummy_gate() {
@@ -163,11 +162,11 @@ impl Circuit {
     }
 
     /// Compiles the circuit into a compiled circuit.
-    pub fn compile(mut self) -> Result<CompiledCircuit, String> {
+    pub fn compile(mut self) -> Result<CompiledCircuit<F>, String> {
         self.pad_circuit();
 
         let circuit_size = self.gates.len();
-        let domain = GeneralEvaluationDomain::<Fr>::new(circuit_size).unwrap();
+        let domain = GeneralEvaluationDomain::<F>::new(circuit_size).unwrap();
         let assignment = self.get_assignment();
 
         let mut interpolated_assignment = assignment
@@ -176,15 +175,15 @@ impl Circuit {
             .collect::<HashMap<_, _>>();
 
         let gate_constraints = GateConstraints::new(
-            interpolated_assignment.remove(Circuit::VEC_A).unwrap(),
-            interpolated_assignment.remove(Circuit::VEC_B).unwrap(),
-            interpolated_assignment.remove(Circuit::VEC_C).unwrap(),
-            interpolated_assignment.remove(Circuit::VEC_QL).unwrap(),
-            interpolated_assignment.remove(Circuit::VEC_QR).unwrap(),
-            interpolated_assignment.remove(Circuit::VEC_QO).unwrap(),
-            interpolated_assignment.remove(Circuit::VEC_QM).unwrap(),
-            interpolated_assignment.remove(Circuit::VEC_QC).unwrap(),
-            interpolated_assignment.remove(Circuit::VEC_PI).unwrap(),
+            interpolated_assignment.remove(Self::VEC_A).unwrap(),
+            interpolated_assignment.remove(Self::VEC_B).unwrap(),
+            interpolated_assignment.remove(Self::VEC_C).unwrap(),
+            interpolated_assignment.remove(Self::VEC_QL).unwrap(),
+            interpolated_assignment.remove(Self::VEC_QR).unwrap(),
+            interpolated_assignment.remove(Self::VEC_QO).unwrap(),
+            interpolated_assignment.remove(Self::VEC_QM).unwrap(),
+            interpolated_assignment.remove(Self::VEC_QC).unwrap(),
+            interpolated_assignment.remove(Self::VEC_PI).unwrap(),
         );
 
         let copy_constraints = self.cal_permutation();
@@ -197,9 +196,9 @@ impl Circuit {
     }
 
     /// Calculates the Copy constraints.
-    fn cal_permutation(&self) -> CopyConstraints {
+    fn cal_permutation(&self) -> CopyConstraints<F> {
         let len = self.gates.len();
-        let domain = GeneralEvaluationDomain::<Fr>::new(len).unwrap();
+        let domain = GeneralEvaluationDomain::<F>::new(len).unwrap();
         let roots = domain.elements().collect::<Vec<_>>();
         let (coset1, coset2, k1, k2) = self.find_cosets(&roots);
 
@@ -213,7 +212,7 @@ impl Circuit {
                 continue;
             }
 
-            let map_element = |pos: &Position| -> Fr {
+            let map_element = |pos: &Position| -> F {
                 match pos {
                     Position::Pos(0, i_2) => roots[*i_2],
                     Position::Pos(1, i_2) => coset1[*i_2],
@@ -235,9 +234,9 @@ impl Circuit {
     }
 
     /// Finds the cosets for permutation.
-    fn find_cosets(&self, roots: &[Fr]) -> (Vec<Fr>, Vec<Fr>, Fr, Fr) {
-        let k1 = roots[0] + Fr::one();
-        let k2 = k1 + Fr::one();
+    fn find_cosets(&self, roots: &[F]) -> (Vec<F>, Vec<F>, F, F) {
+        let k1 = roots[0] + F::one();
+        let k2 = k1 + F::one();
         let coset1 = roots.iter().map(|root| *root * k1).collect();
         let coset2 = roots.iter().map(|root| *root * k2).collect();
 
@@ -245,8 +244,17 @@ impl Circuit {
     }
 }
 
+/// Enum representing different types of gates.
+enum GateType {
+    Addition,
+    Multiplication,
+    Constant,
+}
+
 #[cfg(test)]
 mod tests {
+    use ark_bls12_381::Fr;
+
     use super::*;
 
     #[test]
@@ -280,10 +288,3 @@ mod tests {
         assert_eq!(circuit.vals[0][2], circuit.vals[2][1]);
     }
 }
-
-/// Enum representing different types of gates.
-enum GateType {
-    Addition,
-    Multiplication,
-    Constant,
-}
@@ -1,7 +1,6 @@
 use std::collections::HashMap;
 
-use ark_bls12_381::Fr;
-use ark_ff::One;
+use ark_ff::PrimeField;
 use ark_poly::univariate::DensePolynomial;
 use ark_poly::{EvaluationDomain, Evaluations, GeneralEvaluationDomain};
 
@@ -10,11 +9,11 @@ use crate::gate::{Gate, Position};
 
 /// Represents a circuit consisting of gates and values.
 #[derive(PartialEq, Debug, Default)]
-pub struct CPICircuit {
-    gates: Vec<Gate>,
+pub struct CPICircuit<F: PrimeField> {
+    gates: Vec<Gate<F>>,
 }
 
-impl CPICircuit {
+impl<F: PrimeField> CPICircuit<F> {
     // Constants for vector names
     pub const VEC_A: &'static str = "vec_a";
     pub const VEC_B: &'static str = "vec_b";
@@ -27,14 +26,14 @@ impl CPICircuit {
     pub const VEC_PI: &'static str = "vec_pi";
 }
 
-impl CPICircuit {
+impl<F: PrimeField> CPICircuit<F> {
     /// Adds an addition gate to the circuit.
     pub fn add_addition_gate(
         mut self,
         a: (usize, usize),
         b: (usize, usize),
         c: (usize, usize),
-        pi: Fr,
+        pi: F,
     ) -> Self {
         self.gates.push(Gate::new_add_gate(
             Position::Pos(a.0, a.1),
@@ -51,7 +50,7 @@ impl CPICircuit {
         a: (usize, usize),
         b: (usize, usize),
         c: (usize, usize),
-        pi: Fr,
+        pi: F,
     ) -> Self {
         self.gates.push(Gate::new_mul_gate(
             Position::Pos(a.0, a.1),
@@ -68,8 +67,8 @@ impl CPICircuit {
         a: (usize, usize),
         b: (usize, usize),
         c: (usize, usize),
-        value: Fr,
-        pi: Fr,
+        value: F,
+        pi: F,
     ) -> Self {
         self.gates.push(Gate::new_constant_gate(
             Position::Pos(a.0, a.1),
@@ -87,7 +86,7 @@ impl CPICircuit {
     }
 
     /// Gets the assignment of the circuit.
-    pub(crate) fn get_assignment(&self) -> HashMap<&str, Vec<Fr>> {
+    pub(crate) fn get_assignment(&self) -> HashMap<&str, Vec<F>> {
         let mut result = HashMap::default();
         result.insert(Self::VEC_QL, vec![]);
         result.insert(Self::VEC_QR, vec![]);
@@ -111,12 +110,12 @@ impl CPICircuit {
     }
 
     /// Finds the cosets for permutation.
-    fn find_cosets(&self, len: usize) -> (Vec<Fr>, Vec<Fr>, Fr, Fr) {
-        let domain = GeneralEvaluationDomain::<Fr>::new(len).unwrap();
+    fn find_cosets(&self, len: usize) -> (Vec<F>, Vec<F>, F, F) {
+        let domain = GeneralEvaluationDomain::<F>::new(len).unwrap();
         let roots = domain.elements().collect::<Vec<_>>();
 
-        let k1 = roots.first().unwrap() + &Fr::one();
-        let k2 = k1 + Fr::one();
+        let k1 = *roots.first().unwrap() + F::one();
+        let k2 = k1 + F::one();
         let coset1 = roots.iter().map(|root| *root * k1).collect();
         let coset2 = roots.iter().map(|root| *root * k2).collect();
 
@@ -124,9 +123,9 @@ impl CPICircuit {
     }
 
     /// Calculates the Copy constraints.
-    fn cal_permutation(&self) -> CopyConstraints {
+    fn cal_permutation(&self) -> CopyConstraints<F> {
         let len = self.gates.len();
-        let domain = GeneralEvaluationDomain::<Fr>::new(len).unwrap();
+        let domain = GeneralEvaluationDomain::<F>::new(len).unwrap();
         let roots = domain.elements().collect::<Vec<_>>();
         let (coset1, coset2, k1, k2) = self.find_cosets(len);
 
@@ -139,7 +138,7 @@ impl CPICircuit {
                 continue;
             }
 
-            let map_element = |pos: &Position| -> Fr {
+            let map_element = |pos: &Position| -> F {
                 match pos {
                     Position::Pos(i_1, i_2) => {
                         if *i_1 == 0 {
@@ -181,12 +180,12 @@ impl CPICircuit {
     }
 
     /// Compiles the circuit into a compiled circuit.
-    pub fn compile(mut self) -> Result<(GateConstraints, CopyConstraints, usize), String> {
+    pub fn compile(mut self) -> Result<(GateConstraints<F>, CopyConstraints<F>, usize), String> {
         self = self.pad_circuit();
 
         let circuit_size = self.gates.len();
 
-        let domain = GeneralEvaluationDomain::<Fr>::new(circuit_size).unwrap();
+        let domain = GeneralEvaluationDomain::<F>::new(circuit_size).unwrap();
         let assignment = self.get_assignment();
 
         let mut interpolated_assignment = assignment
@@ -195,9 +194,9 @@ impl CPICircuit {
             .collect::<HashMap<_, _>>();
 
         let gate_constraints = GateConstraints::new(
-            DensePolynomial::<Fr>::default(),
-            DensePolynomial::<Fr>::default(),
-            DensePolynomial::<Fr>::default(),
+            DensePolynomial::<F>::default(),
+            DensePolynomial::<F>::default(),
+            DensePolynomial::<F>::default(),
             interpolated_assignment.remove(Self::VEC_QL).unwrap(),
             interpolated_assignment.remove(Self::VEC_QR).unwrap(),
             interpolated_assignment.remove(Self::VEC_QO).unwrap(),
@@ -211,7 +210,7 @@ impl CPICircuit {
         Ok((gate_constraints, copy_constraints, circuit_size))
     }
 
-    pub fn get_gates(&self) -> Vec<Gate> {
+    pub fn get_gates(&self) -> Vec<Gate<F>> {
         self.gates.clone()
     }
 }
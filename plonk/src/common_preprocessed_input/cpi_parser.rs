@@ -1,15 +1,15 @@
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 
-use ark_bls12_381::Fr;
+use ark_ff::PrimeField;
 use ark_poly::univariate::DensePolynomial;
 
-use kzg::commitment::KzgCommitment;
-use kzg::scheme::KzgScheme;
+use kzg::pcs::PolynomialCommitmentScheme;
 
 use crate::common_preprocessed_input::cpi_circuit::CPICircuit;
 use crate::common_preprocessed_input::cpi_parser::TypeOfCircuit::Multiplication;
 use crate::constraint::{CopyConstraints, GateConstraints};
+use crate::parser::constant_to_field;
 
 /// Enum defining the type of circuit gate
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
@@ -57,39 +57,55 @@ impl ParserWire {
 }
 
 /// Structure representing the common preprocessed input
-pub struct CommonPreprocessedInput {
+pub struct CommonPreprocessedInput<P: PolynomialCommitmentScheme> {
     pub n: usize,
-    pub k1: Fr,
-    pub k2: Fr,
-    pub com_q_lx: KzgCommitment,
-    pub com_q_rx: KzgCommitment,
-    pub com_q_mx: KzgCommitment,
-    pub com_q_ox: KzgCommitment,
-    pub com_q_cx: KzgCommitment,
-    pub com_s_sigma_1: KzgCommitment,
-    pub com_s_sigma_2: KzgCommitment,
-    pub com_s_sigma_3: KzgCommitment,
-    pub pi_x: DensePolynomial<Fr>,
+    pub k1: P::Field,
+    pub k2: P::Field,
+    pub com_q_lx: P::Commitment,
+    pub com_q_rx: P::Commitment,
+    pub com_q_mx: P::Commitment,
+    pub com_q_ox: P::Commitment,
+    pub com_q_cx: P::Commitment,
+    pub com_s_sigma_1: P::Commitment,
+    pub com_s_sigma_2: P::Commitment,
+    pub com_s_sigma_3: P::Commitment,
+    pub pi_x: DensePolynomial<P::Field>,
 }
 
-impl CommonPreprocessedInput {
+impl<P: PolynomialCommitmentScheme> CommonPreprocessedInput<P> {
     pub fn new(
-        compiled_circuit: (GateConstraints, CopyConstraints, usize),
-        scheme: KzgScheme,
-    ) -> Self {
+        compiled_circuit: (GateConstraints<P::Field>, CopyConstraints<P::Field>, usize),
+        scheme: P,
+    ) -> Result<Self, String> {
         let copy_constraint = compiled_circuit.1;
         let gate_constraint = compiled_circuit.0;
 
-        let com_q_mx = scheme.commit(gate_constraint.q_mx());
-        let com_q_lx = scheme.commit(gate_constraint.q_lx());
-        let com_q_rx = scheme.commit(gate_constraint.q_rx());
-        let com_q_ox = scheme.commit(gate_constraint.q_ox());
-        let com_q_cx = scheme.commit(gate_constraint.q_cx());
-        let com_s_sigma_1 = scheme.commit(copy_constraint.s_sigma_1());
-        let com_s_sigma_2 = scheme.commit(copy_constraint.s_sigma_2());
-        let com_s_sigma_3 = scheme.commit(copy_constraint.s_sigma_3());
-
-        Self {
+        let com_q_mx = scheme
+            .commit(gate_constraint.q_mx())
+            .map_err(|error| error.to_string())?;
+        let com_q_lx = scheme
+            .commit(gate_constraint.q_lx())
+            .map_err(|error| error.to_string())?;
+        let com_q_rx = scheme
+            .commit(gate_constraint.q_rx())
+            .map_err(|error| error.to_string())?;
+        let com_q_ox = scheme
+            .commit(gate_constraint.q_ox())
+            .map_err(|error| error.to_string())?;
+        let com_q_cx = scheme
+            .commit(gate_constraint.q_cx())
+            .map_err(|error| error.to_string())?;
+        let com_s_sigma_1 = scheme
+            .commit(copy_constraint.s_sigma_1())
+            .map_err(|error| error.to_string())?;
+        let com_s_sigma_2 = scheme
+            .commit(copy_constraint.s_sigma_2())
+            .map_err(|error| error.to_string())?;
+        let com_s_sigma_3 = scheme
+            .commit(copy_constraint.s_sigma_3())
+            .map_err(|error| error.to_string())?;
+
+        Ok(Self {
             n: compiled_circuit.2,
             k1: *copy_constraint.k1(),
             k2: *copy_constraint.k2(),
@@ -102,7 +118,7 @@ impl CommonPreprocessedInput {
             com_s_sigma_2,
             com_s_sigma_3,
             pi_x: gate_constraint.pi_x().clone(),
-        }
+        })
     }
 }
 
@@ -112,18 +128,19 @@ pub struct CPIGenerator {}
 
 impl CPIGenerator {
     /// Compute common preprocessed input from string input
-    pub fn compute_common_preprocessed_input(
+    pub fn compute_common_preprocessed_input<P: PolynomialCommitmentScheme>(
         self,
         input: &str,
-        scheme: KzgScheme,
-    ) -> Result<CommonPreprocessedInput, String> {
+        scheme: P,
+    ) -> Result<CommonPreprocessedInput<P>, String> {
         let input = Self::normalize(input);
         let (gate_list, position_map) = self.prepare_generation(&input);
         let circuit = Self::gen_circuit(gate_list, position_map);
-        Ok(CommonPreprocessedInput::new(circuit.compile()?, scheme))
+        CommonPreprocessedInput::new(circuit.compile()?, scheme)
     }
 
     /// Prepare generation of gates and position map
+    #[allow(clippy::type_complexity)]
     fn prepare_generation(
         &self,
         string: &str,
@@ -223,10 +240,10 @@ impl CPIGenerator {
     }
 
     /// Generate the circuit with gates and position map
-    fn gen_circuit(
+    fn gen_circuit<F: PrimeField>(
         gate_list: Vec<ParserGate>,
         position_map: HashMap<String, Vec<(usize, usize)>>,
-    ) -> CPICircuit {
+    ) -> CPICircuit<F> {
         let mut result = CPICircuit::default();
         let mut position_map = position_map
             .into_iter()
@@ -258,18 +275,18 @@ impl CPIGenerator {
             let bottom = (bottom.0, bottom.1);
             match &gate.type_of_circuit {
                 TypeOfCircuit::Addition => {
-                    result = result.add_addition_gate(left, right, bottom, Fr::from(0));
+                    result = result.add_addition_gate(left, right, bottom, F::zero());
                 }
                 TypeOfCircuit::Multiplication => {
-                    result = result.add_multiplication_gate(left, right, bottom, Fr::from(0));
+                    result = result.add_multiplication_gate(left, right, bottom, F::zero());
                 }
                 TypeOfCircuit::Constant => {
                     result = result.add_constant_gate(
                         left,
                         right,
                         bottom,
-                        Fr::from(gate.left.value_string.parse::<i32>().unwrap()),
-                        Fr::from(0),
+                        constant_to_field(gate.left.value_string.parse::<i32>().unwrap()),
+                        F::zero(),
                     );
                 }
             }
@@ -424,7 +441,7 @@ impl CPIGenerator {
 mod tests {
     use crate::common_preprocessed_input::cpi_parser::CPIGenerator;
     use crate::parser::Parser;
-    use ark_bls12_381::Fr;
+    use ark_bls12_381::{Bls12_381, Fr};
     use kzg::scheme::KzgScheme;
     use kzg::srs::Srs;
 
@@ -433,7 +450,7 @@ mod tests {
     fn parser_prover_test() {
         let str = "x*y+3*x^2+x*y*z=11";
 
-        let srs = Srs::new(20);
+        let srs = Srs::<Bls12_381>::new(20);
 
         let scheme = KzgScheme::new(srs.clone());
         let scheme1 = KzgScheme::new(srs.clone());
@@ -456,35 +473,41 @@ mod tests {
         assert_eq!(cpi.k2, copy_constraint.k2().clone());
         assert_eq!(
             cpi.com_q_lx,
-            scheme1.commit(&gate_constraint.q_lx().clone())
+            scheme1.commit(&gate_constraint.q_lx().clone()).unwrap()
         );
         assert_eq!(
             cpi.com_q_rx,
-            scheme1.commit(&gate_constraint.q_rx().clone())
+            scheme1.commit(&gate_constraint.q_rx().clone()).unwrap()
         );
         assert_eq!(
             cpi.com_q_mx,
-            scheme1.commit(&gate_constraint.q_mx().clone())
+            scheme1.commit(&gate_constraint.q_mx().clone()).unwrap()
         );
         assert_eq!(
             cpi.com_q_ox,
-            scheme1.commit(&gate_constraint.q_ox().clone())
+            scheme1.commit(&gate_constraint.q_ox().clone()).unwrap()
         );
         assert_eq!(
             cpi.com_q_cx,
-            scheme1.commit(&gate_constraint.q_cx().clone())
+            scheme1.commit(&gate_constraint.q_cx().clone()).unwrap()
         );
         assert_eq!(
             cpi.com_s_sigma_1,
-            scheme1.commit(&copy_constraint.s_sigma_1().clone())
+            scheme1
+                .commit(&copy_constraint.s_sigma_1().clone())
+                .unwrap()
         );
         assert_eq!(
             cpi.com_s_sigma_2,
-            scheme1.commit(&copy_constraint.s_sigma_2().clone())
+            scheme1
+                .commit(&copy_constraint.s_sigma_2().clone())
+                .unwrap()
         );
         assert_eq!(
             cpi.com_s_sigma_3,
-            scheme1.commit(&copy_constraint.s_sigma_3().clone())
+            scheme1
+                .commit(&copy_constraint.s_sigma_3().clone())
+                .unwrap()
         );
         assert_eq!(cpi.pi_x, gate_constraint.pi_x().clone());
     }
@@ -1,16 +1,18 @@
+use ark_ff::PrimeField;
+
 use crate::constraint::{CopyConstraints, GateConstraints};
 
 /// Represents a compiled circuit with gate and copy constraints.
 #[derive(Debug)]
-pub struct CompiledCircuit {
+pub struct CompiledCircuit<F: PrimeField> {
     /// The size of the compiled circuit.
     pub size: usize,
 
-    gate_constraint: GateConstraints,
-    copy_constraint: CopyConstraints,
+    gate_constraint: GateConstraints<F>,
+    copy_constraint: CopyConstraints<F>,
 }
 
-impl CompiledCircuit {
+impl<F: PrimeField> CompiledCircuit<F> {
     /// Creates a new `CompiledCircuit` instance.
     ///
     /// # Parameters
@@ -19,8 +21,8 @@ impl CompiledCircuit {
     /// - `copy_constraint`: The copy constraints of the compiled circuit.
     /// - `size`: The size of the compiled circuit.
     pub fn new(
-        gate_constraint: GateConstraints,
-        copy_constraint: CopyConstraints,
+        gate_constraint: GateConstraints<F>,
+        copy_constraint: CopyConstraints<F>,
         size: usize,
     ) -> Self {
         Self {
@@ -31,12 +33,12 @@ impl CompiledCircuit {
     }
 
     /// Returns a reference to the gate constraints of the compiled circuit.
-    pub fn gate_constraints(&self) -> &GateConstraints {
+    pub fn gate_constraints(&self) -> &GateConstraints<F> {
         &self.gate_constraint
     }
 
     /// Returns a reference to the copy constraints of the compiled circuit.
-    pub fn copy_constraints(&self) -> &CopyConstraints {
+    pub fn copy_constraints(&self) -> &CopyConstraints<F> {
         &self.copy_constraint
     }
 }
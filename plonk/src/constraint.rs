@@ -1,43 +1,43 @@
-use ark_bls12_381::Fr;
+use ark_ff::PrimeField;
 
 use crate::types::Polynomial;
 
 /// Represents gate constraints for a compiled circuit.
 #[derive(Debug, PartialEq)]
-pub struct GateConstraints {
+pub struct GateConstraints<F: PrimeField> {
     /// Polynomial representing the constraint function for the A wire.
-    f_ax: Polynomial,
+    f_ax: Polynomial<F>,
     /// Polynomial representing the constraint function for the B wire.
-    f_bx: Polynomial,
+    f_bx: Polynomial<F>,
     /// Polynomial representing the constraint function for the C wire.
-    f_cx: Polynomial,
+    f_cx: Polynomial<F>,
     /// Polynomial representing the constraint function for the Q_L.
-    q_lx: Polynomial,
+    q_lx: Polynomial<F>,
     /// Polynomial representing the constraint function for the Q_R.
-    q_rx: Polynomial,
+    q_rx: Polynomial<F>,
     /// Polynomial representing the constraint function for the Q_O.
-    q_ox: Polynomial,
+    q_ox: Polynomial<F>,
     /// Polynomial representing the constraint function for the Q_M.
-    q_mx: Polynomial,
+    q_mx: Polynomial<F>,
     /// Polynomial representing the constraint function for the Q_C.
-    q_cx: Polynomial,
+    q_cx: Polynomial<F>,
     /// Polynomial representing the constraint function for the Public input Pi.
-    pi_x: Polynomial,
+    pi_x: Polynomial<F>,
 }
 
-impl GateConstraints {
+impl<F: PrimeField> GateConstraints<F> {
     /// Creates a new instance of `GateConstraints`.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
-        f_ax: Polynomial,
-        f_bx: Polynomial,
-        f_cx: Polynomial,
-        q_lx: Polynomial,
-        q_rx: Polynomial,
-        q_ox: Polynomial,
-        q_mx: Polynomial,
-        q_cx: Polynomial,
-        pi_x: Polynomial,
+        f_ax: Polynomial<F>,
+        f_bx: Polynomial<F>,
+        f_cx: Polynomial<F>,
+        q_lx: Polynomial<F>,
+        q_rx: Polynomial<F>,
+        q_ox: Polynomial<F>,
+        q_mx: Polynomial<F>,
+        q_cx: Polynomial<F>,
+        pi_x: Polynomial<F>,
     ) -> Self {
         Self {
             f_ax,
@@ -53,58 +53,58 @@ impl GateConstraints {
     }
 
     // Accessor methods for each constraint polynomial
-    pub fn f_ax(&self) -> &Polynomial {
+    pub fn f_ax(&self) -> &Polynomial<F> {
         &self.f_ax
     }
-    pub fn f_bx(&self) -> &Polynomial {
+    pub fn f_bx(&self) -> &Polynomial<F> {
         &self.f_bx
     }
-    pub fn f_cx(&self) -> &Polynomial {
+    pub fn f_cx(&self) -> &Polynomial<F> {
         &self.f_cx
     }
-    pub fn q_lx(&self) -> &Polynomial {
+    pub fn q_lx(&self) -> &Polynomial<F> {
         &self.q_lx
     }
-    pub fn q_rx(&self) -> &Polynomial {
+    pub fn q_rx(&self) -> &Polynomial<F> {
         &self.q_rx
     }
-    pub fn q_ox(&self) -> &Polynomial {
+    pub fn q_ox(&self) -> &Polynomial<F> {
         &self.q_ox
     }
-    pub fn q_mx(&self) -> &Polynomial {
+    pub fn q_mx(&self) -> &Polynomial<F> {
         &self.q_mx
     }
-    pub fn q_cx(&self) -> &Polynomial {
+    pub fn q_cx(&self) -> &Polynomial<F> {
         &self.q_cx
     }
-    pub fn pi_x(&self) -> &Polynomial {
+    pub fn pi_x(&self) -> &Polynomial<F> {
         &self.pi_x
     }
 }
 
 /// Represents copy constraints for a compiled circuit.
 #[derive(Debug, PartialEq)]
-pub struct CopyConstraints {
+pub struct CopyConstraints<F: PrimeField> {
     /// Polynomial representing the first prescribed function (sigma_1).
-    s_sigma_1: Polynomial,
+    s_sigma_1: Polynomial<F>,
     /// Polynomial representing the second prescribed function (sigma_2).
-    s_sigma_2: Polynomial,
+    s_sigma_2: Polynomial<F>,
     /// Polynomial representing the third prescribed function (sigma_3).
-    s_sigma_3: Polynomial,
+    s_sigma_3: Polynomial<F>,
     /// First constant factor used in copy constraint calculations.
-    k1: Fr,
+    k1: F,
     /// Second constant factor used in copy constraint calculations.
-    k2: Fr,
+    k2: F,
 }
 
-impl CopyConstraints {
+impl<F: PrimeField> CopyConstraints<F> {
     /// Creates a new instance of `CopyConstraints`.
     pub fn new(
-        s_sigma_1: Polynomial,
-        s_sigma_2: Polynomial,
-        s_sigma_3: Polynomial,
-        k1: Fr,
-        k2: Fr,
+        s_sigma_1: Polynomial<F>,
+        s_sigma_2: Polynomial<F>,
+        s_sigma_3: Polynomial<F>,
+        k1: F,
+        k2: F,
     ) -> Self {
         Self {
             s_sigma_1,
@@ -116,23 +116,23 @@ impl CopyConstraints {
     }
 
     // Accessor methods for each constraint polynomial and constants
-    pub fn s_sigma_1(&self) -> &Polynomial {
+    pub fn s_sigma_1(&self) -> &Polynomial<F> {
         &self.s_sigma_1
     }
 
-    pub fn s_sigma_2(&self) -> &Polynomial {
+    pub fn s_sigma_2(&self) -> &Polynomial<F> {
         &self.s_sigma_2
     }
 
-    pub fn s_sigma_3(&self) -> &Polynomial {
+    pub fn s_sigma_3(&self) -> &Polynomial<F> {
         &self.s_sigma_3
     }
 
-    pub fn k1(&self) -> &Fr {
+    pub fn k1(&self) -> &F {
         &self.k1
     }
 
-    pub fn k2(&self) -> &Fr {
+    pub fn k2(&self) -> &F {
         &self.k2
     }
 }
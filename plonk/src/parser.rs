@@ -1,9 +1,7 @@
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
-use std::ops::Neg;
 
-use ark_bls12_381::Fr;
-use ark_ff::Zero;
+use ark_ff::PrimeField;
 
 use crate::circuit::Circuit;
 use crate::parser::TypeOfCircuit::*;
@@ -16,19 +14,19 @@ enum TypeOfCircuit {
 }
 
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
-struct Gate {
+struct Gate<F: PrimeField> {
     //Left branch of the circuit
-    pub left: Wire,
+    pub left: Wire<F>,
     //Right branch of the circuit
-    pub right: Wire,
+    pub right: Wire<F>,
     //Bottom part (result) of the circuit
-    pub bottom: Wire,
+    pub bottom: Wire<F>,
     // type 0: add, type 1: mul, type 2: const
     pub type_of_circuit: TypeOfCircuit,
 }
 
-impl Gate {
-    fn new(left: Wire, right: Wire, bottom: Wire, type_of_circuit: TypeOfCircuit) -> Self {
+impl<F: PrimeField> Gate<F> {
+    fn new(left: Wire<F>, right: Wire<F>, bottom: Wire<F>, type_of_circuit: TypeOfCircuit) -> Self {
         Gate {
             left,
             right,
@@ -38,19 +36,19 @@ impl Gate {
     }
 
     /// Change the result value of this gate
-    pub fn change_result(&mut self, value: Fr) {
+    pub fn change_result(&mut self, value: F) {
         self.bottom.value_fr = value;
     }
 }
 
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
-struct Wire {
+struct Wire<F: PrimeField> {
     value_string: String,
-    value_fr: Fr,
+    value_fr: F,
 }
 
-impl Wire {
-    fn new(value_string: String, value_fr: Fr) -> Self {
+impl<F: PrimeField> Wire<F> {
+    fn new(value_string: String, value_fr: F) -> Self {
         Wire {
             value_string,
             value_fr,
@@ -62,11 +60,11 @@ impl Wire {
 ///
 /// See parse function for usage
 #[derive(Default)]
-pub struct Parser {
-    pub witnesses: HashMap<String, Fr>,
+pub struct Parser<F: PrimeField> {
+    pub witnesses: HashMap<String, F>,
 }
 
-impl Parser {
+impl<F: PrimeField> Parser<F> {
     /// Add witness for the polynomial string
     ///
     /// ```
@@ -78,24 +76,25 @@ impl Parser {
     ///
     /// parser.parse("x=1");
     /// ```
-    pub fn add_witness(&mut self, variable: &str, value: Fr) {
+    pub fn add_witness(&mut self, variable: &str, value: F) {
         self.witnesses.insert(variable.to_string(), value);
     }
 
     /// Parse the input string to generate the circuit
-    pub fn parse(&self, input: &str) -> Circuit {
+    pub fn parse(&self, input: &str) -> Circuit<F> {
         let input = Self::parse_string(input);
         let (gate_list, position_map) = self.prepare_gen_circuit(&input);
         Self::gen_circuit(gate_list, position_map)
     }
 
     /// Generate [gate_list] and [position_map] to prepare for coordinate pair accumulator
+    #[allow(clippy::type_complexity)]
     fn prepare_gen_circuit(
         &self,
         string: &str,
-    ) -> (Vec<Gate>, HashMap<String, Vec<(usize, usize)>>) {
-        let gate_list: RefCell<Vec<Gate>> = RefCell::new(Vec::new());
-        let gate_set: RefCell<HashSet<Gate>> = RefCell::new(HashSet::new());
+    ) -> (Vec<Gate<F>>, HashMap<String, Vec<(usize, usize)>>) {
+        let gate_list: RefCell<Vec<Gate<F>>> = RefCell::new(Vec::new());
+        let gate_set: RefCell<HashSet<Gate<F>>> = RefCell::new(HashSet::new());
         //Map of integer key will be here, it will then be inserted into gen circuit method
         let position_map: RefCell<HashMap<String, Vec<(usize, usize)>>> =
             RefCell::new(HashMap::new());
@@ -127,7 +126,7 @@ impl Parser {
                             ),
                         )
                     })
-                    .collect::<Vec<Wire>>()
+                    .collect::<Vec<Wire<F>>>()
             })
             .map(|multi_collections| {
                 let mut gate_list = gate_list.borrow_mut();
@@ -188,16 +187,16 @@ impl Parser {
             .borrow_mut()
             .last_mut()
             .unwrap()
-            .change_result(Fr::from(0));
+            .change_result(F::zero());
 
         (gate_list.take(), position_map.take())
     }
 
     /// Generate the actual circuit with coordinate pair accumulator
     fn gen_circuit(
-        gate_list: Vec<Gate>,
+        gate_list: Vec<Gate<F>>,
         position_map: HashMap<String, Vec<(usize, usize)>>,
-    ) -> Circuit {
+    ) -> Circuit<F> {
         let mut result = Circuit::default();
         let mut position_map = position_map
             .into_iter()
@@ -215,28 +214,28 @@ impl Parser {
                 .unwrap()
                 .pop()
                 .unwrap();
-            let left = (left.0, left.1, Fr::from(gate.left.value_fr));
+            let left = (left.0, left.1, gate.left.value_fr);
             let right = position_map
                 .get_mut(&gate.right.value_string)
                 .unwrap()
                 .pop()
                 .unwrap();
-            let right = (right.0, right.1, Fr::from(gate.right.value_fr));
+            let right = (right.0, right.1, gate.right.value_fr);
             let bottom = position_map
                 .get_mut(&gate.bottom.value_string)
                 .unwrap()
                 .pop()
                 .unwrap();
-            let bottom = (bottom.0, bottom.1, Fr::from(gate.bottom.value_fr));
+            let bottom = (bottom.0, bottom.1, gate.bottom.value_fr);
             match gate.type_of_circuit {
                 Addition => {
-                    result.add_addition_gate(left, right, bottom, Fr::from(0));
+                    result.add_addition_gate(left, right, bottom, F::zero());
                 }
                 Multiplication => {
-                    result.add_multiplication_gate(left, right, bottom, Fr::from(0));
+                    result.add_multiplication_gate(left, right, bottom, F::zero());
                 }
                 Constant => {
-                    result.add_constant_gate(left, right, bottom, Fr::from(0));
+                    result.add_constant_gate(left, right, bottom, F::zero());
                 }
             }
             #[cfg(test)]
@@ -248,12 +247,12 @@ impl Parser {
     /// Generate an additional gate
     fn generate_additional_gate(
         &self,
-        gate_list: &mut Vec<Gate>,
-        gate_set: &mut HashSet<Gate>,
+        gate_list: &mut Vec<Gate<F>>,
+        gate_set: &mut HashSet<Gate<F>>,
         position_map: &mut HashMap<String, Vec<(usize, usize)>>,
-        left: Wire,
-        right: Wire,
-    ) -> Wire {
+        left: Wire<F>,
+        right: Wire<F>,
+    ) -> Wire<F> {
         let gate_number = gate_list.len();
         let result = Wire::new(
             format!("{}+{}", &left.value_string, &right.value_string),
@@ -279,16 +278,16 @@ impl Parser {
     /// Constant gate ensure the prover send the correct polynomial
     fn generate_constant_gate(
         &self,
-        gate_list: &mut Vec<Gate>,
-        gate_set: &mut HashSet<Gate>,
+        gate_list: &mut Vec<Gate<F>>,
+        gate_set: &mut HashSet<Gate<F>>,
         position_map: &mut HashMap<String, Vec<(usize, usize)>>,
-        value: Wire,
-    ) -> Wire {
+        value: Wire<F>,
+    ) -> Wire<F> {
         let gate_number = gate_list.len();
-        let right = Wire::new("0".to_string(), Fr::zero());
+        let right = Wire::new("0".to_string(), F::zero());
         let result = Wire::new(
             format!("{}+{}", &value.value_string, "0"),
-            value.value_fr + Fr::zero(),
+            value.value_fr + F::zero(),
         );
         let gate = Gate::new(value.clone(), right.clone(), result.clone(), Constant);
         //if this gate already exist, skip this move
@@ -304,15 +303,15 @@ impl Parser {
         result
     }
 
-    /// Get the value of `value` in Fr
+    /// Get the value of `value` in the scalar field
     /// if value is a constant insert a constant gate
     fn get_witness_value(
         &self,
         mut value: &str,
-        gate_list: &mut Vec<Gate>,
-        gate_set: &mut HashSet<Gate>,
+        gate_list: &mut Vec<Gate<F>>,
+        gate_set: &mut HashSet<Gate<F>>,
         position_map: &mut HashMap<String, Vec<(usize, usize)>>,
-    ) -> Fr {
+    ) -> F {
         let mut is_negative = false;
         if &value[..1] == "-" {
             is_negative = true;
@@ -328,15 +327,15 @@ impl Parser {
                 let wire = if is_negative {
                     Wire::new(
                         "-".to_string() + constant.to_string().as_str(),
-                        Fr::from(constant).neg(),
+                        constant_to_field::<F>(constant).neg(),
                     )
                 } else {
-                    Wire::new(constant.to_string(), Fr::from(constant))
+                    Wire::new(constant.to_string(), constant_to_field(constant))
                 };
                 #[cfg(test)]
                 println!("{:?} {}", wire, is_negative);
                 self.generate_constant_gate(gate_list, gate_set, position_map, wire.clone());
-                Fr::from(constant)
+                constant_to_field(constant)
             }
         };
         if is_negative {
@@ -415,11 +414,22 @@ impl Parser {
     }
 }
 
+/// Convert a signed integer constant of the circuit string into a field element
+pub(crate) fn constant_to_field<F: PrimeField>(constant: i32) -> F {
+    let value = F::from(constant.unsigned_abs());
+    if constant < 0 {
+        value.neg()
+    } else {
+        value
+    }
+}
+
 //TODO: implement / operator
 
 #[cfg(test)]
 mod tests {
-    use ark_bls12_381::Fr;
+    use ark_bls12_381::{Bls12_381, Fr};
+    use kzg::scheme::KzgScheme;
     use kzg::srs::Srs;
     use sha2::Sha256;
 
@@ -435,11 +445,11 @@ mod tests {
         parser.add_witness("y", Fr::from(2));
         parser.add_witness("z", Fr::from(3));
         let compiled_circuit = parser.parse("x*y+3*x^2+x*y*z=11").compile().unwrap();
-        let srs = Srs::new(20);
+        let scheme = KzgScheme::new(Srs::<Bls12_381>::new(20));
 
-        let proof = prover::generate_proof::<Sha256>(&compiled_circuit, srs.clone());
+        let proof = prover::generate_proof::<_, Sha256>(&compiled_circuit, &scheme).unwrap();
 
-        assert!(verifier::verify::<Sha256>(&compiled_circuit, srs, proof).is_ok());
+        assert!(verifier::verify::<_, Sha256>(&compiled_circuit, &scheme, proof).is_ok());
     }
 
     /// Test generated circuit with prover
@@ -450,9 +460,9 @@ mod tests {
         parser.add_witness("y", Fr::from(2));
         parser.add_witness("z", Fr::from(3));
         let compiled_circuit = parser.parse("x*y+3*x^2+x*y*z=11").compile().unwrap();
-        let srs = Srs::new(20);
+        let scheme = KzgScheme::new(Srs::<Bls12_381>::new(20));
 
-        let proof = prover::generate_proof::<Sha256>(&compiled_circuit, srs.clone());
+        let proof = prover::generate_proof::<_, Sha256>(&compiled_circuit, &scheme).unwrap();
 
         let mut parser1 = Parser::default();
         parser1.add_witness("x", Fr::from(1));
@@ -460,10 +470,10 @@ mod tests {
         parser1.add_witness("z", Fr::from(4));
         let compiled_circuit1 = parser1.parse("x*y+3*x^2+x*y*z=13").compile().unwrap();
 
-        let proof1 = prover::generate_proof::<Sha256>(&compiled_circuit1, srs.clone());
+        let proof1 = prover::generate_proof::<_, Sha256>(&compiled_circuit1, &scheme).unwrap();
 
-        assert!(verifier::verify::<Sha256>(&compiled_circuit, srs.clone(), proof1).is_err());
-        assert!(verifier::verify::<Sha256>(&compiled_circuit1, srs, proof).is_err());
+        assert!(verifier::verify::<_, Sha256>(&compiled_circuit, &scheme, proof1).is_err());
+        assert!(verifier::verify::<_, Sha256>(&compiled_circuit1, &scheme, proof).is_err());
     }
 
     #[should_panic]
@@ -474,9 +484,9 @@ mod tests {
         parser.add_witness("y", Fr::from(2));
         parser.add_witness("z", Fr::from(3));
         let compiled_circuit = parser.parse("x+y+z=0").compile().unwrap();
-        let srs = Srs::new(20);
+        let scheme = KzgScheme::new(Srs::<Bls12_381>::new(20));
 
-        let _proof = prover::generate_proof::<Sha256>(&compiled_circuit, srs);
+        let _proof = prover::generate_proof::<_, Sha256>(&compiled_circuit, &scheme).unwrap();
     }
 
     /// Test generated circuit with expected circuit
@@ -487,7 +497,7 @@ mod tests {
         parser.add_witness("y", Fr::from(2));
         parser.add_witness("z", Fr::from(3));
         let generated_circuit = parser.parse("x*y+3*x*x+x*y*z=11");
-        let srs = Srs::new(20);
+        let scheme = KzgScheme::new(Srs::<Bls12_381>::new(20));
 
         println!("{:?}", generated_circuit);
 
@@ -560,8 +570,8 @@ mod tests {
         assert_eq!(hand_written_circuit, generated_circuit);
         let compiled_circuit = hand_written_circuit.compile().unwrap();
         //Verify if the handwritten circuit is true
-        let proof = prover::generate_proof::<Sha256>(&compiled_circuit, srs.clone());
-        assert!(verifier::verify::<Sha256>(&compiled_circuit, srs, proof).is_ok());
+        let proof = prover::generate_proof::<_, Sha256>(&compiled_circuit, &scheme).unwrap();
+        assert!(verifier::verify::<_, Sha256>(&compiled_circuit, &scheme, proof).is_ok());
     }
 
     ///Test with a missing witness
@@ -585,17 +595,17 @@ mod tests {
         parser.add_witness("x", Fr::from(-1));
         parser.add_witness("y", Fr::from(-2));
         parser.add_witness("z", Fr::from(-3));
-        let srs = Srs::new(20);
+        let scheme = KzgScheme::new(Srs::<Bls12_381>::new(20));
 
         let compiled_circuit = parser.parse("x*y+3*x*x+x*y*z=-1").compile().unwrap();
-        let proof = prover::generate_proof::<Sha256>(&compiled_circuit, srs.clone());
-        assert!(verifier::verify::<Sha256>(&compiled_circuit, srs, proof).is_ok());
+        let proof = prover::generate_proof::<_, Sha256>(&compiled_circuit, &scheme).unwrap();
+        assert!(verifier::verify::<_, Sha256>(&compiled_circuit, &scheme, proof).is_ok());
     }
 
     /// Test parse_string() function
     #[test]
     fn parse_string_test() {
-        let result = Parser::parse_string("x * y + 3 * x ^ 2 + x * y * z = 11");
+        let result = Parser::<Fr>::parse_string("x * y + 3 * x ^ 2 + x * y * z = 11");
         assert_eq!(result, "x*y+3*x*x+x*y*z=11".to_string());
     }
 
@@ -605,6 +615,6 @@ mod tests {
     #[test]
     #[should_panic]
     fn parse_string_panic_test() {
-        let _result = Parser::parse_string("x * y + 3 * x ^ x + x * y * z=0");
+        let _result = Parser::<Fr>::parse_string("x * y + 3 * x ^ x + x * y * z=0");
     }
 }
@@ -1,8 +1,7 @@
-use std::ops::{Add, Div, Mul};
+use std::ops::{Add, Mul};
 
-use ark_bls12_381::Fr;
-use ark_ff::{Field, UniformRand, Zero};
-use ark_poly::univariate::{DenseOrSparsePolynomial, DensePolynomial};
+use ark_ff::{Field, PrimeField, UniformRand, Zero};
+use ark_poly::univariate::DensePolynomial;
 use ark_poly::{
     DenseUVPolynomial, EvaluationDomain, Evaluations, GeneralEvaluationDomain, Polynomial as Poly,
 };
@@ -11,9 +10,7 @@ use ark_std::rand::SeedableRng;
 use digest::Digest;
 use sha2::Sha256;
 
-use kzg::commitment::KzgCommitment;
-use kzg::scheme::KzgScheme;
-use kzg::srs::Srs;
+use kzg::pcs::HomomorphicCommitmentScheme;
 
 use crate::challenge::ChallengeGenerator;
 use crate::compiled_circuit::CompiledCircuit;
@@ -21,44 +18,45 @@ use crate::slice_polynomial::SlicePoly;
 use crate::types::Polynomial;
 
 /// Struct representing a proof.
-pub struct Proof {
-    pub a_commit: KzgCommitment,
+pub struct Proof<P: HomomorphicCommitmentScheme> {
+    pub a_commit: P::Commitment,
     /// Commitment of wire polynomial b(x)
-    pub b_commit: KzgCommitment,
+    pub b_commit: P::Commitment,
     /// Commitment of wire polynomial c(x)
-    pub c_commit: KzgCommitment,
+    pub c_commit: P::Commitment,
     /// Commitment of permutation polynomial z(x)
-    pub z_commit: KzgCommitment,
+    pub z_commit: P::Commitment,
     /// Commitment of the first part of quotient polynomial t(X)
-    pub t_lo_commit: KzgCommitment,
+    pub t_lo_commit: P::Commitment,
     /// Commitment of the second part of quotient polynomial t(X)
-    pub t_mid_commit: KzgCommitment,
+    pub t_mid_commit: P::Commitment,
     /// Commitment of the third part of quotient polynomial t(X)
-    pub t_hi_commit: KzgCommitment,
-    /// Commitment of opening proof polynomial w_ev_x
-    pub w_ev_x_commit: KzgCommitment,
-    /// Commitment of opening proof polynomial w_ev_wx
-    pub w_ev_wx_commit: KzgCommitment,
+    pub t_hi_commit: P::Commitment,
+    /// Opening of the linearisation and batched polynomials at the evaluation challenge
+    pub w_ev_x_opening: P::Opening,
+    /// Opening of permutation polynomial z(x) at the shifted evaluation challenge
+    pub w_ev_wx_opening: P::Opening,
     /// Opening evaluation of a(x)
-    pub bar_a: Fr,
+    pub bar_a: P::Field,
     /// Opening evaluation of b(x)
-    pub bar_b: Fr,
+    pub bar_b: P::Field,
     /// Opening evaluation of c(x)
-    pub bar_c: Fr,
+    pub bar_c: P::Field,
     /// Opening evaluation of s_sigma_1(x)
-    pub bar_s_sigma_1: Fr,
+    pub bar_s_sigma_1: P::Field,
     /// Opening evaluation of s_sigma_2(x)
-    pub bar_s_sigma_2: Fr,
+    pub bar_s_sigma_2: P::Field,
     /// Opening evaluation of z_w(x)
-    pub bar_z_w: Fr,
-    /// Multipoint evaluation challenge
-    pub u: Fr,
+    pub bar_z_w: P::Field,
     /// Degree of each part of quotient polynomial
     pub degree: usize,
 }
 
 /// Generates a proof for the compiled circuit.
-pub fn generate_proof<T: Digest + Default>(compiled_circuit: &CompiledCircuit, srs: Srs) -> Proof {
+pub fn generate_proof<P: HomomorphicCommitmentScheme, T: Digest + Default>(
+    compiled_circuit: &CompiledCircuit<P::Field>,
+    scheme: &P,
+) -> Result<Proof<P>, String> {
     println!("Generating proof...");
 
     // Round 1
@@ -66,15 +64,14 @@ pub fn generate_proof<T: Digest + Default>(compiled_circuit: &CompiledCircuit, s
     println!("ROUND 1");
 
     let mut rng = StdRng::from_entropy();
-    let scheme = KzgScheme::new(srs);
-    let domain = <GeneralEvaluationDomain<Fr>>::new(compiled_circuit.size).unwrap();
+    let domain = <GeneralEvaluationDomain<P::Field>>::new(compiled_circuit.size).unwrap();
 
-    let b1 = Fr::rand(&mut rng);
-    let b2 = Fr::rand(&mut rng);
-    let b3 = Fr::rand(&mut rng);
-    let b4 = Fr::rand(&mut rng);
-    let b5 = Fr::rand(&mut rng);
-    let b6 = Fr::rand(&mut rng);
+    let b1 = P::Field::rand(&mut rng);
+    let b2 = P::Field::rand(&mut rng);
+    let b3 = P::Field::rand(&mut rng);
+    let b4 = P::Field::rand(&mut rng);
+    let b5 = P::Field::rand(&mut rng);
+    let b6 = P::Field::rand(&mut rng);
 
     let pre1 = DensePolynomial::from_coefficients_vec(vec![b2, b1]);
     let pre2 = DensePolynomial::from_coefficients_vec(vec![b4, b3]);
@@ -89,7 +86,7 @@ pub fn generate_proof<T: Digest + Default>(compiled_circuit: &CompiledCircuit, s
     let cx = pre3.mul_by_vanishing_poly(domain);
     let cx = compiled_circuit.gate_constraints().f_cx().clone().add(cx);
 
-    let [a_commit, b_commit, c_commit] = commit_round1(&ax, &bx, &cx, &scheme);
+    let [a_commit, b_commit, c_commit] = commit_round1(&ax, &bx, &cx, scheme)?;
 
     // Round 2
     #[cfg(test)]
@@ -101,9 +98,9 @@ pub fn generate_proof<T: Digest + Default>(compiled_circuit: &CompiledCircuit, s
     challenge.feed(&c_commit);
     let [beta, gamma] = challenge.generate_challenges();
 
-    let b7 = Fr::rand(&mut rng);
-    let b8 = Fr::rand(&mut rng);
-    let b9 = Fr::rand(&mut rng);
+    let b7 = P::Field::rand(&mut rng);
+    let b8 = P::Field::rand(&mut rng);
+    let b9 = P::Field::rand(&mut rng);
 
     let pre4 = DensePolynomial::from_coefficients_vec(vec![b9, b8, b7]);
     let pre4 = pre4.mul_by_vanishing_poly(domain);
@@ -120,7 +117,7 @@ pub fn generate_proof<T: Digest + Default>(compiled_circuit: &CompiledCircuit, s
     let z_x = pre4 + acc_x;
     let z_wx = pre4w + acc_wx;
 
-    let z_commit = scheme.commit(&z_x);
+    let z_commit = scheme.commit(&z_x).map_err(|error| error.to_string())?;
 
     // check z_x and z_wx
     #[cfg(test)]
@@ -147,7 +144,7 @@ pub fn generate_proof<T: Digest + Default>(compiled_circuit: &CompiledCircuit, s
     );
 
     let slice_poly = SlicePoly::new(tx);
-    let [t_lo_commit, t_mid_commit, t_hi_commit] = slice_poly.commit(&scheme);
+    let [t_lo_commit, t_mid_commit, t_hi_commit] = slice_poly.commit(scheme)?;
 
     // Round 4
     #[cfg(test)]
@@ -180,14 +177,14 @@ pub fn generate_proof<T: Digest + Default>(compiled_circuit: &CompiledCircuit, s
     // Round 5
     #[cfg(test)]
     println!("ROUND 5");
-    challenge.feed(&scheme.commit_para(bar_a));
-    challenge.feed(&scheme.commit_para(bar_b));
-    challenge.feed(&scheme.commit_para(bar_c));
-    challenge.feed(&scheme.commit_para(bar_s_sigma_1));
-    challenge.feed(&scheme.commit_para(bar_s_sigma_2));
-    challenge.feed(&scheme.commit_para(bar_z_w));
-
-    let [v] = challenge.generate_challenges();
+    challenge.feed(&bar_a);
+    challenge.feed(&bar_b);
+    challenge.feed(&bar_c);
+    challenge.feed(&bar_s_sigma_1);
+    challenge.feed(&bar_s_sigma_2);
+    challenge.feed(&bar_z_w);
+
+    let [v]: [P::Field; 1] = challenge.generate_challenges();
     let r_x = compute_linearisation_polynomial(
         &beta,
         &gamma,
@@ -209,69 +206,31 @@ pub fn generate_proof<T: Digest + Default>(compiled_circuit: &CompiledCircuit, s
         &domain,
         compiled_circuit,
     );
-    let bar_r = r_x.evaluate(&evaluation_challenge);
-
-    let w_ev_x = poly_sub_para(&r_x, &bar_r)
-        + poly_sub_para(&ax, &bar_a).mul(v)
-        + poly_sub_para(&bx, &bar_b).mul(v.square())
-        + poly_sub_para(&cx, &bar_c).mul(v * v * v)
-        + poly_sub_para(
-            compiled_circuit.copy_constraints().s_sigma_1(),
-            &bar_s_sigma_1,
-        )
-        .mul(v * v * v * v)
-        + poly_sub_para(
-            compiled_circuit.copy_constraints().s_sigma_2(),
-            &bar_s_sigma_2,
-        )
-        .mul(v * v * v * v * v);
-
-    // Check w_ev_x
-    {
-        let cur = DensePolynomial::from_coefficients_vec(vec![-evaluation_challenge, Fr::from(1)]);
-        let a = DenseOrSparsePolynomial::from(w_ev_x.clone());
-        let b = DenseOrSparsePolynomial::from(cur);
-        let div = a.divide_with_q_and_r(&b).expect("division failed");
-        assert_eq!(
-            div.1,
-            DensePolynomial::from_coefficients_vec(vec![]),
-            "w_ev_x was computed incorrectly"
-        );
-    }
-
-    let w_ev_x = w_ev_x.div(&DensePolynomial::from_coefficients_vec(vec![
-        -evaluation_challenge,
-        Fr::from(1),
-    ]));
-    let w_ev_wx = poly_sub_para(&z_x, &bar_z_w);
-
-    // Check w_ev_wx
-    {
-        let cur =
-            DensePolynomial::from_coefficients_vec(vec![-evaluation_challenge * w, Fr::from(1)]);
-        let a = DenseOrSparsePolynomial::from(w_ev_wx.clone());
-        let b = DenseOrSparsePolynomial::from(cur);
-        let div = a.divide_with_q_and_r(&b).expect("division failed");
-        assert_eq!(
-            div.1,
-            DensePolynomial::from_coefficients_vec(vec![]),
-            "w_ev_wx was computed incorrectly"
-        );
-    }
-
-    let w_ev_wx = w_ev_wx.div(&DensePolynomial::from_coefficients_vec(vec![
-        -evaluation_challenge * w,
-        Fr::from(1),
-    ]));
-
-    let w_ev_x_commit = scheme.commit(&w_ev_x);
-    let w_ev_wx_commit = scheme.commit(&w_ev_wx);
-
-    challenge.feed(&w_ev_x_commit);
-    challenge.feed(&w_ev_wx_commit);
-    let [u] = challenge.generate_challenges();
-
-    Proof {
+    // r(x) vanishes at the evaluation challenge
+    #[cfg(test)]
+    assert!(r_x.evaluate(&evaluation_challenge).is_zero());
+
+    let w_ev_x = r_x
+        + ax.mul(v)
+        + bx.mul(v.square())
+        + cx.mul(v * v * v)
+        + compiled_circuit
+            .copy_constraints()
+            .s_sigma_1()
+            .mul(v * v * v * v)
+        + compiled_circuit
+            .copy_constraints()
+            .s_sigma_2()
+            .mul(v * v * v * v * v);
+
+    let w_ev_x_opening = scheme
+        .open(&w_ev_x, evaluation_challenge)
+        .map_err(|error| error.to_string())?;
+    let w_ev_wx_opening = scheme
+        .open(&z_x, evaluation_challenge * w)
+        .map_err(|error| error.to_string())?;
+
+    Ok(Proof {
         a_commit,
         b_commit,
         c_commit,
@@ -279,34 +238,26 @@ pub fn generate_proof<T: Digest + Default>(compiled_circuit: &CompiledCircuit, s
         t_lo_commit,
         t_mid_commit,
         t_hi_commit,
-        w_ev_x_commit,
-        w_ev_wx_commit,
+        w_ev_x_opening,
+        w_ev_wx_opening,
         bar_a,
         bar_b,
         bar_c,
         bar_s_sigma_1,
         bar_s_sigma_2,
         bar_z_w,
-        u,
         degree: slice_poly.get_degree(),
-    }
-}
-
-/// Subtracts a parameter from a polynomial.
-fn poly_sub_para(poly: &Polynomial, para: &Fr) -> Polynomial {
-    let mut tmp = poly.clone();
-    tmp.coeffs[0] -= para;
-    tmp
+    })
 }
 
-fn compute_acc(
-    beta: &Fr,
-    gamma: &Fr,
-    domain: &GeneralEvaluationDomain<Fr>,
-    compiled_circuit: &CompiledCircuit,
-) -> (Polynomial, Polynomial) {
-    let mut acc_e = vec![Fr::from(1)];
-    let mut pre_acc_e = Fr::from(1);
+fn compute_acc<F: PrimeField>(
+    beta: &F,
+    gamma: &F,
+    domain: &GeneralEvaluationDomain<F>,
+    compiled_circuit: &CompiledCircuit<F>,
+) -> (Polynomial<F>, Polynomial<F>) {
+    let mut acc_e = vec![F::one()];
+    let mut pre_acc_e = F::one();
     let roots = domain.elements().collect::<Vec<_>>();
     let k1 = compiled_circuit.copy_constraints().k1();
     let k2 = compiled_circuit.copy_constraints().k2();
@@ -378,18 +329,18 @@ fn compute_acc(
 
 /// Computes the accumulator polynomials `acc(x)` and `acc(w*x)` for a given beta, gamma, evaluation domain, and compiled circuit.
 #[allow(clippy::too_many_arguments)]
-fn compute_quotient_polynomial(
-    beta: &Fr,
-    gamma: &Fr,
-    alpha: &Fr,
-    ax: &Polynomial,
-    bx: &Polynomial,
-    cx: &Polynomial,
-    z_x: &Polynomial,
-    z_wx: &Polynomial,
-    domain: &GeneralEvaluationDomain<Fr>,
-    compiled_circuit: &CompiledCircuit,
-) -> Polynomial {
+fn compute_quotient_polynomial<F: PrimeField>(
+    beta: &F,
+    gamma: &F,
+    alpha: &F,
+    ax: &Polynomial<F>,
+    bx: &Polynomial<F>,
+    cx: &Polynomial<F>,
+    z_x: &Polynomial<F>,
+    z_wx: &Polynomial<F>,
+    domain: &GeneralEvaluationDomain<F>,
+    compiled_circuit: &CompiledCircuit<F>,
+) -> Polynomial<F> {
     let k1 = compiled_circuit.copy_constraints().k1();
     let k2 = compiled_circuit.copy_constraints().k2();
 
@@ -433,7 +384,7 @@ fn compute_quotient_polynomial(
     let line4 = {
         let l1 = l1_poly(domain);
         let mut zx2 = z_x.clone();
-        zx2.coeffs[0] -= Fr::from(1);
+        zx2.coeffs[0] -= F::one();
         zx2.mul(&l1).mul(alpha.square())
     };
 
@@ -443,10 +394,10 @@ fn compute_quotient_polynomial(
     quotient1 + quotient23 + quotient4
 }
 
-fn divide_by_vanishing_poly<'a>(
-    poly: &Polynomial,
-    domain: &GeneralEvaluationDomain<Fr>,
-) -> Result<Polynomial, &'a str> {
+fn divide_by_vanishing_poly<'a, F: PrimeField>(
+    poly: &Polynomial<F>,
+    domain: &GeneralEvaluationDomain<F>,
+) -> Result<Polynomial<F>, &'a str> {
     let (result, rest) = poly.divide_by_vanishing_poly(*domain).unwrap();
     if !rest.is_zero() {
         return Err("has remainder");
@@ -456,37 +407,37 @@ fn divide_by_vanishing_poly<'a>(
 
 /// Divides a polynomial by the vanishing polynomial of the given domain.
 /// Returns the quotient polynomial if the division is successful, otherwise returns an error indicating a remainder.
-pub(crate) fn l1_poly(domain: &GeneralEvaluationDomain<Fr>) -> Polynomial {
+pub(crate) fn l1_poly<F: PrimeField>(domain: &GeneralEvaluationDomain<F>) -> Polynomial<F> {
     let n = domain.size();
-    let mut l1_e = vec![Fr::from(0); n];
-    l1_e[0] = Fr::from(1);
+    let mut l1_e = vec![F::zero(); n];
+    l1_e[0] = F::one();
     Evaluations::from_vec_and_domain(l1_e, *domain).interpolate()
 }
 
 /// Computes the linearization polynomial for the proof generation.
 /// This function computes various terms involving the provided parameters and polynomials.
 #[allow(clippy::too_many_arguments)]
-fn compute_linearisation_polynomial(
-    beta: &Fr,
-    gamma: &Fr,
-    alpha: &Fr,
-    eval_challenge: &Fr,
-    bar_a: &Fr,
-    bar_b: &Fr,
-    bar_c: &Fr,
-    bar_s_sigma_1: &Fr,
-    bar_s_sigma_2: &Fr,
-    bar_z_w: &Fr,
-    pi_e: &Fr,
-    tx_compact: &Polynomial,
-    z_x: &Polynomial,
-    ax: &Polynomial,
-    bx: &Polynomial,
-    cx: &Polynomial,
-    z_wx: &Polynomial,
-    domain: &GeneralEvaluationDomain<Fr>,
-    compiled_circuit: &CompiledCircuit,
-) -> Polynomial {
+fn compute_linearisation_polynomial<F: PrimeField>(
+    beta: &F,
+    gamma: &F,
+    alpha: &F,
+    eval_challenge: &F,
+    bar_a: &F,
+    bar_b: &F,
+    bar_c: &F,
+    bar_s_sigma_1: &F,
+    bar_s_sigma_2: &F,
+    bar_z_w: &F,
+    pi_e: &F,
+    tx_compact: &Polynomial<F>,
+    z_x: &Polynomial<F>,
+    ax: &Polynomial<F>,
+    bx: &Polynomial<F>,
+    cx: &Polynomial<F>,
+    z_wx: &Polynomial<F>,
+    domain: &GeneralEvaluationDomain<F>,
+    compiled_circuit: &CompiledCircuit<F>,
+) -> Polynomial<F> {
     let mut line1 = compiled_circuit
         .gate_constraints()
         .q_mx()
@@ -555,7 +506,7 @@ fn compute_linearisation_polynomial(
     let line4 = {
         let l1_e = l1_poly(domain).evaluate(eval_challenge);
         let mut zx2 = z_x.clone();
-        zx2.coeffs[0] -= Fr::from(1);
+        zx2.coeffs[0] -= F::one();
         zx2.mul(l1_e).mul(alpha.square())
     };
 
@@ -568,14 +519,14 @@ fn compute_linearisation_polynomial(
 }
 
 /// Computes the commitments for round 1 of the proof generation process.
-fn commit_round1(
-    ax: &Polynomial,
-    bx: &Polynomial,
-    cx: &Polynomial,
-    scheme: &KzgScheme,
-) -> [KzgCommitment; 3] {
-    let c_ax = scheme.commit(ax);
-    let c_bx = scheme.commit(bx);
-    let c_cx = scheme.commit(cx);
-    [c_ax, c_bx, c_cx]
+fn commit_round1<P: HomomorphicCommitmentScheme>(
+    ax: &Polynomial<P::Field>,
+    bx: &Polynomial<P::Field>,
+    cx: &Polynomial<P::Field>,
+    scheme: &P,
+) -> Result<[P::Commitment; 3], String> {
+    let c_ax = scheme.commit(ax).map_err(|error| error.to_string())?;
+    let c_bx = scheme.commit(bx).map_err(|error| error.to_string())?;
+    let c_cx = scheme.commit(cx).map_err(|error| error.to_string())?;
+    Ok([c_ax, c_bx, c_cx])
 }
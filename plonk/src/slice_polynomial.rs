@@ -1,25 +1,23 @@
 use std::ops::Mul;
 
-use ark_bls12_381::Fr;
-use ark_ff::{One, Zero};
+use ark_ff::{PrimeField, Zero};
 use ark_poly::univariate::SparsePolynomial;
 use ark_poly::{DenseUVPolynomial, Polynomial as Poly};
 
-use kzg::commitment::KzgCommitment;
-use kzg::scheme::KzgScheme;
+use kzg::pcs::PolynomialCommitmentScheme;
 
 use crate::types::Polynomial;
 
 /// Struct representing a slice polynomial.
 #[derive(Debug)]
-pub(crate) struct SlicePoly {
-    slices: [Polynomial; 3],
+pub(crate) struct SlicePoly<F: PrimeField> {
+    slices: [Polynomial<F>; 3],
     degree: usize,
 }
 
-impl SlicePoly {
+impl<F: PrimeField> SlicePoly<F> {
     /// Creates a new slice polynomial with the given polynomial and degree.
-    pub fn new(polynomial: Polynomial) -> Self {
+    pub fn new(polynomial: Polynomial<F>) -> Self {
         let coefficients = polynomial.coeffs;
 
         let mut tmp = coefficients.len() / 3;
@@ -27,7 +25,7 @@ impl SlicePoly {
             tmp += 1;
         }
 
-        let mut slices = [(); 3].map(|_| Polynomial::zero());
+        let mut slices = [(); 3].map(|_| Polynomial::<F>::zero());
         coefficients
             .chunks(tmp)
             .map(Polynomial::from_coefficients_slice)
@@ -47,20 +45,28 @@ impl SlicePoly {
         self.degree
     }
 
-    /// Commits to each slice polynomial using the provided KZG scheme.
-    pub fn commit(&self, scheme: &KzgScheme) -> [KzgCommitment; 3] {
-        self.slices.clone().map(|slice| scheme.commit(&slice))
+    /// Commits to each slice polynomial using the provided commitment scheme.
+    pub fn commit<P: PolynomialCommitmentScheme<Field = F>>(
+        &self,
+        scheme: &P,
+    ) -> Result<[P::Commitment; 3], String> {
+        let [lo, mid, hi] = &self.slices;
+        Ok([
+            scheme.commit(lo).map_err(|error| error.to_string())?,
+            scheme.commit(mid).map_err(|error| error.to_string())?,
+            scheme.commit(hi).map_err(|error| error.to_string())?,
+        ])
     }
 
     /// Compacts the slice polynomial at the given point.
-    pub fn compact(&self, point: &Fr) -> Polynomial {
+    pub fn compact(&self, point: &F) -> Polynomial<F> {
         self.slices
             .iter()
             .enumerate()
             .map(|(index, slice)| {
                 let exponent = SparsePolynomial::from_coefficients_slice(&[(
                     (self.degree + 1) * index,
-                    Fr::one(),
+                    F::one(),
                 )]);
                 slice.mul(exponent.evaluate(point))
             })
@@ -71,6 +77,7 @@ impl SlicePoly {
 
 #[cfg(test)]
 mod tests {
+    use ark_bls12_381::Fr;
     use ark_ff::{Field, UniformRand};
     use ark_std::test_rng;
 
@@ -81,7 +88,7 @@ mod tests {
         let rng = &mut test_rng();
         let degree = 3;
         let coeffs: Vec<Fr> = (0..12).map(|_| Fr::rand(rng)).collect();
-        let poly = Polynomial::from_coefficients_vec(coeffs.clone());
+        let poly = Polynomial::<Fr>::from_coefficients_vec(coeffs.clone());
 
         let slice_poly = SlicePoly::new(poly.clone());
         assert_eq!(slice_poly.get_degree(), degree);
@@ -1,6 +1,6 @@
-use ark_bls12_381::{Fr, G1Affine, G2Affine};
+use ark_ec::pairing::Pairing;
 use ark_poly::univariate::DensePolynomial;
 
-pub type G1Point = G1Affine;
-pub type G2Point = G2Affine;
-pub type Polynomial = DensePolynomial<Fr>;
+pub type G1Point<E> = <E as Pairing>::G1Affine;
+pub type G2Point<E> = <E as Pairing>::G2Affine;
+pub type Polynomial<F> = DensePolynomial<F>;
@@ -1,14 +1,11 @@
 use std::ops::Mul;
 
-use ark_bls12_381::{Bls12_381, Fr};
-use ark_ec::pairing::Pairing;
-use ark_ff::{BigInt, Field};
-use ark_poly::{EvaluationDomain, GeneralEvaluationDomain, Polynomial};
+use ark_ff::{Field, One};
+use ark_poly::univariate::DensePolynomial;
+use ark_poly::{DenseUVPolynomial, EvaluationDomain, GeneralEvaluationDomain, Polynomial};
 use digest::Digest;
 
-use kzg::commitment::KzgCommitment;
-use kzg::scheme::KzgScheme;
-use kzg::srs::Srs;
+use kzg::pcs::HomomorphicCommitmentScheme;
 
 use crate::challenge::ChallengeGenerator;
 use crate::compiled_circuit::CompiledCircuit;
@@ -16,35 +13,30 @@ use crate::prover::Proof;
 
 /// Verifies a zero-knowledge proof for a compiled circuit.
 ///
-pub fn verify<T: Digest + Default>(
-    compiled_circuit: &CompiledCircuit,
-    srs: Srs,
-    proof: Proof,
+pub fn verify<P: HomomorphicCommitmentScheme, T: Digest + Default>(
+    compiled_circuit: &CompiledCircuit<P::Field>,
+    scheme: &P,
+    proof: Proof<P>,
 ) -> Result<(), String> {
     println!("Verifying...");
 
     #[cfg(test)]
     println!("Precompute");
 
-    let scheme = KzgScheme::new(srs.clone());
-
     let (q_m_c, q_l_c, q_r_c, q_o_c, q_c_c, s_sigma_1_c, s_sigma_2_c, s_sigma_3_c) =
-        get_circuit_commitment(compiled_circuit, &scheme);
+        get_circuit_commitment(compiled_circuit, scheme)?;
 
     #[cfg(test)]
     println!("Verify challenges");
-    let (alpha, beta, gamma, evaluation_challenge, v, u) = verify_challenges::<T>(&proof, &scheme);
-
-    if u != proof.u {
-        return Err(String::from("Verify: Challenge verification failed."));
-    }
+    let (alpha, beta, gamma, evaluation_challenge, v) = verify_challenges::<P, T>(&proof);
 
-    let domain = <GeneralEvaluationDomain<Fr>>::new(compiled_circuit.size).unwrap();
+    let domain = <GeneralEvaluationDomain<P::Field>>::new(compiled_circuit.size).unwrap();
     let w = domain.element(1);
 
-    let z_h_e = evaluation_challenge.pow(BigInt::new([domain.size() as u64])) - Fr::from(1);
-    let l_1_e =
-        z_h_e / (Fr::from(compiled_circuit.size as u128) * (evaluation_challenge - Fr::from(1)));
+    let z_h_e = evaluation_challenge.pow([domain.size() as u64]) - P::Field::one();
+    let l_1_e = z_h_e
+        / (P::Field::from(compiled_circuit.size as u128)
+            * (evaluation_challenge - P::Field::one()));
     let p_i_e = compiled_circuit
         .gate_constraints()
         .pi_x()
@@ -69,7 +61,7 @@ pub fn verify<T: Digest + Default>(
         + q_o_c.mul(proof.bar_c)
         + q_c_c;
 
-    let d_line2 = proof.z_commit.mul(
+    let d_line2 = proof.z_commit.clone().mul(
         (proof.bar_a + beta * evaluation_challenge + gamma)
             * (proof.bar_b
                 + beta * compiled_circuit.copy_constraints().k1() * evaluation_challenge
@@ -78,8 +70,7 @@ pub fn verify<T: Digest + Default>(
                 + beta * compiled_circuit.copy_constraints().k2() * evaluation_challenge
                 + gamma)
             * alpha
-            + l_1_e * alpha * alpha
-            + u,
+            + l_1_e * alpha * alpha,
     );
 
     let d_line3 = s_sigma_3_c.mul(
@@ -93,10 +84,10 @@ pub fn verify<T: Digest + Default>(
     let d_line4 = (proof.t_lo_commit
         + proof
             .t_mid_commit
-            .mul(evaluation_challenge.pow(BigInt::new([proof.degree as u64 + 1])))
+            .mul(evaluation_challenge.pow([proof.degree as u64 + 1]))
         + proof
             .t_hi_commit
-            .mul(evaluation_challenge.pow(BigInt::new([proof.degree as u64 * 2 + 2]))))
+            .mul(evaluation_challenge.pow([proof.degree as u64 * 2 + 2])))
     .mul(z_h_e);
 
     let d = d_line1 + d_line2 - d_line3 - d_line4;
@@ -104,51 +95,43 @@ pub fn verify<T: Digest + Default>(
     #[cfg(test)]
     println!("Compute [F]");
 
+    // [D] misses the constant term r_0 of the linearisation polynomial r(x)
+    let r_0_c = scheme
+        .commit(&DensePolynomial::from_coefficients_slice(&[r_0]))
+        .map_err(|error| error.to_string())?;
     let f = d
-        + proof.a_commit.mul(v)
-        + proof.b_commit.mul(v * v)
-        + proof.c_commit.mul(v * v * v)
-        + s_sigma_1_c.mul(v * v * v * v)
-        + s_sigma_2_c.mul(v * v * v * v * v);
+        + r_0_c
+        + proof.a_commit.clone() * v
+        + proof.b_commit.clone() * (v * v)
+        + proof.c_commit.clone() * (v * v * v)
+        + s_sigma_1_c * (v * v * v * v)
+        + s_sigma_2_c * (v * v * v * v * v);
 
     #[cfg(test)]
-    println!("Compute [E]");
-    let e = -r_0
-        + v * proof.bar_a
+    println!("Compute E");
+    // r(x) vanishes at the evaluation challenge
+    let e = v * proof.bar_a
         + v * v * proof.bar_b
         + v * v * v * proof.bar_c
         + v * v * v * v * proof.bar_s_sigma_1
-        + v * v * v * v * v * proof.bar_s_sigma_2
-        + u * proof.bar_z_w;
-    let e = scheme.commit_para(e);
-
-    #[cfg(test)]
-    println!("Compute left side of paring");
-
-    let pairing_left_side = Bls12_381::pairing(
-        (proof.w_ev_x_commit.clone() + proof.w_ev_wx_commit.clone().mul(u)).0,
-        srs.g2s(),
-    );
-
-    #[cfg(test)]
-    println!("Compute right side of paring");
-    let pairing_right_side = Bls12_381::pairing(
-        (proof.w_ev_x_commit.clone().mul(evaluation_challenge)
-            + proof
-                .w_ev_wx_commit
-                .clone()
-                .mul(u * evaluation_challenge * w)
-            + f
-            - e)
-            .0,
-        srs.g2(),
-    );
+        + v * v * v * v * v * proof.bar_s_sigma_2;
 
     #[cfg(test)]
-    println!("Check pairing");
+    println!("Check openings");
 
-    if pairing_left_side != pairing_right_side {
-        return Err(String::from("Verify: Pairing failed, rejected"));
+    if P::evaluation(&proof.w_ev_x_opening) != e
+        || !scheme.verify(&f, &proof.w_ev_x_opening, evaluation_challenge)
+    {
+        return Err(String::from("Verify: Opening of [F] failed, rejected"));
+    }
+    if P::evaluation(&proof.w_ev_wx_opening) != proof.bar_z_w
+        || !scheme.verify(
+            &proof.z_commit,
+            &proof.w_ev_wx_opening,
+            evaluation_challenge * w,
+        )
+    {
+        return Err(String::from("Verify: Opening of [z] failed, rejected"));
     }
 
     println!("Accepted!!!");
@@ -157,38 +140,58 @@ pub fn verify<T: Digest + Default>(
 }
 
 /// Gets commitments of the circuit via compiled_circuit
-fn get_circuit_commitment(
-    compiled_circuit: &CompiledCircuit,
-    scheme: &KzgScheme,
-) -> (
-    KzgCommitment,
-    KzgCommitment,
-    KzgCommitment,
-    KzgCommitment,
-    KzgCommitment,
-    KzgCommitment,
-    KzgCommitment,
-    KzgCommitment,
-) {
-    let q_m_c = scheme.commit(compiled_circuit.gate_constraints().q_mx());
-    let q_l_c = scheme.commit(compiled_circuit.gate_constraints().q_lx());
-    let q_r_c = scheme.commit(compiled_circuit.gate_constraints().q_rx());
-    let q_o_c = scheme.commit(compiled_circuit.gate_constraints().q_ox());
-    let q_c_c = scheme.commit(compiled_circuit.gate_constraints().q_cx());
-    let s_sigma1_c = scheme.commit(compiled_circuit.copy_constraints().s_sigma_1());
-    let s_sigma2_c = scheme.commit(compiled_circuit.copy_constraints().s_sigma_2());
-    let s_sigma3_c = scheme.commit(compiled_circuit.copy_constraints().s_sigma_3());
-
+#[allow(clippy::type_complexity)]
+fn get_circuit_commitment<P: HomomorphicCommitmentScheme>(
+    compiled_circuit: &CompiledCircuit<P::Field>,
+    scheme: &P,
+) -> Result<
     (
+        P::Commitment,
+        P::Commitment,
+        P::Commitment,
+        P::Commitment,
+        P::Commitment,
+        P::Commitment,
+        P::Commitment,
+        P::Commitment,
+    ),
+    String,
+> {
+    let q_m_c = scheme
+        .commit(compiled_circuit.gate_constraints().q_mx())
+        .map_err(|error| error.to_string())?;
+    let q_l_c = scheme
+        .commit(compiled_circuit.gate_constraints().q_lx())
+        .map_err(|error| error.to_string())?;
+    let q_r_c = scheme
+        .commit(compiled_circuit.gate_constraints().q_rx())
+        .map_err(|error| error.to_string())?;
+    let q_o_c = scheme
+        .commit(compiled_circuit.gate_constraints().q_ox())
+        .map_err(|error| error.to_string())?;
+    let q_c_c = scheme
+        .commit(compiled_circuit.gate_constraints().q_cx())
+        .map_err(|error| error.to_string())?;
+    let s_sigma1_c = scheme
+        .commit(compiled_circuit.copy_constraints().s_sigma_1())
+        .map_err(|error| error.to_string())?;
+    let s_sigma2_c = scheme
+        .commit(compiled_circuit.copy_constraints().s_sigma_2())
+        .map_err(|error| error.to_string())?;
+    let s_sigma3_c = scheme
+        .commit(compiled_circuit.copy_constraints().s_sigma_3())
+        .map_err(|error| error.to_string())?;
+
+    Ok((
         q_m_c, q_l_c, q_r_c, q_o_c, q_c_c, s_sigma1_c, s_sigma2_c, s_sigma3_c,
-    )
+    ))
 }
 
-/// Verifies Fiat-Shamir challenges.
-fn verify_challenges<T: Digest + Default>(
-    proof: &Proof,
-    scheme: &KzgScheme,
-) -> (Fr, Fr, Fr, Fr, Fr, Fr) {
+/// Recomputes the Fiat-Shamir challenges.
+#[allow(clippy::type_complexity)]
+fn verify_challenges<P: HomomorphicCommitmentScheme, T: Digest + Default>(
+    proof: &Proof<P>,
+) -> (P::Field, P::Field, P::Field, P::Field, P::Field) {
     let commitments = [
         proof.a_commit.clone(),
         proof.b_commit.clone(),
@@ -203,20 +206,15 @@ fn verify_challenges<T: Digest + Default>(
     challenge.feed(&proof.t_hi_commit);
     let [evaluation_challenge] = challenge.generate_challenges();
 
-    challenge.feed(&scheme.commit_para(proof.bar_a));
-    challenge.feed(&scheme.commit_para(proof.bar_b));
-    challenge.feed(&scheme.commit_para(proof.bar_c));
-    challenge.feed(&scheme.commit_para(proof.bar_s_sigma_1));
-    challenge.feed(&scheme.commit_para(proof.bar_s_sigma_2));
-    challenge.feed(&scheme.commit_para(proof.bar_z_w));
+    challenge.feed(&proof.bar_a);
+    challenge.feed(&proof.bar_b);
+    challenge.feed(&proof.bar_c);
+    challenge.feed(&proof.bar_s_sigma_1);
+    challenge.feed(&proof.bar_s_sigma_2);
+    challenge.feed(&proof.bar_z_w);
     let [v] = challenge.generate_challenges();
 
-    challenge.feed(&proof.w_ev_x_commit);
-    challenge.feed(&proof.w_ev_wx_commit);
-
-    let [u] = challenge.generate_challenges();
-
-    (alpha, beta, gamma, evaluation_challenge, v, u)
+    (alpha, beta, gamma, evaluation_challenge, v)
 }
 
 #[cfg(test)]
@@ -226,6 +224,12 @@ mod tests {
     use crate::circuit::Circuit;
     use crate::prover::generate_proof;
 
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_bn254::Bn254;
+    use kzg::ipa::{IpaParams, IpaScheme};
+    use kzg::scheme::KzgScheme;
+    use kzg::srs::Srs;
+
     use super::*;
 
     #[test]
@@ -259,10 +263,10 @@ mod tests {
 
         let compiled_circuit = circuit.compile().unwrap();
 
-        let srs = Srs::new(compiled_circuit.size);
+        let scheme = KzgScheme::new(Srs::<Bls12_381>::new(compiled_circuit.size));
 
-        let proof = generate_proof::<Sha256>(&compiled_circuit, srs.clone());
-        assert!(verify::<Sha256>(&compiled_circuit, srs, proof).is_ok());
+        let proof = generate_proof::<_, Sha256>(&compiled_circuit, &scheme).unwrap();
+        assert!(verify::<_, Sha256>(&compiled_circuit, &scheme, proof).is_ok());
     }
 
     #[test]
@@ -296,10 +300,10 @@ mod tests {
         );
         let compiled_circuit = circuit.compile().unwrap();
 
-        let srs = Srs::new(compiled_circuit.size);
+        let scheme = KzgScheme::new(Srs::<Bls12_381>::new(compiled_circuit.size));
 
-        let proof = generate_proof::<Sha256>(&compiled_circuit, srs.clone());
-        assert!(verify::<Sha256>(&compiled_circuit, srs, proof).is_ok());
+        let proof = generate_proof::<_, Sha256>(&compiled_circuit, &scheme).unwrap();
+        assert!(verify::<_, Sha256>(&compiled_circuit, &scheme, proof).is_ok());
     }
 
     #[test]
@@ -351,10 +355,10 @@ mod tests {
         );
         let compiled_circuit = circuit.compile().unwrap();
 
-        let srs = Srs::new(compiled_circuit.size);
+        let scheme = KzgScheme::new(Srs::<Bls12_381>::new(compiled_circuit.size));
 
-        let proof = generate_proof::<Sha256>(&compiled_circuit, srs.clone());
-        assert!(verify::<Sha256>(&compiled_circuit, srs, proof).is_ok());
+        let proof = generate_proof::<_, Sha256>(&compiled_circuit, &scheme).unwrap();
+        assert!(verify::<_, Sha256>(&compiled_circuit, &scheme, proof).is_ok());
     }
 
     #[test]
@@ -375,9 +379,57 @@ mod tests {
         );
         let compiled_circuit = circuit.compile().unwrap();
 
-        let srs = Srs::new(compiled_circuit.size);
+        let scheme = KzgScheme::new(Srs::<Bls12_381>::new(compiled_circuit.size));
+
+        let proof = generate_proof::<_, Sha256>(&compiled_circuit, &scheme).unwrap();
+        assert!(verify::<_, Sha256>(&compiled_circuit, &scheme, proof).is_ok());
+    }
+
+    #[test]
+    fn verifier_accepted_test_bn254() {
+        // check xyz = 6 over the BN254 curve
+        let mut circuit = Circuit::default();
+        circuit.add_multiplication_gate(
+            (0, 0, ark_bn254::Fr::from(1)),
+            (1, 0, ark_bn254::Fr::from(2)),
+            (0, 1, ark_bn254::Fr::from(2)),
+            ark_bn254::Fr::from(0),
+        );
+        circuit.add_multiplication_gate(
+            (2, 0, ark_bn254::Fr::from(2)),
+            (1, 1, ark_bn254::Fr::from(3)),
+            (2, 1, ark_bn254::Fr::from(6)),
+            ark_bn254::Fr::from(0),
+        );
+        let compiled_circuit = circuit.compile().unwrap();
+
+        let scheme = KzgScheme::new(Srs::<Bn254>::new(compiled_circuit.size));
+
+        let proof = generate_proof::<_, Sha256>(&compiled_circuit, &scheme).unwrap();
+        assert!(verify::<_, Sha256>(&compiled_circuit, &scheme, proof).is_ok());
+    }
+
+    #[test]
+    fn verifier_accepted_ipa_test() {
+        // check xyz = 6 without a trusted setup
+        let mut circuit = Circuit::default();
+        circuit.add_multiplication_gate(
+            (0, 0, Fr::from(1)),
+            (1, 0, Fr::from(2)),
+            (0, 1, Fr::from(2)),
+            Fr::from(0),
+        );
+        circuit.add_multiplication_gate(
+            (2, 0, Fr::from(2)),
+            (1, 1, Fr::from(3)),
+            (2, 1, Fr::from(6)),
+            Fr::from(0),
+        );
+        let compiled_circuit = circuit.compile().unwrap();
+
+        let scheme = IpaScheme::new(IpaParams::<Bls12_381>::new(compiled_circuit.size + 2));
 
-        let proof = generate_proof::<Sha256>(&compiled_circuit, srs.clone());
-        assert!(verify::<Sha256>(&compiled_circuit, srs, proof).is_ok());
+        let proof = generate_proof::<_, Sha256>(&compiled_circuit, &scheme).unwrap();
+        assert!(verify::<_, Sha256>(&compiled_circuit, &scheme, proof).is_ok());
     }
 }